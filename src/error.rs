@@ -0,0 +1,50 @@
+use std::error::Error;
+use std::fmt;
+
+/// The errors returned by the fallible routines of this crate.
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuantError {
+    /// An input array's shape differs from the shape of the other inputs.
+    ShapeMismatch { expected: Vec<usize>, got: Vec<usize> },
+    /// A target value lies outside the range a root finder's bracket can reach.
+    NoBracket,
+    /// An iterative solver failed to converge within its iteration budget.
+    NoConvergence,
+    /// An input lies outside the domain of the computation.
+    InvalidInput(String),
+    /// A matrix that must be symmetric positive definite is not.
+    NotPositiveDefinite,
+    /// Reading or parsing external data failed.
+    Io(String),
+}
+
+impl QuantError {
+    /// Check that an input has the `expected` shape.
+    pub(crate) fn check_shape(expected: &[usize], got: &[usize]) -> Result<(), QuantError> {
+        if expected == got {
+            Ok(())
+        } else {
+            Err(QuantError::ShapeMismatch {
+                expected: expected.to_vec(),
+                got: got.to_vec(),
+            })
+        }
+    }
+}
+
+impl fmt::Display for QuantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuantError::ShapeMismatch { expected, got } => {
+                write!(f, "shape mismatch: expected {:?}, got {:?}", expected, got)
+            }
+            QuantError::NoBracket => write!(f, "target is not bracketed by the search interval"),
+            QuantError::NoConvergence => write!(f, "solver did not converge"),
+            QuantError::InvalidInput(reason) => write!(f, "invalid input: {}", reason),
+            QuantError::NotPositiveDefinite => write!(f, "matrix is not positive definite"),
+            QuantError::Io(reason) => write!(f, "io error: {}", reason),
+        }
+    }
+}
+
+impl Error for QuantError {}
@@ -0,0 +1,171 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use crate::error::QuantError;
+use crate::numerics::root::bisect;
+
+/// The yield sensitivity of a bond's price.
+///
+/// * `macaulay`: The present value weighted mean time of the cash flows, in years.
+/// * `modified`: -1/P · ∂P/∂y.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Duration<F> {
+    pub macaulay: F,
+    pub modified: F,
+}
+
+/// Price a fixed coupon bond by discounting its coupons and face value at a flat
+/// yield compounded once per coupon period.
+///
+/// A `coupon_rate` of zero prices a zero coupon bond.
+///
+/// * `face`: The face value repaid at maturity.
+/// * `coupon_rate`: The annual coupon rate as decimal of the face value.
+/// * `n_periods`: The number of remaining coupon periods.
+/// * `periods_per_year`: The number of coupon payments per year.
+/// * `yield_rate`: The annual yield as decimal, compounded `periods_per_year` times a year.
+///
+/// * `price`: The present value of the bond's cash flows.
+pub fn price_bond<F: ag::Float>(
+    face: F,
+    coupon_rate: F,
+    n_periods: usize,
+    periods_per_year: usize,
+    yield_rate: F,
+) -> F {
+    let m = F::from(periods_per_year).unwrap();
+    let coupon = face * coupon_rate / m;
+    let growth = F::one() + yield_rate / m;
+    let coupons = (1..n_periods + 1).fold(F::zero(), |pv, period| pv + coupon / growth.powi(period as i32));
+    coupons + face / growth.powi(n_periods as i32)
+}
+
+/// Solve for the yield to maturity at which `price_bond` reproduces a market price.
+///
+/// The yield is bracketed between zero and a growing upper bound, then bisected.
+///
+/// * `price`: The market price of the bond.
+/// * `face`: The face value repaid at maturity.
+/// * `coupon_rate`: The annual coupon rate as decimal of the face value.
+/// * `n_periods`: The number of remaining coupon periods.
+/// * `periods_per_year`: The number of coupon payments per year.
+///
+/// * `yield`: The annual yield as decimal, compounded `periods_per_year` times a year,
+///   `QuantError::NoBracket` when the price is at least the undiscounted sum of the
+///   cash flows so no positive yield up to 2⁶⁴ reproduces it, or
+///   `QuantError::InvalidInput` when the price is not positive or the bond has no
+///   coupon periods.
+pub fn yield_to_maturity<F: ag::Float>(
+    price: F,
+    face: F,
+    coupon_rate: F,
+    n_periods: usize,
+    periods_per_year: usize,
+) -> Result<F, QuantError> {
+    if !(price > F::zero()) {
+        return Err(QuantError::InvalidInput(format!("bond price {} must be positive", price)));
+    }
+    if n_periods == 0 || periods_per_year == 0 {
+        return Err(QuantError::InvalidInput(
+            "the bond needs at least one coupon period and payment per year".to_string(),
+        ));
+    }
+    let pv = |y: F| price_bond(face, coupon_rate, n_periods, periods_per_year, y);
+    if pv(F::zero()) <= price {
+        return Err(QuantError::NoBracket);
+    }
+
+    let two = F::from(2f64).unwrap();
+    let mut lo = F::zero();
+    let mut hi = F::one();
+    for _ in 0..64 {
+        if pv(hi) <= price {
+            return bisect(|y| pv(y) - price, lo, hi, F::epsilon() * hi);
+        }
+        lo = hi;
+        hi = hi * two;
+    }
+    Err(QuantError::NoBracket)
+}
+
+/// Calculate the Macaulay and modified duration of a fixed coupon bond.
+///
+/// The modified duration `-1/P · ∂P/∂y` is computed by differentiating the bond's
+/// price graph with respect to the yield, and the Macaulay duration follows as
+/// `modified · (1 + y / m)`.
+///
+/// * `face`: The face value repaid at maturity.
+/// * `coupon_rate`: The annual coupon rate as decimal of the face value.
+/// * `n_periods`: The number of remaining coupon periods.
+/// * `periods_per_year`: The number of coupon payments `m` per year.
+/// * `yield_rate`: The annual yield as decimal, compounded `periods_per_year` times a year.
+///
+/// * `duration`: The Macaulay and modified duration, in years.
+pub fn duration<F: ag::Float>(
+    face: F,
+    coupon_rate: F,
+    n_periods: usize,
+    periods_per_year: usize,
+    yield_rate: F,
+) -> Duration<F> {
+    let modified = ag::run(|ctx: &mut ag::Context<F>| {
+        let y = math::convert_to_tensor(nd::arr1(&[yield_rate]).into_dyn(), ctx);
+        let price = price_graph(face, coupon_rate, n_periods, periods_per_year, &y);
+        let slope = math::grad(&[price], &[y])[0];
+        let mut results = ctx.evaluator().push(&price).push(&slope).run().into_iter();
+        let price = results.next().unwrap().unwrap()[0];
+        let slope = results.next().unwrap().unwrap()[0];
+        -slope / price
+    });
+    let m = F::from(periods_per_year).unwrap();
+    Duration {
+        macaulay: modified * (F::one() + yield_rate / m),
+        modified,
+    }
+}
+
+/// Calculate the convexity `1/P · ∂²P/∂y²` of a fixed coupon bond by differentiating
+/// its price graph twice with respect to the yield.
+///
+/// * `face`: The face value repaid at maturity.
+/// * `coupon_rate`: The annual coupon rate as decimal of the face value.
+/// * `n_periods`: The number of remaining coupon periods.
+/// * `periods_per_year`: The number of coupon payments per year.
+/// * `yield_rate`: The annual yield as decimal, compounded `periods_per_year` times a year.
+///
+/// * `convexity`: The convexity, in years squared.
+pub fn convexity<F: ag::Float>(
+    face: F,
+    coupon_rate: F,
+    n_periods: usize,
+    periods_per_year: usize,
+    yield_rate: F,
+) -> F {
+    ag::run(|ctx: &mut ag::Context<F>| {
+        let y = math::convert_to_tensor(nd::arr1(&[yield_rate]).into_dyn(), ctx);
+        let price = price_graph(face, coupon_rate, n_periods, periods_per_year, &y);
+        let slope = math::grad(&[price], &[y])[0];
+        let curvature = math::grad(&[slope], &[y])[0];
+        let mut results = ctx.evaluator().push(&price).push(&curvature).run().into_iter();
+        let price = results.next().unwrap().unwrap()[0];
+        let curvature = results.next().unwrap().unwrap()[0];
+        curvature / price
+    })
+}
+
+/// The bond price of `price_bond` as a differentiable function of the yield.
+fn price_graph<'graph, F: ag::Float>(
+    face: F,
+    coupon_rate: F,
+    n_periods: usize,
+    periods_per_year: usize,
+    yield_rate: &ag::Tensor<'graph, F>,
+) -> ag::Tensor<'graph, F> {
+    let m = F::from(periods_per_year).unwrap();
+    let coupon = face * coupon_rate / m;
+    let growth = (yield_rate / m) + F::one();
+    let discount = |period: usize| math::pow(&growth, -F::from(period).unwrap());
+    (1..n_periods + 1).fold(discount(n_periods) * face, |pv, period| pv + discount(period) * coupon)
+}
@@ -0,0 +1,125 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::fixed_income::curve::{check_schedule, simple_forward_rate, DiscountCurve};
+use crate::options::model::OptionType;
+use crate::stats::normal::cdf_scalar;
+
+/// Price a caplet, a call on a simple forward rate, with the Black (1976) model
+///
+/// V = τ P (F N(d1) - K N(d2)), d1 = (ln(F/K) + σ²T/2) / (σ√T), d2 = d1 - σ√T.
+///
+/// The rate fixes at `time` and the caplet pays `τ max(F - K, 0)` at the end of the
+/// accrual period, so the payoff is discounted to the payment date rather than the
+/// fixing. With no time or volatility left the caplet is worth its discounted
+/// intrinsic value.
+///
+/// * `forward_rate`: The positive simple forward rate of the accrual period as decimal.
+/// * `strike`: The positive cap rate as decimal.
+/// * `vol`: The lognormal volatility of the forward rate in decimal.
+/// * `time`: The time until the rate fixes as decimal of a year.
+/// * `accrual`: The accrual fraction τ of the period, as decimal of a year.
+/// * `discount_factor`: The discount factor P to the payment date.
+///
+/// * `price`: The price of the caplet per unit notional.
+pub fn price_caplet<F: ag::Float>(forward_rate: F, strike: F, vol: F, time: F, accrual: F, discount_factor: F) -> F {
+    black(OptionType::Call, forward_rate, strike, vol, time) * accrual * discount_factor
+}
+
+/// Price a floorlet, a put on a simple forward rate paying `τ max(K - F, 0)`, with the
+/// Black (1976) model
+///
+/// V = τ P (K N(-d2) - F N(-d1)).
+///
+/// A caplet less the floorlet at the same strike is the forward rate agreement
+/// `τ P (F - K)`.
+///
+/// * `forward_rate`: The positive simple forward rate of the accrual period as decimal.
+/// * `strike`: The positive floor rate as decimal.
+/// * `vol`: The lognormal volatility of the forward rate in decimal.
+/// * `time`: The time until the rate fixes as decimal of a year.
+/// * `accrual`: The accrual fraction τ of the period, as decimal of a year.
+/// * `discount_factor`: The discount factor P to the payment date.
+///
+/// * `price`: The price of the floorlet per unit notional.
+pub fn price_floorlet<F: ag::Float>(forward_rate: F, strike: F, vol: F, time: F, accrual: F, discount_factor: F) -> F {
+    black(OptionType::Put, forward_rate, strike, vol, time) * accrual * discount_factor
+}
+
+/// Price a cap as the sum of its caplets off a discount curve.
+///
+/// Caplet `i` fixes at `reset_times[i]` and pays at `reset_times[i + 1]` on the simple
+/// forward rate `(P(tᵢ) / P(tᵢ₊₁) - 1) / τᵢ` of the curve, with `τᵢ = tᵢ₊₁ - tᵢ`.
+///
+/// * `curve`: The discount curve of the forward rates and payments.
+/// * `reset_times`: The increasing start of each period followed by the end of the
+///   last, as decimal of a year.
+/// * `strike`: The positive cap rate as decimal.
+/// * `vols`: The lognormal volatility of each caplet's forward rate in decimal.
+///
+/// * `price`: The price of the cap per unit notional, `QuantError::ShapeMismatch` when
+///   there is not one vol per period, or `QuantError::InvalidInput` when the reset
+///   times are not increasing or start before 0.
+pub fn price_cap<F: ag::Float, C: DiscountCurve<F>>(
+    curve: &C,
+    reset_times: &[F],
+    strike: F,
+    vols: &[F],
+) -> Result<F, QuantError> {
+    price_periods(OptionType::Call, curve, reset_times, strike, vols)
+}
+
+/// Price a floor as the sum of its floorlets off a discount curve, with the schedule
+/// of `price_cap`.
+///
+/// * `curve`: The discount curve of the forward rates and payments.
+/// * `reset_times`: The increasing start of each period followed by the end of the
+///   last, as decimal of a year.
+/// * `strike`: The positive floor rate as decimal.
+/// * `vols`: The lognormal volatility of each floorlet's forward rate in decimal.
+///
+/// * `price`: The price of the floor per unit notional, `QuantError::ShapeMismatch`
+///   when there is not one vol per period, or `QuantError::InvalidInput` when the
+///   reset times are not increasing or start before 0.
+pub fn price_floor<F: ag::Float, C: DiscountCurve<F>>(
+    curve: &C,
+    reset_times: &[F],
+    strike: F,
+    vols: &[F],
+) -> Result<F, QuantError> {
+    price_periods(OptionType::Put, curve, reset_times, strike, vols)
+}
+
+fn price_periods<F: ag::Float, C: DiscountCurve<F>>(
+    ty: OptionType,
+    curve: &C,
+    reset_times: &[F],
+    strike: F,
+    vols: &[F],
+) -> Result<F, QuantError> {
+    QuantError::check_shape(&[reset_times.len().saturating_sub(1)], &[vols.len()])?;
+    check_schedule(reset_times)?;
+    Ok(reset_times
+        .windows(2)
+        .zip(vols.iter())
+        .fold(F::zero(), |acc, (period, &vol)| {
+            let (start, end) = (period[0], period[1]);
+            let forward = simple_forward_rate(curve, start, end);
+            acc + black(ty, forward, strike, vol, start) * (end - start) * curve.discount_factor(end)
+        }))
+}
+
+/// The undiscounted Black (1976) price of an option on a forward.
+fn black<F: ag::Float>(ty: OptionType, forward: F, strike: F, vol: F, time: F) -> F {
+    let std = vol * time.max(F::zero()).sqrt();
+    if !(std > F::zero()) {
+        return ty.payoff(forward, strike);
+    }
+    let n = |x: F| F::from(cdf_scalar(x.to_f64().unwrap())).unwrap();
+    let d1 = ((forward / strike).ln() + std * std / F::from(2f64).unwrap()) / std;
+    let d2 = d1 - std;
+    match ty {
+        OptionType::Call => forward * n(d1) - strike * n(d2),
+        OptionType::Put => strike * n(-d2) - forward * n(-d1),
+    }
+}
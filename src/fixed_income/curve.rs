@@ -0,0 +1,314 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::numerics::interp::CubicSpline;
+use crate::numerics::root::bisect;
+
+/// A market quote used to bootstrap a `ZeroCurve`. Cash flows are per unit of face
+/// value and fall on `maturity - j / periods_per_year` for `j = 0, 1, ...` while
+/// the time is positive.
+///
+/// * `Bond`: A coupon bond with its dirty `price` per unit face value.
+/// * `ParYield`: A par yield, e.g. a bond with coupon rate `rate` priced at par.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Instrument<F> {
+    Bond {
+        maturity: F,
+        coupon_rate: F,
+        periods_per_year: usize,
+        price: F,
+    },
+    ParYield {
+        maturity: F,
+        rate: F,
+        periods_per_year: usize,
+    },
+}
+
+impl<F: ag::Float> Instrument<F> {
+    /// The time to the instrument's final cash flow as decimal of a year.
+    pub fn maturity(&self) -> F {
+        match *self {
+            Instrument::Bond { maturity, .. } | Instrument::ParYield { maturity, .. } => maturity,
+        }
+    }
+
+    /// The quoted price per unit face value.
+    pub fn price(&self) -> F {
+        match *self {
+            Instrument::Bond { price, .. } => price,
+            Instrument::ParYield { .. } => F::one(),
+        }
+    }
+
+    /// The `(time, amount)` of each cash flow per unit face value.
+    pub fn cash_flows(&self) -> Vec<(F, F)> {
+        let (maturity, coupon_rate, periods_per_year) = match *self {
+            Instrument::Bond {
+                maturity,
+                coupon_rate,
+                periods_per_year,
+                ..
+            } => (maturity, coupon_rate, periods_per_year),
+            Instrument::ParYield {
+                maturity,
+                rate,
+                periods_per_year,
+            } => (maturity, rate, periods_per_year),
+        };
+        let m = F::from(periods_per_year).unwrap();
+        let coupon = coupon_rate / m;
+        // Ignore coupon dates within rounding distance of today.
+        let cutoff = F::from(1e-9f64).unwrap();
+
+        let mut flows = Vec::new();
+        let mut j = 0;
+        loop {
+            let time = maturity - F::from(j).unwrap() / m;
+            if time <= cutoff {
+                break;
+            }
+            let principal = if j == 0 { F::one() } else { F::zero() };
+            flows.push((time, coupon + principal));
+            j += 1;
+        }
+        flows.reverse();
+        flows
+    }
+}
+
+/// A term structure of risk free discounting, in continuously compounded rates.
+pub trait DiscountCurve<F: ag::Float> {
+    /// The discount factor for a cash flow at time `t`, as decimal of a year.
+    fn discount_factor(&self, t: F) -> F;
+
+    /// The instantaneous forward rate `-∂ ln P(t) / ∂t` at time `t`.
+    fn instantaneous_rate(&self, t: F) -> F;
+
+    /// The continuously compounded zero rate `-ln P(t) / t` to time `t`, which is the
+    /// constant rate a pricer should use to discount a payoff at `t`. At `t = 0` it is
+    /// the instantaneous rate.
+    fn zero_rate(&self, t: F) -> F {
+        if t <= F::zero() {
+            return self.instantaneous_rate(F::zero());
+        }
+        -self.discount_factor(t).ln() / t
+    }
+}
+
+/// The simple forward rate `(P(t0) / P(t1) - 1) / (t1 - t0)` of a curve between two
+/// times.
+pub fn simple_forward_rate<F: ag::Float, C: DiscountCurve<F>>(curve: &C, t0: F, t1: F) -> F {
+    (curve.discount_factor(t0) / curve.discount_factor(t1) - F::one()) / (t1 - t0)
+}
+
+/// Check that a schedule of period boundaries has at least one period, starts at or
+/// after 0 and strictly increases.
+pub(crate) fn check_schedule<F: ag::Float>(times: &[F]) -> Result<(), QuantError> {
+    let increasing = times.iter().zip(times.iter().skip(1)).all(|(a, b)| a < b);
+    if times.len() < 2 || times[0] < F::zero() || !increasing {
+        return Err(QuantError::InvalidInput(
+            "schedule times must be non negative and strictly increasing".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// A curve with the same continuously compounded rate at every maturity, the term
+/// structure behind the pricers' constant `r`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlatCurve<F> {
+    pub rate: F,
+}
+
+impl<F: ag::Float> FlatCurve<F> {
+    /// Create a curve at the continuously compounded `rate` as decimal.
+    pub fn new(rate: F) -> Self {
+        FlatCurve { rate }
+    }
+}
+
+impl<F: ag::Float> DiscountCurve<F> for FlatCurve<F> {
+    fn discount_factor(&self, t: F) -> F {
+        (-self.rate * t).exp()
+    }
+
+    fn instantaneous_rate(&self, _t: F) -> F {
+        self.rate
+    }
+
+    fn zero_rate(&self, _t: F) -> F {
+        self.rate
+    }
+}
+
+/// A zero coupon discount curve defined by discount factors at increasing pillar
+/// times.
+///
+/// Discount factors are interpolated log-linearly between pillars, with an implicit
+/// pillar of `1` at time `0`, which means a constant continuously compounded forward
+/// rate between pillars. Beyond the last pillar the zero rate is held flat.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZeroCurve<F> {
+    times: Vec<F>,
+    discount_factors: Vec<F>,
+}
+
+impl<F: ag::Float> ZeroCurve<F> {
+    /// Create a curve from its pillars.
+    ///
+    /// * `times`: The strictly increasing, positive pillar times as decimal of a year.
+    /// * `discount_factors`: The positive discount factor at each pillar.
+    ///
+    /// * `curve`: The curve, `QuantError::ShapeMismatch` when the lengths differ, or
+    ///   `QuantError::InvalidInput` when a time or discount factor is out of range.
+    pub fn new(times: Vec<F>, discount_factors: Vec<F>) -> Result<Self, QuantError> {
+        QuantError::check_shape(&[times.len()], &[discount_factors.len()])?;
+        let increasing = times
+            .iter()
+            .zip(times.iter().skip(1))
+            .all(|(a, b)| a < b);
+        if times.is_empty() || !(times[0] > F::zero()) || !increasing {
+            return Err(QuantError::InvalidInput(
+                "pillar times must be positive and strictly increasing".to_string(),
+            ));
+        }
+        if discount_factors.iter().any(|df| !(*df > F::zero())) {
+            return Err(QuantError::InvalidInput("discount factors must be positive".to_string()));
+        }
+        Ok(ZeroCurve {
+            times,
+            discount_factors,
+        })
+    }
+
+    /// The pillar times as decimal of a year.
+    pub fn times(&self) -> &[F] {
+        &self.times
+    }
+
+    /// The discount factor at each pillar.
+    pub fn discount_factors(&self) -> &[F] {
+        &self.discount_factors
+    }
+
+    /// The discount factor for a cash flow at time `t`, log-linearly interpolated
+    /// between pillars.
+    pub fn discount_factor(&self, t: F) -> F {
+        if t <= F::zero() {
+            return F::one();
+        }
+        let last = self.times.len() - 1;
+        if t >= self.times[last] {
+            return (self.discount_factors[last].ln() * t / self.times[last]).exp();
+        }
+
+        let i = self.times.iter().position(|&time| time >= t).unwrap();
+        let (t0, ln0) = if i == 0 {
+            (F::zero(), F::zero())
+        } else {
+            (self.times[i - 1], self.discount_factors[i - 1].ln())
+        };
+        let (t1, ln1) = (self.times[i], self.discount_factors[i].ln());
+        (ln0 + (ln1 - ln0) * (t - t0) / (t1 - t0)).exp()
+    }
+
+    /// The continuously compounded zero rate to time `t`.
+    pub fn zero_rate(&self, t: F) -> F {
+        if t <= F::zero() {
+            return -self.discount_factors[0].ln() / self.times[0];
+        }
+        -self.discount_factor(t).ln() / t
+    }
+
+    /// A natural cubic spline through the pillars' zero rates, for a zero rate curve
+    /// that is smooth rather than only continuous between pillars. Like the spline,
+    /// the rates are held flat before the first and beyond the last pillar.
+    pub fn zero_rate_spline(&self) -> CubicSpline<F> {
+        let rates = self
+            .times
+            .iter()
+            .zip(self.discount_factors.iter())
+            .map(|(&t, &df)| -df.ln() / t)
+            .collect();
+        CubicSpline::natural(self.times.clone(), rates)
+    }
+
+    /// The present value of an instrument's cash flows per unit face value.
+    pub fn present_value(&self, instrument: &Instrument<F>) -> F {
+        instrument
+            .cash_flows()
+            .iter()
+            .fold(F::zero(), |pv, &(time, amount)| pv + amount * self.discount_factor(time))
+    }
+}
+
+impl<F: ag::Float> DiscountCurve<F> for ZeroCurve<F> {
+    fn discount_factor(&self, t: F) -> F {
+        ZeroCurve::discount_factor(self, t)
+    }
+
+    /// The forward rate of the pillar interval holding `t`, taking the later interval
+    /// at a pillar, and the zero rate of the last pillar beyond it.
+    fn instantaneous_rate(&self, t: F) -> F {
+        match self.times.iter().position(|&time| time > t) {
+            Some(i) => {
+                let (t0, ln0) = if i == 0 {
+                    (F::zero(), F::zero())
+                } else {
+                    (self.times[i - 1], self.discount_factors[i - 1].ln())
+                };
+                -(self.discount_factors[i].ln() - ln0) / (self.times[i] - t0)
+            }
+            None => self.zero_rate(self.times[self.times.len() - 1]),
+        }
+    }
+
+    fn zero_rate(&self, t: F) -> F {
+        ZeroCurve::zero_rate(self, t)
+    }
+}
+
+/// Bootstrap a zero curve from instruments at strictly increasing maturities.
+///
+/// Each instrument adds a pillar at its maturity. Its discount factor is solved by
+/// bisection on the pillar's zero rate so that the instrument reprices exactly,
+/// with the earlier pillars held fixed and the cash flows between the previous
+/// pillar and the maturity discounted by log-linear interpolation.
+///
+/// * `instruments`: The market quotes, ordered by maturity.
+///
+/// * `curve`: The bootstrapped curve, `QuantError::InvalidInput` when the maturities
+///   are not positive and strictly increasing, or `QuantError::NoBracket` when an
+///   instrument cannot be repriced with a zero rate between -50% and 200%.
+pub fn bootstrap_zero_curve<F: ag::Float>(instruments: &[Instrument<F>]) -> Result<ZeroCurve<F>, QuantError> {
+    let mut times: Vec<F> = Vec::new();
+    let mut discount_factors: Vec<F> = Vec::new();
+
+    for instrument in instruments {
+        let maturity = instrument.maturity();
+        if !(maturity > times.last().cloned().unwrap_or(F::zero())) {
+            return Err(QuantError::InvalidInput(
+                "instrument maturities must be positive and strictly increasing".to_string(),
+            ));
+        }
+        let price = instrument.price();
+        let pv = |rate: F| {
+            let mut pillars = times.clone();
+            let mut dfs = discount_factors.clone();
+            pillars.push(maturity);
+            dfs.push((-rate * maturity).exp());
+            ZeroCurve { times: pillars, discount_factors: dfs }.present_value(instrument)
+        };
+
+        // The present value falls as the pillar's zero rate rises.
+        let rate = bisect(|rate| pv(rate) - price, F::from(-0.5f64).unwrap(), F::from(2f64).unwrap(), F::epsilon())?;
+
+        times.push(maturity);
+        discount_factors.push((-rate * maturity).exp());
+    }
+
+    ZeroCurve::new(times, discount_factors)
+}
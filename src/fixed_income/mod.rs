@@ -0,0 +1,5 @@
+pub mod bond;
+pub mod caplet;
+pub mod curve;
+pub mod nelson_siegel;
+pub mod swap;
@@ -0,0 +1,142 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use autograd::prelude::*;
+use crate::error::QuantError;
+use crate::numerics::linalg::least_squares;
+use crate::numerics::optimize::run_epochs;
+
+/// The parameters of a Nelson-Siegel yield curve
+///
+/// y(t) = β₀ + β₁ (1 - e^{-t/τ}) / (t/τ) + β₂ ((1 - e^{-t/τ}) / (t/τ) - e^{-t/τ})
+///
+/// * `beta0`: The long run level of the yields.
+/// * `beta1`: The short end's deviation from the level.
+/// * `beta2`: The size of the medium term hump.
+/// * `tau`: The positive decay time of the slope and hump, in years.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NelsonSiegelParams<F> {
+    pub beta0: F,
+    pub beta1: F,
+    pub beta2: F,
+    pub tau: F,
+}
+
+impl<F: ag::Float> NelsonSiegelParams<F> {
+    /// The yield of the curve at maturity `t`, as decimal of a year.
+    pub fn yield_at(&self, t: F) -> F {
+        if t <= F::zero() {
+            return self.beta0 + self.beta1;
+        }
+        let x = t / self.tau;
+        let decay = (-x).exp();
+        let slope = (F::one() - decay) / x;
+        self.beta0 + self.beta1 * slope + self.beta2 * (slope - decay)
+    }
+}
+
+/// Fit a Nelson-Siegel curve to market yields by minimizing the mean squared yield
+/// error with Adam.
+///
+/// The loss is not convex in `tau`, so the fit starts from the best of a log spaced
+/// grid of `tau` between 0.1 and 10 years, with the betas of each grid point solved
+/// by least squares. Adam then refines all four parameters, the betas in percent
+/// and `tau` through its logarithm so that it stays positive.
+///
+/// * `maturities`: The positive maturities of the yields, as decimal of a year.
+/// * `yields`: The market yields as decimal.
+///
+/// * `params`: The fitted parameters, `QuantError::ShapeMismatch` when the inputs'
+///   shapes differ, or `QuantError::InvalidInput` with fewer than 3 yields or a
+///   maturity that is not positive.
+pub fn fit_nelson_siegel<F: ag::Float>(
+    maturities: ag::NdArrayView<F>,
+    yields: ag::NdArrayView<F>,
+) -> Result<NelsonSiegelParams<F>, QuantError> {
+    QuantError::check_shape(maturities.shape(), yields.shape())?;
+    if yields.len() < 3 {
+        return Err(QuantError::InvalidInput("need at least 3 yields to fit".to_string()));
+    }
+    if maturities.iter().any(|t| !(*t > F::zero())) {
+        return Err(QuantError::InvalidInput("maturities must be positive".to_string()));
+    }
+    let hundred = F::from(100f64).unwrap();
+    let percent = yields.mapv(|y| y * hundred);
+    let (betas, log_tau) = initial_guess(maturities, percent.view());
+
+    let mut env = ag::VariableEnvironment::new();
+    let beta0_id = env.name("beta0").set(nd::arr1(&[betas[0]]).into_dyn());
+    let beta1_id = env.name("beta1").set(nd::arr1(&[betas[1]]).into_dyn());
+    let beta2_id = env.name("beta2").set(nd::arr1(&[betas[2]]).into_dyn());
+    let log_tau_id = env.name("log_tau").set(nd::arr1(&[log_tau]).into_dyn());
+
+    let adam = ag::optimizers::adam::Adam::default("AdamNS", env.default_namespace().current_var_ids(), &mut env);
+
+    env.run(|ctx| {
+        let beta0 = ctx.variable("beta0");
+        let beta1 = ctx.variable("beta1");
+        let beta2 = ctx.variable("beta2");
+        let log_tau = ctx.variable("log_tau");
+        let t = ctx.placeholder("t", &[-1]);
+        let y = ctx.placeholder("y", &[-1]);
+
+        let x = t / math::exp(log_tau);
+        let decay = math::exp(math::neg(x));
+        let slope = (math::neg(decay) + F::one()) / x;
+        let pred = beta0 + beta1 * slope + beta2 * (slope - decay);
+
+        let loss = math::reduce_mean(math::square(pred - y), &[0], false);
+        let grads = math::grad(&[loss], &[beta0, beta1, beta2, log_tau]);
+        let update = adam.get_update_op(&[beta0, beta1, beta2, log_tau], &grads, ctx);
+
+        run_epochs(ctx, &update, &[(t, maturities.view()), (y, percent.view())], 2000);
+    });
+
+    let value = |id| env.get_array_by_id(id).unwrap().clone().into_inner()[0];
+    Ok(NelsonSiegelParams {
+        beta0: value(beta0_id) / hundred,
+        beta1: value(beta1_id) / hundred,
+        beta2: value(beta2_id) / hundred,
+        tau: value(log_tau_id).exp(),
+    })
+}
+
+/// The least squares betas and log `tau` of the best fitting point on a grid of `tau`.
+fn initial_guess<F: ag::Float>(maturities: ag::NdArrayView<F>, yields: ag::NdArrayView<F>) -> (nd::Array1<F>, F) {
+    let t = maturities.iter().cloned().collect::<Vec<_>>();
+    let y = nd::Array1::from(yields.iter().cloned().collect::<Vec<_>>());
+    let (lo, hi) = (F::from(0.1f64).unwrap().ln(), F::from(10f64).unwrap().ln());
+    let n_grid = 41;
+
+    let mut best: Option<(F, nd::Array1<F>, F)> = None;
+    for i in 0..n_grid {
+        let log_tau = lo + (hi - lo) * F::from(i).unwrap() / F::from(n_grid - 1).unwrap();
+        let tau = log_tau.exp();
+        let basis = nd::Array2::from_shape_fn((t.len(), 3), |(row, column)| {
+            let x = t[row] / tau;
+            let decay = (-x).exp();
+            let slope = (F::one() - decay) / x;
+            match column {
+                0 => F::one(),
+                1 => slope,
+                _ => slope - decay,
+            }
+        });
+        let betas = match least_squares(&basis, &y) {
+            Some(betas) => betas,
+            None => continue,
+        };
+        let residual = &basis.dot(&betas) - &y;
+        let loss = residual.iter().fold(F::zero(), |acc, &e| acc + e * e);
+        if best.as_ref().map_or(true, |(best_loss, _, _)| loss < *best_loss) {
+            best = Some((loss, betas, log_tau));
+        }
+    }
+
+    match best {
+        Some((_, betas, log_tau)) => (betas, log_tau),
+        None => (nd::Array1::from(vec![y[y.len() - 1], F::zero(), F::zero()]), F::zero()),
+    }
+}
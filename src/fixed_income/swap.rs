@@ -0,0 +1,78 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::fixed_income::curve::{check_schedule, simple_forward_rate, DiscountCurve};
+
+/// The present value of a vanilla interest rate swap to the payer of the fixed rate.
+///
+/// Both legs pay on the same schedule. Period `i` runs from `schedule[i]` to
+/// `schedule[i + 1]` with accrual `τᵢ = tᵢ₊₁ - tᵢ` and pays at its end. The floating leg
+/// pays the simple forward rates of `forward_curve`, and both legs are discounted off
+/// `discount_curve`, so
+///
+/// PV = N Σᵢ τᵢ P(tᵢ₊₁) (Lᵢ - K).
+///
+/// Pass the same curve twice for single curve valuation.
+///
+/// * `notional`: The notional both legs accrue on.
+/// * `fixed_rate`: The fixed rate K as decimal.
+/// * `schedule`: The increasing start of the swap followed by each payment date, as
+///   decimal of a year.
+/// * `discount_curve`: The curve the cash flows are discounted off.
+/// * `forward_curve`: The curve projecting the floating rates Lᵢ.
+///
+/// * `pv`: The value of paying fixed and receiving floating, or
+///   `QuantError::InvalidInput` when the schedule is not increasing or starts before 0.
+pub fn price_vanilla_swap<F: ag::Float, D: DiscountCurve<F>, P: DiscountCurve<F>>(
+    notional: F,
+    fixed_rate: F,
+    schedule: &[F],
+    discount_curve: &D,
+    forward_curve: &P,
+) -> Result<F, QuantError> {
+    check_schedule(schedule)?;
+    let floating = floating_leg(schedule, discount_curve, forward_curve);
+    Ok(notional * (floating - fixed_rate * annuity(schedule, discount_curve)))
+}
+
+/// The par swap rate, the fixed rate at which `price_vanilla_swap` is worth zero
+///
+/// K = Σᵢ τᵢ P(tᵢ₊₁) Lᵢ / Σᵢ τᵢ P(tᵢ₊₁).
+///
+/// The present value is linear in the fixed rate, so the root is the ratio of the
+/// floating leg to the annuity of the fixed leg.
+///
+/// * `schedule`: The increasing start of the swap followed by each payment date, as
+///   decimal of a year.
+/// * `discount_curve`: The curve the cash flows are discounted off.
+/// * `forward_curve`: The curve projecting the floating rates.
+///
+/// * `rate`: The par swap rate as decimal, or `QuantError::InvalidInput` when the
+///   schedule is not increasing or starts before 0.
+pub fn par_swap_rate<F: ag::Float, D: DiscountCurve<F>, P: DiscountCurve<F>>(
+    schedule: &[F],
+    discount_curve: &D,
+    forward_curve: &P,
+) -> Result<F, QuantError> {
+    check_schedule(schedule)?;
+    Ok(floating_leg(schedule, discount_curve, forward_curve) / annuity(schedule, discount_curve))
+}
+
+/// The value Σᵢ τᵢ P(tᵢ₊₁) of receiving a rate of 1 on every period of the schedule.
+fn annuity<F: ag::Float, D: DiscountCurve<F>>(schedule: &[F], discount_curve: &D) -> F {
+    schedule.windows(2).fold(F::zero(), |acc, period| {
+        acc + (period[1] - period[0]) * discount_curve.discount_factor(period[1])
+    })
+}
+
+/// The value per unit notional of receiving the floating rates.
+fn floating_leg<F: ag::Float, D: DiscountCurve<F>, P: DiscountCurve<F>>(
+    schedule: &[F],
+    discount_curve: &D,
+    forward_curve: &P,
+) -> F {
+    schedule.windows(2).fold(F::zero(), |acc, period| {
+        let forward = simple_forward_rate(forward_curve, period[0], period[1]);
+        acc + (period[1] - period[0]) * discount_curve.discount_factor(period[1]) * forward
+    })
+}
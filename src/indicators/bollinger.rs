@@ -0,0 +1,38 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+use crate::indicators::moving_average::sma;
+
+/// Bollinger bands around the simple moving average of prices.
+///
+/// The middle band is the `sma` of each window and the outer bands lie `num_std`
+/// standard deviations of the window's prices above and below it. As in Bollinger's
+/// definition the deviation is the population one, dividing by `window`.
+///
+/// * `prices`: The prices in time order.
+/// * `window`: The number of prices in each window, at least 1.
+/// * `num_std`: The width of the bands in standard deviations, e.g. `2`.
+///
+/// * `(upper, middle, lower)`: The `n - window + 1` values of each band for the windows
+///   ending at each price, or `QuantError::InvalidInput` when the window is empty or
+///   longer than the series.
+pub fn bollinger_bands<F: ag::Float>(
+    prices: &ag::NdArray<F>,
+    window: usize,
+    num_std: F,
+) -> Result<(ag::NdArray<F>, ag::NdArray<F>, ag::NdArray<F>), QuantError> {
+    let middle = sma(prices, window)?;
+    let prices = prices.iter().cloned().collect::<Vec<_>>();
+    let n = F::from(window).unwrap();
+    let widths = prices
+        .windows(window)
+        .zip(middle.iter())
+        .map(|(values, &mean)| {
+            let sum_squares = values.iter().fold(F::zero(), |acc, &p| acc + (p - mean).powi(2));
+            (sum_squares / n).sqrt() * num_std
+        })
+        .collect::<Vec<_>>();
+    let widths = nd::Array1::from(widths).into_dyn();
+    Ok((&middle + &widths, middle.clone(), &middle - &widths))
+}
@@ -0,0 +1,38 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::indicators::moving_average::ema;
+
+/// The moving average convergence divergence of prices.
+///
+/// The MACD line is the `fast` span `ema` of the prices less their `slow` span `ema`,
+/// the signal line is the `signal` span `ema` of the MACD line, and the histogram is
+/// the MACD line less the signal line. All averages are seeded with their first
+/// input, so the three series start at 0 and have a value at every price. The usual
+/// spans are 12, 26 and 9.
+///
+/// * `prices`: The prices in time order.
+/// * `fast`: The positive span of the fast average.
+/// * `slow`: The span of the slow average, longer than `fast`.
+/// * `signal`: The positive span of the signal line.
+///
+/// * `(macd_line, signal_line, histogram)`: The `n` values of each series at each
+///   price, or `QuantError::InvalidInput` when a span is 0, `fast` is not shorter
+///   than `slow`, or there are no prices.
+pub fn macd<F: ag::Float>(
+    prices: &ag::NdArray<F>,
+    fast: usize,
+    slow: usize,
+    signal: usize,
+) -> Result<(ag::NdArray<F>, ag::NdArray<F>, ag::NdArray<F>), QuantError> {
+    if fast >= slow {
+        return Err(QuantError::InvalidInput(format!(
+            "fast span {} must be shorter than the slow span {}",
+            fast, slow
+        )));
+    }
+    let macd_line = ema(prices, fast)? - ema(prices, slow)?;
+    let signal_line = ema(&macd_line, signal)?;
+    let histogram = &macd_line - &signal_line;
+    Ok((macd_line, signal_line, histogram))
+}
@@ -0,0 +1,4 @@
+pub mod bollinger;
+pub mod macd;
+pub mod moving_average;
+pub mod rsi;
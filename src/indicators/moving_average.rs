@@ -0,0 +1,59 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+
+/// The simple moving average of prices over a sliding window.
+///
+/// * `prices`: The prices in time order.
+/// * `window`: The number of prices in each average, at least 1.
+///
+/// * `averages`: The `n - window + 1` averages of the windows ending at each price, or
+///   `QuantError::InvalidInput` when the window is empty or longer than the series.
+pub fn sma<F: ag::Float>(prices: &ag::NdArray<F>, window: usize) -> Result<ag::NdArray<F>, QuantError> {
+    if window < 1 || window > prices.len() {
+        return Err(QuantError::InvalidInput(format!(
+            "window {} must be between 1 and the {} prices",
+            window,
+            prices.len()
+        )));
+    }
+    let prices = prices.iter().cloned().collect::<Vec<_>>();
+    let n = F::from(window).unwrap();
+    let averages = prices
+        .windows(window)
+        .map(|values| values.iter().fold(F::zero(), |acc, &p| acc + p) / n)
+        .collect::<Vec<_>>();
+    Ok(nd::Array1::from(averages).into_dyn())
+}
+
+/// The exponential moving average of prices
+///
+/// EMAₜ = α Pₜ + (1 - α) EMAₜ₋₁, α = 2 / (span + 1).
+///
+/// The average is seeded with the first price, EMA₀ = P₀, so it has a value at every
+/// price but leans on the seed for roughly the first `span` of them.
+///
+/// * `prices`: The prices in time order.
+/// * `span`: The positive span of the average, in number of prices.
+///
+/// * `averages`: The `n` averages at each price, or `QuantError::InvalidInput` when
+///   the span is 0 or there are no prices.
+pub fn ema<F: ag::Float>(prices: &ag::NdArray<F>, span: usize) -> Result<ag::NdArray<F>, QuantError> {
+    if span < 1 {
+        return Err(QuantError::InvalidInput("span must be positive".to_string()));
+    }
+    if prices.is_empty() {
+        return Err(QuantError::InvalidInput("need at least 1 price".to_string()));
+    }
+    let alpha = F::from(2f64).unwrap() / F::from(span + 1).unwrap();
+    let mut average = prices.iter().next().cloned().unwrap();
+    let averages = prices
+        .iter()
+        .map(|&p| {
+            average = alpha * p + (F::one() - alpha) * average;
+            average
+        })
+        .collect::<Vec<_>>();
+    Ok(nd::Array1::from(averages).into_dyn())
+}
@@ -0,0 +1,54 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+
+/// The relative strength index of prices
+///
+/// RSI = 100 - 100 / (1 + G / L),
+///
+/// where `G` and `L` are Wilder's smoothed averages of the gains and the losses of the
+/// price changes. The first averages are the means of the first `period` changes, and
+/// each later change `c` updates them as `G ← (G (period - 1) + max(c, 0)) / period`,
+/// and likewise for the losses. The index lies in `[0, 100]` and is 100 when the
+/// average loss is zero.
+///
+/// * `prices`: The prices in time order.
+/// * `period`: The positive number of price changes in the smoothing.
+///
+/// * `rsi`: The `n - period` indices at each price after the first `period`, or
+///   `QuantError::InvalidInput` when the period is 0 or there are no more than
+///   `period` prices.
+pub fn rsi<F: ag::Float>(prices: &ag::NdArray<F>, period: usize) -> Result<ag::NdArray<F>, QuantError> {
+    if period < 1 || period >= prices.len() {
+        return Err(QuantError::InvalidInput(format!(
+            "period {} must be between 1 and the {} price changes",
+            period,
+            prices.len().saturating_sub(1)
+        )));
+    }
+    let prices = prices.iter().cloned().collect::<Vec<_>>();
+    let changes = prices.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
+    let gain = |c: F| c.max(F::zero());
+    let loss = |c: F| (-c).max(F::zero());
+
+    let n = F::from(period).unwrap();
+    let mut average_gain = changes[..period].iter().fold(F::zero(), |acc, &c| acc + gain(c)) / n;
+    let mut average_loss = changes[..period].iter().fold(F::zero(), |acc, &c| acc + loss(c)) / n;
+
+    let hundred = F::from(100f64).unwrap();
+    let index = |average_gain: F, average_loss: F| {
+        if average_loss == F::zero() {
+            hundred
+        } else {
+            hundred - hundred / (F::one() + average_gain / average_loss)
+        }
+    };
+    let mut values = vec![index(average_gain, average_loss)];
+    for &c in changes[period..].iter() {
+        average_gain = (average_gain * (n - F::one()) + gain(c)) / n;
+        average_loss = (average_loss * (n - F::one()) + loss(c)) / n;
+        values.push(index(average_gain, average_loss));
+    }
+    Ok(nd::Array1::from(values).into_dyn())
+}
@@ -0,0 +1,133 @@
+use std::path::Path;
+
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+use crate::options::model::OptionType;
+
+/// The quotes of an option chain, one entry per option in the file's row order.
+///
+/// * `types`: The type of each option, `Call` or `Put`.
+/// * `strikes`: The options' strike prices per share.
+/// * `maturities`: The time until each option's maturity as decimal of a year.
+/// * `bids`: The options' bid prices.
+/// * `asks`: The options' ask prices.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionChain<F> {
+    pub types: Vec<OptionType>,
+    pub strikes: ag::NdArray<F>,
+    pub maturities: ag::NdArray<F>,
+    pub bids: ag::NdArray<F>,
+    pub asks: ag::NdArray<F>,
+}
+
+impl<F: ag::Float> OptionChain<F> {
+    /// The number of options in the chain.
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Whether the chain has no options.
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// The midpoint of each option's bid and ask, the usual target price of an
+    /// implied volatility fit.
+    pub fn mids(&self) -> ag::NdArray<F> {
+        let half = F::from(0.5f64).unwrap();
+        (&self.bids + &self.asks).mapv(|sum| sum * half)
+    }
+}
+
+/// Load an option chain from a CSV file with a header row.
+///
+/// The columns `type`, `strike`, `maturity`, `bid` and `ask` are found by name,
+/// ignoring case, and any other columns are ignored. The type is `call`, `put`, `c`
+/// or `p`. Rows with an empty or `NaN` bid or ask have no usable quote and are
+/// skipped.
+///
+/// * `path`: The path of the CSV file.
+///
+/// * `chain`: The parsed quotes, `QuantError::Io` when the file cannot be read, or
+///   `QuantError::InvalidInput` when a column is missing or a row is malformed.
+pub fn load_option_chain<F: ag::Float, P: AsRef<Path>>(path: P) -> Result<OptionChain<F>, QuantError> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .trim(::csv::Trim::All)
+        .from_path(path.as_ref())
+        .map_err(|e| QuantError::Io(e.to_string()))?;
+    let headers = reader.headers().map_err(|e| QuantError::Io(e.to_string()))?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name))
+            .ok_or_else(|| QuantError::InvalidInput(format!("missing column {}", name)))
+    };
+    let (type_column, strike_column, maturity_column, bid_column, ask_column) = (
+        column("type")?,
+        column("strike")?,
+        column("maturity")?,
+        column("bid")?,
+        column("ask")?,
+    );
+
+    let mut types = Vec::new();
+    let (mut strikes, mut maturities, mut bids, mut asks) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for (i, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| QuantError::Io(e.to_string()))?;
+        // The header is line 1.
+        let line = i + 2;
+        let cell = |index: usize| record.get(index).unwrap_or("");
+
+        let (bid, ask) = match (quote(cell(bid_column), line)?, quote(cell(ask_column), line)?) {
+            (Some(bid), Some(ask)) => (bid, ask),
+            _ => continue,
+        };
+        types.push(option_type(cell(type_column), line)?);
+        strikes.push(number(cell(strike_column), "strike", line)?);
+        maturities.push(number(cell(maturity_column), "maturity", line)?);
+        bids.push(bid);
+        asks.push(ask);
+    }
+
+    let array = |values: Vec<f64>| {
+        values
+            .into_iter()
+            .map(|x| F::from(x).unwrap())
+            .collect::<nd::Array1<F>>()
+            .into_dyn()
+    };
+    Ok(OptionChain {
+        types,
+        strikes: array(strikes),
+        maturities: array(maturities),
+        bids: array(bids),
+        asks: array(asks),
+    })
+}
+
+/// Parse a required numeric cell.
+fn number(cell: &str, name: &str, line: usize) -> Result<f64, QuantError> {
+    match cell.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(QuantError::InvalidInput(format!("line {}: invalid {} {:?}", line, name, cell))),
+    }
+}
+
+/// Parse a quote cell, which is missing when empty or `NaN`.
+fn quote(cell: &str, line: usize) -> Result<Option<f64>, QuantError> {
+    if cell.is_empty() || cell.eq_ignore_ascii_case("nan") {
+        return Ok(None);
+    }
+    number(cell, "quote", line).map(Some)
+}
+
+/// Parse an option type cell.
+fn option_type(cell: &str, line: usize) -> Result<OptionType, QuantError> {
+    match cell.to_ascii_lowercase().as_str() {
+        "call" | "c" => Ok(OptionType::Call),
+        "put" | "p" => Ok(OptionType::Put),
+        _ => Err(QuantError::InvalidInput(format!("line {}: invalid option type {:?}", line, cell))),
+    }
+}
@@ -0,0 +1,2 @@
+#[cfg(feature = "csv")]
+pub mod csv;
@@ -1 +1,14 @@
+pub mod error;
+pub mod fixed_income;
+pub mod indicators;
+pub mod io;
+pub mod models;
+pub mod numerics;
 pub mod options;
+pub mod prelude;
+pub mod risk;
+#[cfg(feature = "serde")]
+mod serde_array;
+pub mod stats;
+pub mod strategy;
+pub mod timeseries;
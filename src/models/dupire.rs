@@ -0,0 +1,175 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+
+/// A local volatility surface σ(K, T) on a grid of strikes and maturities.
+///
+/// Queries between grid points interpolate the local variance bilinearly, and queries
+/// outside the grid are clamped to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalVolSurface<F> {
+    strikes: Vec<F>,
+    maturities: Vec<F>,
+    variances: nd::Array2<F>,
+}
+
+impl<F: ag::Float> LocalVolSurface<F> {
+    /// The strikes of the grid.
+    pub fn strikes(&self) -> &[F] {
+        &self.strikes
+    }
+
+    /// The maturities of the grid as decimal of a year.
+    pub fn maturities(&self) -> &[F] {
+        &self.maturities
+    }
+
+    /// The local vol at a strike and maturity, interpolated on the grid and clamped
+    /// outside of it.
+    ///
+    /// * `strike`: The strike price.
+    /// * `maturity`: The time as decimal of a year.
+    pub fn vol_at(&self, strike: F, maturity: F) -> F {
+        let (i, u) = locate(&self.strikes, strike);
+        let (j, w) = locate(&self.maturities, maturity);
+        let (i1, j1) = ((i + 1).min(self.strikes.len() - 1), (j + 1).min(self.maturities.len() - 1));
+        let v = &self.variances;
+        let near = v[[i, j]] + (v[[i1, j]] - v[[i, j]]) * u;
+        let far = v[[i, j1]] + (v[[i1, j1]] - v[[i, j1]]) * u;
+        (near + (far - near) * w).max(F::zero()).sqrt()
+    }
+}
+
+/// Build the local volatility surface implied by european call prices with Dupire's
+/// (1994) formula
+///
+/// σ²(K, T) = (∂C/∂T + rK ∂C/∂K) / (½ K² ∂²C/∂K²).
+///
+/// The derivatives are three point finite differences on the grid, which may be
+/// unevenly spaced: central in the interior and one sided at the first and last
+/// maturity. Differencing prices amplifies their noise, most of all in the density
+/// `∂²C/∂K²` of the denominator, so the surface is smoothed in two ways:
+///
+/// * Points where the density or the numerator is not positive, e.g. from a butterfly
+///   or calendar arbitrage in the quotes, or where the density is below `1e-6` of the
+///   largest density of the maturity, e.g. in the far wings where both are dominated
+///   by rounding, are discarded.
+/// * The discarded points, and the first and last strikes where the central
+///   difference is unavailable, take the local variance of the nearest kept strike of
+///   the same maturity.
+///
+/// The differences are accurate when the strikes resolve the density, i.e. their
+/// spacing is well below `Sσ√T` at the shortest maturity.
+///
+/// * `call_surface`: The `[strikes, maturities]` call prices, e.g. the layout of
+///   `black_scholes::price_surface`.
+/// * `strikes`: The strictly increasing strikes of the grid, at least 3.
+/// * `maturities`: The positive, strictly increasing maturities of the grid as decimal
+///   of a year, at least 2.
+/// * `rate`: The risk free interest rate as decimal.
+///
+/// * `surface`: The local vols, `QuantError::ShapeMismatch` when `call_surface` does not
+///   match the grid, or `QuantError::InvalidInput` when the grid is too small or
+///   unordered, or a maturity keeps no point.
+pub fn local_volatility<F: ag::Float>(
+    call_surface: ag::NdArrayView<F>,
+    strikes: &[F],
+    maturities: &[F],
+    rate: F,
+) -> Result<LocalVolSurface<F>, QuantError> {
+    let (n_k, n_t) = (strikes.len(), maturities.len());
+    QuantError::check_shape(&[n_k, n_t], call_surface.shape())?;
+    let increasing = |xs: &[F]| xs.iter().zip(xs.iter().skip(1)).all(|(a, b)| a < b);
+    if n_k < 3 || !increasing(strikes) {
+        return Err(QuantError::InvalidInput(
+            "need at least 3 strictly increasing strikes".to_string(),
+        ));
+    }
+    if n_t < 2 || !(maturities[0] > F::zero()) || !increasing(maturities) {
+        return Err(QuantError::InvalidInput(
+            "need at least 2 positive, strictly increasing maturities".to_string(),
+        ));
+    }
+    let prices = |i: usize, j: usize| call_surface[[i, j]];
+    let half = F::from(0.5f64).unwrap();
+    let threshold = F::from(1e-6f64).unwrap();
+
+    let mut variances = nd::Array2::<F>::zeros((n_k, n_t));
+    for j in 0..n_t {
+        let smile = (0..n_k).map(|i| prices(i, j)).collect::<Vec<_>>();
+        let densities = (1..n_k - 1)
+            .map(|i| half * strikes[i] * strikes[i] * second_derivative(strikes, &smile, i))
+            .collect::<Vec<_>>();
+        let largest = densities.iter().fold(F::zero(), |acc, &d| acc.max(d));
+
+        let kept = (1..n_k - 1)
+            .filter_map(|i| {
+                let term = (0..n_t).map(|jj| prices(i, jj)).collect::<Vec<_>>();
+                let numerator =
+                    first_derivative(maturities, &term, j) + rate * strikes[i] * first_derivative(strikes, &smile, i);
+                let density = densities[i - 1];
+                if density > threshold * largest && numerator >= F::zero() {
+                    Some((i, numerator / density))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        if kept.is_empty() {
+            return Err(QuantError::InvalidInput(format!(
+                "no strike of maturity {} has a positive local variance",
+                maturities[j].to_f64().unwrap()
+            )));
+        }
+        for i in 0..n_k {
+            let nearest = kept
+                .iter()
+                .min_by_key(|(kept_i, _)| (*kept_i as isize - i as isize).abs())
+                .unwrap();
+            variances[[i, j]] = nearest.1;
+        }
+    }
+
+    Ok(LocalVolSurface {
+        strikes: strikes.to_vec(),
+        maturities: maturities.to_vec(),
+        variances,
+    })
+}
+
+/// The three point derivative of `ys` at `xs[i]`, or the two point one on a grid of 2.
+fn first_derivative<F: ag::Float>(xs: &[F], ys: &[F], i: usize) -> F {
+    let n = xs.len();
+    if n == 2 {
+        return (ys[1] - ys[0]) / (xs[1] - xs[0]);
+    }
+    let a = i.saturating_sub(1).min(n - 3);
+    let (b, c) = (a + 1, a + 2);
+    let x = xs[i];
+    let (xa, xb, xc) = (xs[a], xs[b], xs[c]);
+    ys[a] * (x - xb + x - xc) / ((xa - xb) * (xa - xc))
+        + ys[b] * (x - xa + x - xc) / ((xb - xa) * (xb - xc))
+        + ys[c] * (x - xa + x - xb) / ((xc - xa) * (xc - xb))
+}
+
+/// The three point second derivative of `ys` at the interior point `xs[i]`.
+fn second_derivative<F: ag::Float>(xs: &[F], ys: &[F], i: usize) -> F {
+    let (xa, xb, xc) = (xs[i - 1], xs[i], xs[i + 1]);
+    let two = F::from(2f64).unwrap();
+    two * (ys[i - 1] / ((xa - xb) * (xa - xc)) + ys[i] / ((xb - xa) * (xb - xc)) + ys[i + 1] / ((xc - xa) * (xc - xb)))
+}
+
+/// The index of the grid interval holding `x` and the weight of its upper end,
+/// clamped to the grid.
+fn locate<F: ag::Float>(xs: &[F], x: F) -> (usize, F) {
+    let last = xs.len() - 1;
+    if x <= xs[0] {
+        return (0, F::zero());
+    }
+    if x >= xs[last] {
+        return (last, F::zero());
+    }
+    let i = xs.partition_point(|&v| v <= x) - 1;
+    (i, (x - xs[i]) / (xs[i + 1] - xs[i]))
+}
@@ -0,0 +1,32 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+/// The decay factor RiskMetrics uses for daily returns.
+pub const RISKMETRICS_LAMBDA: f64 = 0.94;
+
+/// The exponentially weighted moving average estimate of volatility
+///
+/// σ²ₜ = λ σ²ₜ₋₁ + (1 - λ) r²ₜ₋₁,
+///
+/// where each `σₜ` only uses the returns before period `t`. The recursion starts from
+/// the mean squared return of the series.
+///
+/// * `returns`: The demeaned returns per period as decimal, in time order.
+/// * `lambda`: The decay factor strictly between 0 and 1, `RISKMETRICS_LAMBDA` by
+///   convention for daily returns.
+///
+/// * `volatility`: The volatility per period of each return, with the same length as
+///   `returns`.
+pub fn ewma_volatility<F: ag::Float>(returns: &ag::NdArray<F>, lambda: F) -> ag::NdArray<F> {
+    let n = F::from(returns.len()).unwrap();
+    let mut variance = returns.iter().fold(F::zero(), |acc, &r| acc + r * r) / n;
+    let volatility = returns
+        .iter()
+        .map(|&r| {
+            let sigma = variance.sqrt();
+            variance = lambda * variance + (F::one() - lambda) * r * r;
+            sigma
+        })
+        .collect::<Vec<_>>();
+    nd::Array1::from(volatility).into_dyn()
+}
@@ -0,0 +1,196 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::op::{ComputeContext, GradientContext, Op, OpError};
+use autograd::tensor_ops as math;
+
+use autograd::prelude::*;
+use crate::error::QuantError;
+use crate::numerics::optimize::run_epochs;
+
+/// The parameters of a GARCH(1,1) model of the conditional variance of returns
+///
+/// σ²ₜ = ω + α r²ₜ₋₁ + β σ²ₜ₋₁
+///
+/// * `omega`: The positive constant ω of the variance recursion.
+/// * `alpha`: The non negative reaction α of the variance to the last squared return.
+/// * `beta`: The non negative persistence β of the last variance, with `α + β < 1`.
+/// * `variance`: The conditional variance of the period after the fitted returns.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GarchParams<F> {
+    pub omega: F,
+    pub alpha: F,
+    pub beta: F,
+    pub variance: F,
+}
+
+impl<F: ag::Float> GarchParams<F> {
+    /// The unconditional variance `ω / (1 - α - β)` the forecasts revert to.
+    pub fn long_run_variance(&self) -> F {
+        self.omega / (F::one() - self.alpha - self.beta)
+    }
+}
+
+/// Fit a GARCH(1,1) model to a series of returns by maximizing the gaussian
+/// log-likelihood with Adam.
+///
+/// The recursion starts from the sample variance of the returns. The constraints hold
+/// by construction: the persistence `α + β` is a sigmoid in `(0, 1)`, split between
+/// `α` and `β` by a second sigmoid, and the long run variance is the sample variance
+/// scaled by an exponential, which fixes `ω`.
+///
+/// * `returns`: The demeaned returns per period as decimal, in time order.
+///
+/// * `params`: The fitted parameters, or `QuantError::InvalidInput` with fewer than 3
+///   returns.
+pub fn fit_garch_11<F: ag::Float>(returns: &ag::NdArray<F>) -> Result<GarchParams<F>, QuantError> {
+    if returns.len() < 3 {
+        return Err(QuantError::InvalidInput("need at least 3 returns to fit".to_string()));
+    }
+    let returns = returns.iter().cloned().collect::<nd::Array1<F>>().into_dyn();
+    let n = F::from(returns.len()).unwrap();
+    let sample_variance = returns.iter().fold(F::zero(), |acc, &r| acc + r * r) / n;
+
+    // Start from α = 0.09 and β = 0.81 at the sample variance.
+    let logit = |x: f64| F::from((x / (1. - x)).ln()).unwrap();
+    let mut env = ag::VariableEnvironment::new();
+    let log_scale_id = env.name("log_scale").set(nd::arr1(&[F::zero()]).into_dyn());
+    let persistence_id = env.name("persistence").set(nd::arr1(&[logit(0.9)]).into_dyn());
+    let share_id = env.name("share").set(nd::arr1(&[logit(0.1)]).into_dyn());
+
+    let adam = ag::optimizers::adam::Adam::default("AdamGARCH", env.default_namespace().current_var_ids(), &mut env);
+
+    env.run(|ctx| {
+        let log_scale = ctx.variable("log_scale");
+        let persistence = ctx.variable("persistence");
+        let share = ctx.variable("share");
+        let r = ctx.placeholder("r", &[-1]);
+
+        let (omega, alpha, beta) = constrain(log_scale, persistence, share, sample_variance);
+        let theta = math::concat(&[omega, alpha, beta], 0);
+        let loss = ag::Tensor::builder(theta.graph())
+            .append_input(theta, false)
+            .append_input(r, false)
+            .build(NegativeLogLikelihood { initial_variance: sample_variance });
+        let grads = math::grad(&[loss], &[log_scale, persistence, share]);
+        let update = adam.get_update_op(&[log_scale, persistence, share], &grads, ctx);
+
+        run_epochs(ctx, &update, &[(r, returns.view())], 3000);
+    });
+
+    let value = |id| env.get_array_by_id(id).unwrap().clone().into_inner()[0];
+    let sigmoid = |x: F| F::one() / (F::one() + (-x).exp());
+    let (p, s) = (sigmoid(value(persistence_id)), sigmoid(value(share_id)));
+    let omega = sample_variance * value(log_scale_id).exp() * (F::one() - p);
+    let (alpha, beta) = (p * s, p * (F::one() - s));
+
+    let last = returns.iter().fold(sample_variance, |variance, &r| omega + alpha * r * r + beta * variance);
+    Ok(GarchParams { omega, alpha, beta, variance: last })
+}
+
+/// Forecast the conditional variance of the periods after the fitted returns
+///
+/// E[σ²ₜ₊ₕ] = V + (α + β)^{h-1} (σ²ₜ₊₁ - V),
+///
+/// where `V` is the long run variance.
+///
+/// * `params`: The fitted GARCH(1,1) parameters.
+/// * `horizon`: The number of periods to forecast.
+///
+/// * `variances`: The `[horizon]` variance forecasts, starting with `params.variance`.
+pub fn forecast_variance<F: ag::Float>(params: &GarchParams<F>, horizon: usize) -> ag::NdArray<F> {
+    let long_run = params.long_run_variance();
+    let persistence = params.alpha + params.beta;
+    nd::Array1::from_shape_fn(horizon, |h| long_run + persistence.powi(h as i32) * (params.variance - long_run))
+        .into_dyn()
+}
+
+/// Map the unconstrained variables to `(ω, α, β)` with `α, β ≥ 0` and `α + β < 1`.
+fn constrain<'graph, F: ag::Float>(
+    log_scale: ag::Tensor<'graph, F>,
+    persistence: ag::Tensor<'graph, F>,
+    share: ag::Tensor<'graph, F>,
+    sample_variance: F,
+) -> (ag::Tensor<'graph, F>, ag::Tensor<'graph, F>, ag::Tensor<'graph, F>) {
+    let p = math::sigmoid(persistence);
+    let s = math::sigmoid(share);
+    let omega = math::exp(log_scale) * (math::neg(p) + F::one()) * sample_variance;
+    (omega, p * s, p * (math::neg(s) + F::one()))
+}
+
+/// The mean gaussian negative log-likelihood, up to a constant, of the returns given
+/// `[ω, α, β]`, and its gradient with respect to the parameters.
+fn likelihood<F: ag::Float>(theta: &[F], initial_variance: F, returns: &[F]) -> (F, [F; 3]) {
+    let (omega, alpha, beta) = (theta[0], theta[1], theta[2]);
+    let half = F::from(0.5f64).unwrap();
+    let n = F::from(returns.len()).unwrap();
+
+    let mut variance = initial_variance;
+    let mut d_variance = [F::zero(); 3];
+    let mut loss = F::zero();
+    let mut grad = [F::zero(); 3];
+    for (t, &r) in returns.iter().enumerate() {
+        if t > 0 {
+            let last_square = returns[t - 1] * returns[t - 1];
+            d_variance = [
+                F::one() + beta * d_variance[0],
+                last_square + beta * d_variance[1],
+                variance + beta * d_variance[2],
+            ];
+            variance = omega + alpha * last_square + beta * variance;
+        }
+        loss = loss + half * (variance.ln() + r * r / variance) / n;
+        let d_loss = half * (F::one() / variance - r * r / (variance * variance)) / n;
+        for i in 0..3 {
+            grad[i] = grad[i] + d_loss * d_variance[i];
+        }
+    }
+    (loss, grad)
+}
+
+/// The negative log-likelihood of the returns, input `1`, given `[ω, α, β]`, input `0`.
+struct NegativeLogLikelihood<F> {
+    initial_variance: F,
+}
+
+impl<F: ag::Float> Op<F> for NegativeLogLikelihood<F> {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let theta = ctx.input(0).iter().cloned().collect::<Vec<_>>();
+        let returns = ctx.input(1).iter().cloned().collect::<Vec<_>>();
+        let (loss, _) = likelihood(&theta, self.initial_variance, &returns);
+        ctx.append_output(nd::arr1(&[loss]).into_dyn());
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let theta = ctx.input(0);
+        let returns = ctx.input(1);
+        let gy = ctx.output_grad();
+        let gtheta = ag::Tensor::builder(theta.graph())
+            .append_input(theta, false)
+            .append_input(returns, false)
+            .build(NegativeLogLikelihoodGrad { initial_variance: self.initial_variance });
+        ctx.append_input_grad(Some(gtheta * gy));
+        ctx.append_input_grad(None);
+    }
+}
+
+/// The gradient of `NegativeLogLikelihood` with respect to `[ω, α, β]`.
+struct NegativeLogLikelihoodGrad<F> {
+    initial_variance: F,
+}
+
+impl<F: ag::Float> Op<F> for NegativeLogLikelihoodGrad<F> {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let theta = ctx.input(0).iter().cloned().collect::<Vec<_>>();
+        let returns = ctx.input(1).iter().cloned().collect::<Vec<_>>();
+        let (_, grad) = likelihood(&theta, self.initial_variance, &returns);
+        ctx.append_output(nd::arr1(&grad).into_dyn());
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        ctx.append_input_grad(None);
+        ctx.append_input_grad(None);
+    }
+}
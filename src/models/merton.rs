@@ -0,0 +1,68 @@
+use autograd as ag;
+use autograd::tensor_ops as math;
+
+use crate::options::black_scholes::BlackScholesPricingModel;
+use crate::options::model::*;
+
+/// Price european calls under the Merton (1976) jump diffusion model, where the stock
+/// jumps by a lognormal factor `J`, `ln J ~ N(μ, δ²)`, at the times of a Poisson
+/// process with intensity `λ`.
+///
+/// Conditioned on `n` jumps the stock is lognormal, so the price is the Poisson
+/// weighted sum of Black-Scholes prices
+///
+/// C = Σₙ e^{-λ'T} (λ'T)ⁿ / n! · BS(S, K, σₙ, rₙ, T),
+///
+/// with `k̄ = e^{μ + δ²/2} - 1`, `λ' = λ(1 + k̄)`, `σₙ² = σ² + nδ²/T` and
+/// `rₙ = r - λk̄ + n ln(1 + k̄) / T`. The series is truncated once the remaining
+/// Poisson weights are negligible.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The diffusive volatility of the stocks in decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `jump_intensity`: The expected number of jumps `λ` per year.
+/// * `jump_mean`: The mean `μ` of the log jump size.
+/// * `jump_std`: The standard deviation `δ` of the log jump size.
+///
+/// * `prices`: The price of the calls.
+pub fn price_call_merton<'graph, A, F: ag::Float>(
+    s: A,
+    k: A,
+    vol: A,
+    r: F,
+    t: F,
+    jump_intensity: F,
+    jump_mean: F,
+    jump_std: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let half = F::from(0.5f64).unwrap();
+    let tol = F::from(1e-16f64).unwrap();
+    let mean_jump = (jump_mean + half * jump_std * jump_std).exp() - F::one();
+    let intensity = jump_intensity * (F::one() + mean_jump) * t;
+    let dividends = *vol.as_ref() * F::zero();
+
+    let mut weight = (-intensity).exp();
+    let mut cumulative = weight;
+    let r_0 = r - jump_intensity * mean_jump;
+    let mut price =
+        BlackScholesPricingModel::price(OptionType::Call, s.as_ref(), k.as_ref(), vol.as_ref(), &dividends, r_0, t) * weight;
+    for n in 1..200 {
+        if F::one() - cumulative < tol {
+            break;
+        }
+        let jumps = F::from(n).unwrap();
+        weight = weight * intensity / jumps;
+        cumulative = cumulative + weight;
+
+        let vol_n = math::sqrt(math::square(vol.as_ref()) + jumps * jump_std * jump_std / t);
+        let r_n = r_0 + jumps * (F::one() + mean_jump).ln() / t;
+        price = price
+            + BlackScholesPricingModel::price(OptionType::Call, s.as_ref(), k.as_ref(), &vol_n, &dividends, r_n, t) * weight;
+    }
+    price
+}
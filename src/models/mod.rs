@@ -0,0 +1,11 @@
+pub mod cir;
+pub mod dupire;
+pub mod ewma;
+pub mod garch;
+pub mod gbm;
+pub mod heston;
+pub mod merton;
+pub mod ou;
+pub mod sabr;
+pub mod svi;
+pub mod vasicek;
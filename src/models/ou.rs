@@ -0,0 +1,110 @@
+use autograd as ag;
+
+use autograd::rand::Rng;
+
+use crate::error::QuantError;
+use crate::models::vasicek::simulate_vasicek;
+
+/// The parameters of an Ornstein-Uhlenbeck process
+///
+/// dX = κ(θ - X) dt + σ dW
+///
+/// * `kappa`: The positive speed of mean reversion, per year.
+/// * `theta`: The long run mean the process reverts to.
+/// * `sigma`: The volatility of the process, per square root of a year.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OuParams<F> {
+    pub kappa: F,
+    pub theta: F,
+    pub sigma: F,
+}
+
+impl<F: ag::Float> OuParams<F> {
+    /// The time `ln 2 / κ` for the expected distance to `θ` to halve, in years.
+    pub fn half_life(&self) -> F {
+        F::from(2f64).unwrap().ln() / self.kappa
+    }
+}
+
+/// Simulate paths of an Ornstein-Uhlenbeck process, e.g. a commodity price or the
+/// spread of a pair of assets.
+///
+/// The process is the one of the Vasicek short rate, so the paths use the same exact
+/// gaussian transition
+///
+/// X_{t+Δt} = θ + (X_t - θ) e^{-κΔt} + σ √((1 - e^{-2κΔt}) / 2κ) Z,
+///
+/// and carry no discretization error at the simulation dates. The result has shape
+/// `[n_paths, n_steps + 1]` with column `0` equal to `x0`.
+///
+/// * `x0`: The initial value of the process.
+/// * `kappa`: The positive speed of mean reversion.
+/// * `theta`: The long run mean of the process.
+/// * `sigma`: The volatility of the process.
+/// * `t`: The length of the simulation as decimal of a year.
+/// * `n_steps`: The number of time steps on each path.
+/// * `n_paths`: The number of simulated paths.
+/// * `rng`: The source of randomness for the normal draws.
+///
+/// * `paths`: The simulated values.
+pub fn simulate_ou<F: ag::Float, R: Rng>(
+    x0: F,
+    kappa: F,
+    theta: F,
+    sigma: F,
+    t: F,
+    n_steps: usize,
+    n_paths: usize,
+    rng: &mut R,
+) -> ag::NdArray<F> {
+    simulate_vasicek(x0, kappa, theta, sigma, t, n_steps, n_paths, rng)
+}
+
+/// Fit an Ornstein-Uhlenbeck process to an evenly spaced series by maximum likelihood.
+///
+/// Sampled every `dt` the process is the AR(1) `X_{i+1} = a + b X_i + ε` with
+/// `b = e^{-κ dt}`, `a = θ(1 - b)` and `Var ε = σ²(1 - b²) / 2κ`, so the conditional
+/// maximum likelihood estimates follow in closed form from the least squares
+/// regression of each value on the one before.
+///
+/// * `series`: The observed values in time order.
+/// * `dt`: The positive time between observations as decimal of a year.
+///
+/// * `params`: The fitted parameters, or `QuantError::InvalidInput` with fewer than 3
+///   values, a `dt` that is not positive, or a series whose fitted slope `b` is not
+///   strictly between 0 and 1 and so does not mean revert.
+pub fn fit_ou<F: ag::Float>(series: &ag::NdArray<F>, dt: F) -> Result<OuParams<F>, QuantError> {
+    if series.len() < 3 {
+        return Err(QuantError::InvalidInput("need at least 3 values to fit".to_string()));
+    }
+    if !(dt > F::zero()) {
+        return Err(QuantError::InvalidInput("dt must be positive".to_string()));
+    }
+    let values = series.iter().cloned().collect::<Vec<_>>();
+    let (x, y) = (&values[..values.len() - 1], &values[1..]);
+    let n = F::from(x.len()).unwrap();
+    let mean_x = x.iter().fold(F::zero(), |acc, &v| acc + v) / n;
+    let mean_y = y.iter().fold(F::zero(), |acc, &v| acc + v) / n;
+    let (covariance, variance) = x.iter().zip(y.iter()).fold((F::zero(), F::zero()), |(cov, var), (&xi, &yi)| {
+        (cov + (xi - mean_x) * (yi - mean_y), var + (xi - mean_x) * (xi - mean_x))
+    });
+    let b = covariance / variance;
+    if !(b > F::zero() && b < F::one()) {
+        return Err(QuantError::InvalidInput("series does not mean revert".to_string()));
+    }
+    let a = mean_y - b * mean_x;
+    let residual_variance = x
+        .iter()
+        .zip(y.iter())
+        .fold(F::zero(), |acc, (&xi, &yi)| acc + (yi - a - b * xi).powi(2))
+        / n;
+
+    let kappa = -b.ln() / dt;
+    let two = F::from(2f64).unwrap();
+    Ok(OuParams {
+        kappa,
+        theta: a / (F::one() - b),
+        sigma: (residual_variance * two * kappa / (F::one() - b * b)).sqrt(),
+    })
+}
@@ -0,0 +1,50 @@
+use autograd as ag;
+
+/// The Black implied volatility of the SABR model
+///
+/// dF = α Fᵝ dW₁, dα = ν α dW₂, d⟨W₁, W₂⟩ = ρ dt
+///
+/// from the lognormal approximation of Hagan et al. (2002). The result can be passed
+/// as the volatility of a Black-76 price.
+///
+/// The formula contains `z / x(z)` with `z = ν/α (FK)^{(1-β)/2} ln(F/K)`, which is
+/// `0 / 0` at the money, so for `|z|` below 1e-8 it is replaced by its expansion
+/// `1 - ρz/2`.
+///
+/// * `f`: The forward price of the underlying.
+/// * `k`: The option's strike price.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `alpha`: The initial volatility level `α`.
+/// * `beta`: The CEV exponent `β` between 0 and 1.
+/// * `rho`: The correlation `ρ` between the forward and volatility shocks.
+/// * `nu`: The volatility of volatility `ν`.
+///
+/// * `vol`: The Black implied volatility in decimal.
+pub fn sabr_implied_vol<F: ag::Float>(f: F, k: F, t: F, alpha: F, beta: F, rho: F, nu: F) -> F {
+    let one = F::one();
+    let two = F::from(2f64).unwrap();
+    let c = |x: f64| F::from(x).unwrap();
+
+    let one_beta = one - beta;
+    let fk = (f * k).powf(one_beta / two);
+    let log_fk = (f / k).ln();
+
+    let z = nu / alpha * fk * log_fk;
+    let ratio = if z.abs() < c(1e-8) {
+        one - rho * z / two
+    } else {
+        let x = (((one - two * rho * z + z * z).sqrt() + z - rho) / (one - rho)).ln();
+        z / x
+    };
+
+    let denominator = fk
+        * (one
+            + one_beta.powi(2) / c(24.) * log_fk.powi(2)
+            + one_beta.powi(4) / c(1920.) * log_fk.powi(4));
+    let correction = one
+        + (one_beta.powi(2) / c(24.) * alpha * alpha / (fk * fk)
+            + rho * beta * nu * alpha / (c(4.) * fk)
+            + (two - c(3.) * rho * rho) / c(24.) * nu * nu)
+            * t;
+    alpha / denominator * ratio * correction
+}
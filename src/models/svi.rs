@@ -0,0 +1,169 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use autograd::prelude::*;
+use crate::error::QuantError;
+use crate::numerics::linalg::least_squares;
+use crate::numerics::optimize::run_epochs;
+
+/// The parameters of a raw SVI smile, the total implied variance
+///
+/// w(k) = a + b (ρ (k - m) + √((k - m)² + σ²))
+///
+/// as a function of the log moneyness k = ln(K / F), after Gatheral (2004).
+///
+/// * `a`: The overall level of the variance.
+/// * `b`: The non-negative slope of the wings.
+/// * `rho`: The skew, between -1 and 1, tilting the left and right wings.
+/// * `m`: The log moneyness of the smile's vertex.
+/// * `sigma`: The positive curvature of the smile at its vertex.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SviParams<F> {
+    pub a: F,
+    pub b: F,
+    pub rho: F,
+    pub m: F,
+    pub sigma: F,
+}
+
+impl<F: ag::Float> SviParams<F> {
+    /// The total implied variance σ²T at log moneyness `k`.
+    pub fn total_variance(&self, k: F) -> F {
+        let x = k - self.m;
+        self.a + self.b * (self.rho * x + (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    /// The implied volatility of an option on the smile.
+    ///
+    /// * `strike`: The option's strike price.
+    /// * `forward`: The forward price of the underlying at maturity.
+    /// * `t`: The time until option maturity as decimal of a year.
+    pub fn implied_vol(&self, strike: F, forward: F, t: F) -> F {
+        (self.total_variance((strike / forward).ln()) / t).max(F::zero()).sqrt()
+    }
+}
+
+/// Fit a raw SVI smile to market total implied variances by minimizing the mean
+/// squared variance error with Adam.
+///
+/// For a fixed vertex `m` and curvature `sigma` the smile is linear in `a`, `bρ` and
+/// `b`, so the fit starts from the best point of a grid over `m` and `sigma` with
+/// the linear parameters solved by least squares. Adam then refines all five
+/// parameters, with `b` and `sigma` through their logarithms and `rho` through its
+/// inverse hyperbolic tangent so that they stay in range.
+///
+/// * `log_moneyness`: The log moneyness ln(K / F) of each quote.
+/// * `total_variance`: The total implied variance σ²T of each quote.
+///
+/// * `params`: The fitted parameters, `QuantError::ShapeMismatch` when the inputs'
+///   shapes differ, or `QuantError::InvalidInput` with fewer than 5 quotes or a total
+///   variance that is not positive.
+pub fn fit_svi<F: ag::Float>(
+    log_moneyness: ag::NdArrayView<F>,
+    total_variance: ag::NdArrayView<F>,
+) -> Result<SviParams<F>, QuantError> {
+    QuantError::check_shape(log_moneyness.shape(), total_variance.shape())?;
+    if total_variance.len() < 5 {
+        return Err(QuantError::InvalidInput("need at least 5 quotes to fit".to_string()));
+    }
+    if total_variance.iter().any(|w| !(*w > F::zero())) {
+        return Err(QuantError::InvalidInput("total variances must be positive".to_string()));
+    }
+    let initial = initial_guess(log_moneyness, total_variance);
+    // Scale the errors to the size of the variances so that Adam's steps do not
+    // depend on the level of the smile.
+    let n = F::from(total_variance.len()).unwrap();
+    let inverse_scale = n / total_variance.iter().fold(F::zero(), |acc, &w| acc + w);
+
+    let mut env = ag::VariableEnvironment::new();
+    let a_id = env.name("a").set(nd::arr1(&[initial.a]).into_dyn());
+    let m_id = env.name("m").set(nd::arr1(&[initial.m]).into_dyn());
+    let log_b_id = env.name("log_b").set(nd::arr1(&[initial.b.ln()]).into_dyn());
+    let atanh_rho_id = env.name("atanh_rho").set(nd::arr1(&[initial.rho.atanh()]).into_dyn());
+    let log_sigma_id = env.name("log_sigma").set(nd::arr1(&[initial.sigma.ln()]).into_dyn());
+
+    let adam = ag::optimizers::adam::Adam::default("AdamSVI", env.default_namespace().current_var_ids(), &mut env);
+
+    env.run(|ctx| {
+        let a = ctx.variable("a");
+        let m = ctx.variable("m");
+        let log_b = ctx.variable("log_b");
+        let atanh_rho = ctx.variable("atanh_rho");
+        let log_sigma = ctx.variable("log_sigma");
+        let k = ctx.placeholder("k", &[-1]);
+        let w = ctx.placeholder("w", &[-1]);
+
+        let x = k - m;
+        let sigma = math::exp(log_sigma);
+        let wings = math::tanh(atanh_rho) * x + math::sqrt(math::square(x) + math::square(sigma));
+        let pred = a + math::exp(log_b) * wings;
+
+        let loss = math::reduce_mean(math::square((pred - w) * inverse_scale), &[0], false);
+        let grads = math::grad(&[loss], &[a, m, log_b, atanh_rho, log_sigma]);
+        let update = adam.get_update_op(&[a, m, log_b, atanh_rho, log_sigma], &grads, ctx);
+
+        run_epochs(ctx, &update, &[(k, log_moneyness.view()), (w, total_variance.view())], 5000);
+    });
+
+    let value = |id| env.get_array_by_id(id).unwrap().clone().into_inner()[0];
+    Ok(SviParams {
+        a: value(a_id),
+        b: value(log_b_id).exp(),
+        rho: value(atanh_rho_id).tanh(),
+        m: value(m_id),
+        sigma: value(log_sigma_id).exp(),
+    })
+}
+
+/// The least squares parameters of the best fitting point on a grid of the vertex `m`
+/// over the quotes' log moneyness and of `sigma` between 0.01 and 1.
+fn initial_guess<F: ag::Float>(log_moneyness: ag::NdArrayView<F>, total_variance: ag::NdArrayView<F>) -> SviParams<F> {
+    let k = log_moneyness.iter().cloned().collect::<Vec<_>>();
+    let w = nd::Array1::from(total_variance.iter().cloned().collect::<Vec<_>>());
+    let (k_lo, k_hi) = k.iter().fold((k[0], k[0]), |(lo, hi), &x| (lo.min(x), hi.max(x)));
+    let (sigma_lo, sigma_hi) = (F::from(0.01f64).unwrap().ln(), F::zero());
+    let n_grid = 41;
+    let grid = |lo: F, hi: F, i: usize| lo + (hi - lo) * F::from(i).unwrap() / F::from(n_grid - 1).unwrap();
+    // Keep the starting point strictly inside the ranges of `b` and `rho`.
+    let (min_b, max_rho) = (F::from(1e-4f64).unwrap(), F::from(0.99f64).unwrap());
+
+    let mut best: Option<(F, SviParams<F>)> = None;
+    for i in 0..n_grid {
+        let m = grid(k_lo, k_hi, i);
+        for j in 0..n_grid {
+            let sigma = grid(sigma_lo, sigma_hi, j).exp();
+            let basis = nd::Array2::from_shape_fn((k.len(), 3), |(row, column)| {
+                let x = k[row] - m;
+                match column {
+                    0 => F::one(),
+                    1 => x,
+                    _ => (x * x + sigma * sigma).sqrt(),
+                }
+            });
+            let coefficients = match least_squares(&basis, &w) {
+                Some(coefficients) => coefficients,
+                None => continue,
+            };
+            let residual = &basis.dot(&coefficients) - &w;
+            let loss = residual.iter().fold(F::zero(), |acc, &e| acc + e * e);
+            if best.as_ref().map_or(true, |(best_loss, _)| loss < *best_loss) {
+                let b = coefficients[2].max(min_b);
+                let rho = (coefficients[1] / b).max(-max_rho).min(max_rho);
+                best = Some((loss, SviParams { a: coefficients[0], b, rho, m, sigma }));
+            }
+        }
+    }
+
+    match best {
+        Some((_, params)) => params,
+        None => SviParams {
+            a: w.iter().fold(w[0], |lo, &x| lo.min(x)),
+            b: F::from(0.1f64).unwrap(),
+            rho: F::zero(),
+            m: F::zero(),
+            sigma: F::from(0.1f64).unwrap(),
+        },
+    }
+}
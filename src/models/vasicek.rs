@@ -0,0 +1,76 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use autograd::rand::Rng;
+
+use crate::stats::normal;
+
+/// Simulate short rate paths of the Vasicek model
+///
+/// dr = κ(θ - r) dt + σ dW
+///
+/// using the exact gaussian transition between time steps, so the paths carry no
+/// discretization error at the simulation dates.
+///
+/// The result has shape `[n_paths, n_steps + 1]`, laid out like `simulate_gbm_paths`
+/// with column `0` equal to `r0`.
+///
+/// * `r0`: The initial short rate as decimal.
+/// * `kappa`: The positive speed of mean reversion.
+/// * `theta`: The long run mean of the short rate as decimal.
+/// * `sigma`: The volatility of the short rate.
+/// * `t`: The length of the simulation as decimal of a year.
+/// * `n_steps`: The number of time steps on each path.
+/// * `n_paths`: The number of simulated paths.
+/// * `rng`: The source of randomness for the normal draws.
+///
+/// * `paths`: The simulated short rates.
+pub fn simulate_vasicek<F: ag::Float, R: Rng>(
+    r0: F,
+    kappa: F,
+    theta: F,
+    sigma: F,
+    t: F,
+    n_steps: usize,
+    n_paths: usize,
+    rng: &mut R,
+) -> ag::NdArray<F> {
+    let two = F::from(2f64).unwrap();
+    let dt = t / F::from(n_steps).unwrap();
+    let decay = (-kappa * dt).exp();
+    let std = sigma * ((F::one() - (-two * kappa * dt).exp()) / (two * kappa)).sqrt();
+    let z = normal::sample(&[n_paths, n_steps], F::zero(), F::one(), rng)
+        .into_dimensionality::<nd::Ix2>()
+        .unwrap();
+
+    let mut paths = nd::Array2::<F>::zeros((n_paths, n_steps + 1));
+    for i in 0..n_paths {
+        let mut rate = r0;
+        paths[[i, 0]] = rate;
+        for j in 1..n_steps + 1 {
+            rate = theta + (rate - theta) * decay + std * z[[i, j - 1]];
+            paths[[i, j]] = rate;
+        }
+    }
+    paths.into_dyn()
+}
+
+/// The price of a zero coupon bond paying `1` at maturity under the Vasicek model
+///
+/// P = A e^{-B r₀}, B = (1 - e^{-κT}) / κ,
+/// A = exp((θ - σ²/(2κ²))(B - T) - σ²B²/(4κ))
+///
+/// * `r0`: The initial short rate as decimal.
+/// * `kappa`: The positive speed of mean reversion.
+/// * `theta`: The long run mean of the short rate as decimal.
+/// * `sigma`: The volatility of the short rate.
+/// * `t`: The time until the bond's maturity as decimal of a year.
+///
+/// * `price`: The price of the bond.
+pub fn vasicek_bond_price<F: ag::Float>(r0: F, kappa: F, theta: F, sigma: F, t: F) -> F {
+    let two = F::from(2f64).unwrap();
+    let four = F::from(4f64).unwrap();
+    let b = (F::one() - (-kappa * t).exp()) / kappa;
+    let log_a = (theta - sigma * sigma / (two * kappa * kappa)) * (b - t) - sigma * sigma * b * b / (four * kappa);
+    (log_a - b * r0).exp()
+}
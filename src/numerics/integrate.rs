@@ -0,0 +1,75 @@
+use autograd as ag;
+
+/// Integrate `f` over [a, b] with `n` point Gauss-Legendre quadrature, which is exact
+/// for polynomials of degree up to 2n - 1.
+///
+/// * `f`: The integrand.
+/// * `a`: The lower limit of integration.
+/// * `b`: The upper limit of integration.
+/// * `n`: The number of quadrature points.
+///
+/// * `integral`: The approximate integral, zero when `n` is zero.
+pub fn gauss_legendre<F: ag::Float, G: Fn(F) -> F>(f: G, a: F, b: F, n: usize) -> F {
+    let half = F::from(0.5f64).unwrap();
+    let (mid, radius) = ((a + b) * half, (b - a) * half);
+    let (nodes, weights) = gauss_legendre_nodes::<F>(n);
+    nodes.iter().zip(weights.iter()).fold(F::zero(), |acc, (&x, &w)| acc + w * f(mid + radius * x)) * radius
+}
+
+/// The nodes and weights of `n` point Gauss-Legendre quadrature on [-1, 1].
+///
+/// The nodes are the roots of the Legendre polynomial P_n, found by Newton's method
+/// from the asymptotic approximation cos(π (i - 1/4) / (n + 1/2)). They are accurate
+/// to double precision for orders into the hundreds.
+///
+/// * `n`: The number of quadrature points.
+///
+/// * `(nodes, weights)`: The nodes in decreasing order and their weights.
+pub fn gauss_legendre_nodes<F: ag::Float>(n: usize) -> (Vec<F>, Vec<F>) {
+    let mut nodes = Vec::with_capacity(n);
+    let mut weights = Vec::with_capacity(n);
+    for i in 1..n + 1 {
+        let mut x = (std::f64::consts::PI * (i as f64 - 0.25) / (n as f64 + 0.5)).cos();
+        let mut slope = 1.;
+        for _ in 0..100 {
+            // Evaluate the Legendre polynomial P_n(x) by its recurrence.
+            let (mut p0, mut p1) = (1., x);
+            for m in 2..n + 1 {
+                let p2 = ((2 * m - 1) as f64 * x * p1 - (m - 1) as f64 * p0) / m as f64;
+                p0 = p1;
+                p1 = p2;
+            }
+            slope = n as f64 * (x * p1 - p0) / (x * x - 1.);
+            let step = p1 / slope;
+            x -= step;
+            if step.abs() < 1e-15 {
+                break;
+            }
+        }
+        nodes.push(F::from(x).unwrap());
+        weights.push(F::from(2. / ((1. - x * x) * slope * slope)).unwrap());
+    }
+    (nodes, weights)
+}
+
+/// Integrate `f` over [a, b] with the composite Simpson's rule on `n` equal intervals.
+///
+/// The rule needs an even number of intervals, so an odd `n` is rounded up. The error
+/// falls as n⁻⁴ for smooth integrands.
+///
+/// * `f`: The integrand.
+/// * `a`: The lower limit of integration.
+/// * `b`: The upper limit of integration.
+/// * `n`: The number of intervals.
+///
+/// * `integral`: The approximate integral.
+pub fn simpson<F: ag::Float, G: Fn(F) -> F>(f: G, a: F, b: F, n: usize) -> F {
+    let n = (n.max(2) + 1) / 2 * 2;
+    let h = (b - a) / F::from(n).unwrap();
+    let (two, four) = (F::from(2f64).unwrap(), F::from(4f64).unwrap());
+    let interior = (1..n).fold(F::zero(), |acc, i| {
+        let weight = if i % 2 == 1 { four } else { two };
+        acc + weight * f(a + h * F::from(i).unwrap())
+    });
+    (f(a) + interior + f(b)) * h / F::from(3f64).unwrap()
+}
@@ -0,0 +1,154 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+
+/// A natural cubic spline through a set of knots.
+///
+/// On each interval the spline is the cubic that matches the knots' values and has a
+/// continuous first and second derivative across the knots, with a second derivative
+/// of zero at both ends. Queries outside the knots are extrapolated flat at the end
+/// values, so the spline never runs away beyond the data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CubicSpline<F> {
+    xs: Vec<F>,
+    ys: Vec<F>,
+    /// The second derivative of the spline at each knot.
+    curvatures: Vec<F>,
+}
+
+impl<F: ag::Float> CubicSpline<F> {
+    /// Fit a natural cubic spline through the knots `(xs[i], ys[i])`.
+    ///
+    /// * `xs`: The strictly increasing knot positions.
+    /// * `ys`: The value at each knot.
+    ///
+    /// * `spline`: The spline, `QuantError::ShapeMismatch` when the lengths differ, or
+    ///   `QuantError::InvalidInput` when there are no knots or `xs` is not strictly
+    ///   increasing.
+    pub fn new(xs: Vec<F>, ys: Vec<F>) -> Result<Self, QuantError> {
+        QuantError::check_shape(&[xs.len()], &[ys.len()])?;
+        let increasing = xs.iter().zip(xs.iter().skip(1)).all(|(a, b)| a < b);
+        if xs.is_empty() || !increasing {
+            return Err(QuantError::InvalidInput("knots must be strictly increasing".to_string()));
+        }
+        Ok(Self::natural(xs, ys))
+    }
+
+    /// Fit the spline to knots that are already known to be valid.
+    pub(crate) fn natural(xs: Vec<F>, ys: Vec<F>) -> Self {
+        let n = xs.len();
+        let mut curvatures = vec![F::zero(); n];
+        if n > 2 {
+            // Solve the tridiagonal system for the interior curvatures with the
+            // Thomas algorithm.
+            let (two, six) = (F::from(2f64).unwrap(), F::from(6f64).unwrap());
+            let h = (0..n - 1).map(|i| xs[i + 1] - xs[i]).collect::<Vec<_>>();
+            let mut diagonal = vec![F::zero(); n];
+            let mut rhs = vec![F::zero(); n];
+            for i in 1..n - 1 {
+                diagonal[i] = two * (h[i - 1] + h[i]);
+                rhs[i] = six * ((ys[i + 1] - ys[i]) / h[i] - (ys[i] - ys[i - 1]) / h[i - 1]);
+                if i > 1 {
+                    let factor = h[i - 1] / diagonal[i - 1];
+                    diagonal[i] = diagonal[i] - factor * h[i - 1];
+                    rhs[i] = rhs[i] - factor * rhs[i - 1];
+                }
+            }
+            for i in (1..n - 1).rev() {
+                curvatures[i] = (rhs[i] - h[i] * curvatures[i + 1]) / diagonal[i];
+            }
+        }
+        CubicSpline { xs, ys, curvatures }
+    }
+
+    /// The knot positions.
+    pub fn xs(&self) -> &[F] {
+        &self.xs
+    }
+
+    /// The value at each knot.
+    pub fn ys(&self) -> &[F] {
+        &self.ys
+    }
+
+    /// The value of the spline at `x`, flat beyond the first and last knots.
+    pub fn eval(&self, x: F) -> F {
+        match self.locate(x) {
+            Segment::Below => self.ys[0],
+            Segment::Above => self.ys[self.ys.len() - 1],
+            Segment::Inside(i, h, a, b) => {
+                let six = F::from(6f64).unwrap();
+                a * self.ys[i]
+                    + b * self.ys[i + 1]
+                    + ((a * a * a - a) * self.curvatures[i] + (b * b * b - b) * self.curvatures[i + 1]) * h * h / six
+            }
+        }
+    }
+
+    /// The first derivative of the spline at `x`, zero beyond the knots.
+    pub fn derivative(&self, x: F) -> F {
+        match self.locate(x) {
+            Segment::Below | Segment::Above => F::zero(),
+            Segment::Inside(i, h, a, b) => {
+                let (three, six) = (F::from(3f64).unwrap(), F::from(6f64).unwrap());
+                let right = (three * b * b - F::one()) * self.curvatures[i + 1];
+                let left = (three * a * a - F::one()) * self.curvatures[i];
+                (self.ys[i + 1] - self.ys[i]) / h + (right - left) * h / six
+            }
+        }
+    }
+
+    /// The second derivative of the spline at `x`, zero beyond the knots.
+    pub fn second_derivative(&self, x: F) -> F {
+        match self.locate(x) {
+            Segment::Below | Segment::Above => F::zero(),
+            Segment::Inside(i, _, a, b) => a * self.curvatures[i] + b * self.curvatures[i + 1],
+        }
+    }
+
+    /// Find the interval holding `x` and its linear weights on the interval's ends.
+    fn locate(&self, x: F) -> Segment<F> {
+        let n = self.xs.len();
+        if n == 1 || x < self.xs[0] {
+            return Segment::Below;
+        }
+        if x > self.xs[n - 1] {
+            return Segment::Above;
+        }
+        let i = self.xs.partition_point(|&knot| knot <= x).saturating_sub(1).min(n - 2);
+        let h = self.xs[i + 1] - self.xs[i];
+        let b = (x - self.xs[i]) / h;
+        Segment::Inside(i, h, F::one() - b, b)
+    }
+}
+
+/// Where a query falls relative to a spline's knots.
+enum Segment<F> {
+    Below,
+    Above,
+    /// The interval's index and width, and the weights of its left and right knots.
+    Inside(usize, F, F, F),
+}
+
+/// Interpolate linearly between the points `(xs[i], ys[i])`, extrapolating flat at
+/// the end values beyond the first and last points.
+///
+/// Unlike `CubicSpline`, linear interpolation never overshoots the data, which makes
+/// it the safer fallback for noisy or sparse points.
+///
+/// * `xs`: The strictly increasing, non-empty point positions.
+/// * `ys`: The value at each point.
+/// * `x`: The query.
+///
+/// * `y`: The interpolated value.
+pub fn linear_interpolation<F: ag::Float>(xs: &[F], ys: &[F], x: F) -> F {
+    let n = xs.len();
+    if x <= xs[0] {
+        return ys[0];
+    }
+    if x >= xs[n - 1] {
+        return ys[n - 1];
+    }
+    let i = xs.partition_point(|&point| point <= x).saturating_sub(1).min(n - 2);
+    ys[i] + (ys[i + 1] - ys[i]) * (x - xs[i]) / (xs[i + 1] - xs[i])
+}
@@ -0,0 +1,134 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+
+/// Solve the square linear system `a x = b` by gaussian elimination with
+/// partial pivoting.
+///
+/// * `a`: The `[n, n]` coefficient matrix.
+/// * `b`: The `[n]` right hand side.
+///
+/// * `x`: The solution, or `None` when `a` is singular.
+pub fn solve<F: ag::Float>(a: &nd::Array2<F>, b: &nd::Array1<F>) -> Option<nd::Array1<F>> {
+    let n = b.len();
+    let mut a = a.clone();
+    let mut b = b.clone();
+
+    for col in 0..n {
+        let pivot = (col..n).fold(col, |best, row| {
+            if a[[row, col]].abs() > a[[best, col]].abs() {
+                row
+            } else {
+                best
+            }
+        });
+        if a[[pivot, col]].abs() <= F::epsilon() {
+            return None;
+        }
+        if pivot != col {
+            for j in 0..n {
+                a.swap([pivot, j], [col, j]);
+            }
+            b.swap(pivot, col);
+        }
+        for row in col + 1..n {
+            let factor = a[[row, col]] / a[[col, col]];
+            for j in col..n {
+                let value = a[[col, j]];
+                a[[row, j]] -= factor * value;
+            }
+            let value = b[col];
+            b[row] -= factor * value;
+        }
+    }
+
+    let mut x = nd::Array1::<F>::zeros(n);
+    for row in (0..n).rev() {
+        let tail = (row + 1..n).fold(F::zero(), |acc, j| acc + a[[row, j]] * x[j]);
+        x[row] = (b[row] - tail) / a[[row, row]];
+    }
+    Some(x)
+}
+
+/// Solve the tridiagonal linear system
+///
+/// lower[i] x[i - 1] + diagonal[i] x[i] + upper[i] x[i + 1] = rhs[i]
+///
+/// with the Thomas algorithm, in `O(n)`. `lower[0]` and `upper[n - 1]` are ignored.
+/// The algorithm does not pivot, so it is stable for diagonally dominant systems such
+/// as implicit finite difference steps.
+///
+/// * `lower`: The `[n]` sub diagonal.
+/// * `diagonal`: The `[n]` diagonal.
+/// * `upper`: The `[n]` super diagonal.
+/// * `rhs`: The `[n]` right hand side.
+///
+/// * `x`: The solution, or `None` when a pivot vanishes.
+pub fn solve_tridiagonal<F: ag::Float>(lower: &[F], diagonal: &[F], upper: &[F], rhs: &[F]) -> Option<Vec<F>> {
+    let n = diagonal.len();
+    let mut c = vec![F::zero(); n];
+    let mut d = vec![F::zero(); n];
+    for i in 0..n {
+        let (a, previous_c, previous_d) = if i == 0 {
+            (F::zero(), F::zero(), F::zero())
+        } else {
+            (lower[i], c[i - 1], d[i - 1])
+        };
+        let pivot = diagonal[i] - a * previous_c;
+        if pivot.abs() <= F::epsilon() {
+            return None;
+        }
+        c[i] = if i + 1 < n { upper[i] / pivot } else { F::zero() };
+        d[i] = (rhs[i] - a * previous_d) / pivot;
+    }
+
+    let mut x = d;
+    for i in (0..n.saturating_sub(1)).rev() {
+        let next = x[i + 1];
+        x[i] = x[i] - c[i] * next;
+    }
+    Some(x)
+}
+
+/// Fit the ordinary least squares coefficients `beta` minimizing `|x beta - y|²`
+/// by solving the normal equations `xᵀx beta = xᵀy`.
+///
+/// * `x`: The `[n_samples, n_features]` design matrix.
+/// * `y`: The `[n_samples]` observations.
+///
+/// * `beta`: The `[n_features]` coefficients, or `None` when `xᵀx` is singular.
+pub fn least_squares<F: ag::Float>(x: &nd::Array2<F>, y: &nd::Array1<F>) -> Option<nd::Array1<F>> {
+    let xt = x.t();
+    solve(&xt.dot(x), &xt.dot(y))
+}
+
+/// Factor a symmetric positive definite matrix as `a = l lᵀ` with `l` lower
+/// triangular.
+///
+/// * `a`: The `[n, n]` symmetric matrix. Only its lower triangle is read.
+///
+/// * `l`: The lower triangular cholesky factor, `QuantError::NotPositiveDefinite`
+///   when `a` is not positive definite, or `QuantError::ShapeMismatch` when `a`
+///   is not square.
+pub fn cholesky<F: ag::Float>(a: &nd::Array2<F>) -> Result<nd::Array2<F>, QuantError> {
+    let n = a.nrows();
+    QuantError::check_shape(&[n, n], a.shape())?;
+
+    let mut l = nd::Array2::<F>::zeros((n, n));
+    for i in 0..n {
+        for j in 0..i + 1 {
+            let dot = (0..j).fold(F::zero(), |acc, m| acc + l[[i, m]] * l[[j, m]]);
+            if i == j {
+                let pivot = a[[i, i]] - dot;
+                if !(pivot > F::zero()) {
+                    return Err(QuantError::NotPositiveDefinite);
+                }
+                l[[i, j]] = pivot.sqrt();
+            } else {
+                l[[i, j]] = (a[[i, j]] - dot) / l[[j, j]];
+            }
+        }
+    }
+    Ok(l)
+}
@@ -0,0 +1,6 @@
+pub mod integrate;
+pub mod interp;
+pub mod linalg;
+pub(crate) mod optimize;
+pub mod pde;
+pub mod root;
@@ -0,0 +1,55 @@
+use autograd as ag;
+
+/// Apply an optimizer's `update` op for `epochs` epochs.
+///
+/// The caller builds the graph, its placeholders and the update op once inside
+/// `VariableEnvironment::run`; each epoch here only feeds the placeholders and evaluates
+/// the op, so a fit pays for one graph rather than one per epoch.
+///
+/// * `ctx`: The context the graph was built in.
+/// * `update`: The update op from the optimizer's `get_update_op`.
+/// * `feeds`: Each placeholder with the array it is fed every epoch.
+/// * `epochs`: The number of updates to apply.
+pub(crate) fn run_epochs<'graph, F: ag::Float>(
+    ctx: &'graph ag::Context<F>,
+    update: &ag::Tensor<'graph, F>,
+    feeds: &[(ag::Tensor<'graph, F>, ag::NdArrayView<F>)],
+    epochs: usize,
+) {
+    for _ in 0..epochs {
+        ctx.evaluator().push(update).set_feeder(feeder(feeds)).run();
+    }
+}
+
+/// Apply an optimizer's `update` op like `run_epochs` until `stop` accepts a loss.
+///
+/// The scalar `loss` is evaluated with each update, from the variables before that
+/// epoch's update, and handed to `stop`.
+///
+/// * `epochs`: The number of epochs that ran, at most `max_epochs`.
+pub(crate) fn run_epochs_until<'graph, F: ag::Float, S: FnMut(F) -> bool>(
+    ctx: &'graph ag::Context<F>,
+    update: &ag::Tensor<'graph, F>,
+    loss: &ag::Tensor<'graph, F>,
+    feeds: &[(ag::Tensor<'graph, F>, ag::NdArrayView<F>)],
+    max_epochs: usize,
+    mut stop: S,
+) -> usize {
+    for epoch in 0..max_epochs {
+        let results = ctx.evaluator().push(update).push(loss).set_feeder(feeder(feeds)).run();
+        if stop(results[1].as_ref().unwrap()[0]) {
+            return epoch + 1;
+        }
+    }
+    max_epochs
+}
+
+fn feeder<'view, 'graph, F: ag::Float>(
+    feeds: &[(ag::Tensor<'graph, F>, ag::NdArrayView<'view, F>)],
+) -> ag::Feeder<'view, F> {
+    let mut feeder = ag::Feeder::new();
+    for (placeholder, array) in feeds {
+        feeder.push(*placeholder, array.clone());
+    }
+    feeder
+}
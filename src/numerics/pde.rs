@@ -0,0 +1,161 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::numerics::interp::CubicSpline;
+use crate::numerics::linalg::solve_tridiagonal;
+use crate::options::model::Exercise;
+
+/// The relaxation factor of the projected SOR iterations.
+const RELAXATION: f64 = 1.2;
+
+/// The most projected SOR iterations of one time step.
+const MAX_SOR_ITERATIONS: usize = 10_000;
+
+/// Price an option by solving the Black-Scholes PDE
+///
+/// ∂V/∂τ = ½σ²S² ∂²V/∂S² + (r - q)S ∂V/∂S - rV
+///
+/// in the time to maturity τ with the Crank-Nicolson scheme, on a uniform grid of
+/// `n_spot + 1` spots from 0 to `s_max` and `n_time` time steps.
+///
+/// The solution starts from the payoff at maturity and is rolled back one step at a
+/// time, with the values on the edges of the grid given by the boundary conditions.
+/// European options solve each step's tridiagonal system directly. American options
+/// also need `V ≥ payoff` at every node, which projected successive over-relaxation
+/// enforces within each step, so the boundary conditions should include early
+/// exercise, e.g. the strike at `S = 0` for a put. The price at `s` is read off a
+/// natural cubic spline through the final grid.
+///
+/// The scheme is second order in both the spot and time steps, but payoffs with a
+/// kink at the strike converge less smoothly, so the grid should place ample nodes
+/// around it and `s_max` several standard deviations above the spot.
+///
+/// * `payoff`: The payoff `h(S)` at maturity, also the exercise value of american options.
+/// * `lower`: The option's value at `S = 0` as a function of the time to maturity.
+/// * `upper`: The option's value at `S = s_max` as a function of the time to maturity.
+/// * `exercise`: The option's exercise style.
+/// * `s`: The underlying stock's price per share, within the grid.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `s_max`: The top of the spot grid.
+/// * `n_spot`: The number of spot steps, at least 2.
+/// * `n_time`: The number of time steps, at least 1.
+///
+/// * `price`: The price of the option, `QuantError::InvalidInput` when the grid is too
+///   small, `s` lies outside it or the volatility or maturity is not positive, or
+///   `QuantError::NoConvergence` when a projected SOR step does not converge.
+pub fn price_crank_nicolson<F: ag::Float, P, L, U>(
+    payoff: P,
+    lower: L,
+    upper: U,
+    exercise: Exercise,
+    s: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    s_max: F,
+    n_spot: usize,
+    n_time: usize,
+) -> Result<F, QuantError>
+where
+    P: Fn(F) -> F,
+    L: Fn(F) -> F,
+    U: Fn(F) -> F,
+{
+    if n_spot < 2 || n_time < 1 {
+        return Err(QuantError::InvalidInput("need at least 2 spot and 1 time steps".to_string()));
+    }
+    if !(vol > F::zero()) || !(t > F::zero()) {
+        return Err(QuantError::InvalidInput("volatility and maturity must be positive".to_string()));
+    }
+    if !(s >= F::zero() && s <= s_max) {
+        return Err(QuantError::InvalidInput("spot must lie on the grid".to_string()));
+    }
+
+    let m = n_spot;
+    let ds = s_max / F::from(m).unwrap();
+    let dt = t / F::from(n_time).unwrap();
+    let quarter = F::from(0.25f64).unwrap();
+    let half = F::from(0.5f64).unwrap();
+    let spots = (0..m + 1).map(|i| ds * F::from(i).unwrap()).collect::<Vec<_>>();
+    let exercise_values = spots.iter().map(|&spot| payoff(spot)).collect::<Vec<_>>();
+
+    // The weights of V[i - 1], V[i] and V[i + 1] in half a step of the operator.
+    let variance = vol * vol;
+    let index = |i: usize| F::from(i).unwrap();
+    let alpha = (0..m + 1)
+        .map(|i| quarter * dt * (variance * index(i) * index(i) - (r - q) * index(i)))
+        .collect::<Vec<_>>();
+    let beta = (0..m + 1)
+        .map(|i| -half * dt * (variance * index(i) * index(i) + r))
+        .collect::<Vec<_>>();
+    let gamma = (0..m + 1)
+        .map(|i| quarter * dt * (variance * index(i) * index(i) + (r - q) * index(i)))
+        .collect::<Vec<_>>();
+
+    let sub = (1..m).map(|i| -alpha[i]).collect::<Vec<_>>();
+    let diagonal = (1..m).map(|i| F::one() - beta[i]).collect::<Vec<_>>();
+    let sup = (1..m).map(|i| -gamma[i]).collect::<Vec<_>>();
+
+    let mut values = exercise_values.clone();
+    for step in 1..n_time + 1 {
+        let tau = dt * F::from(step).unwrap();
+        let (low, high) = (lower(tau), upper(tau));
+        let mut rhs = (1..m)
+            .map(|i| alpha[i] * values[i - 1] + (F::one() + beta[i]) * values[i] + gamma[i] * values[i + 1])
+            .collect::<Vec<_>>();
+
+        match exercise {
+            Exercise::European => {
+                // Move the new boundary values of the first and last interior rows to
+                // the right hand side.
+                rhs[0] = rhs[0] + alpha[1] * low;
+                rhs[m - 2] = rhs[m - 2] + gamma[m - 1] * high;
+                let interior = solve_tridiagonal(&sub, &diagonal, &sup, &rhs).ok_or(QuantError::NoConvergence)?;
+                values[1..m].copy_from_slice(&interior);
+            }
+            Exercise::American => {
+                projected_sor(&mut values, &exercise_values, &alpha, &beta, &gamma, &rhs, low, high)?;
+            }
+        }
+        values[0] = low;
+        values[m] = high;
+    }
+
+    Ok(CubicSpline::natural(spots, values).eval(s))
+}
+
+/// Solve one implicit step of an american option for the interior nodes, starting
+/// from the previous step's values and keeping every value above its exercise value.
+fn projected_sor<F: ag::Float>(
+    values: &mut [F],
+    exercise_values: &[F],
+    alpha: &[F],
+    beta: &[F],
+    gamma: &[F],
+    rhs: &[F],
+    low: F,
+    high: F,
+) -> Result<(), QuantError> {
+    let m = values.len() - 1;
+    let omega = F::from(RELAXATION).unwrap();
+    let tolerance = F::epsilon() * F::from(1e4f64).unwrap();
+    values[0] = low;
+    values[m] = high;
+    for _ in 0..MAX_SOR_ITERATIONS {
+        let mut change = F::zero();
+        for i in 1..m {
+            let target = (rhs[i - 1] + alpha[i] * values[i - 1] + gamma[i] * values[i + 1]) / (F::one() - beta[i]);
+            let relaxed = (values[i] + omega * (target - values[i])).max(exercise_values[i]);
+            change = change.max((relaxed - values[i]).abs());
+            values[i] = relaxed;
+        }
+        if change <= tolerance * values.iter().fold(F::one(), |acc, v| acc.max(v.abs())) {
+            return Ok(());
+        }
+    }
+    Err(QuantError::NoConvergence)
+}
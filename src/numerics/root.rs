@@ -0,0 +1,192 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+
+/// The largest number of iterations the bracketing solvers take.
+const MAX_ITERATIONS: usize = 200;
+
+/// Find a root of `f` with Newton's method from `x0`.
+///
+/// Newton converges quadratically near a simple root, but it can cycle or run away
+/// from a poor starting point; `brent` is the safe choice when a bracket is known.
+///
+/// * `f`: The function whose root is sought.
+/// * `df`: The derivative of `f`.
+/// * `x0`: The starting point.
+/// * `tol`: The largest accepted Newton step at the root.
+/// * `max_iter`: The maximum number of Newton steps.
+///
+/// * `root`: The root, or `QuantError::NoConvergence` when the iterates reach a zero
+///   or non-finite derivative or do not converge within `max_iter` steps.
+pub fn newton<F: ag::Float, G: Fn(F) -> F, D: Fn(F) -> F>(
+    f: G,
+    df: D,
+    x0: F,
+    tol: F,
+    max_iter: usize,
+) -> Result<F, QuantError> {
+    let mut x = x0;
+    for _ in 0..max_iter {
+        let fx = f(x);
+        if fx == F::zero() {
+            return Ok(x);
+        }
+        let slope = df(x);
+        if slope == F::zero() || !slope.is_finite() {
+            return Err(QuantError::NoConvergence);
+        }
+        let step = fx / slope;
+        x = x - step;
+        if !x.is_finite() {
+            return Err(QuantError::NoConvergence);
+        }
+        if step.abs() <= tol {
+            return Ok(x);
+        }
+    }
+    Err(QuantError::NoConvergence)
+}
+
+/// Find a root of `f` on [lo, hi] by bisection.
+///
+/// Each step halves the bracket, so it never diverges but only gains one bit of
+/// accuracy per evaluation.
+///
+/// * `f`: The function whose root is sought.
+/// * `lo`: The lower end of the bracket.
+/// * `hi`: The upper end of the bracket.
+/// * `tol`: The largest accepted width of the final bracket.
+///
+/// * `root`: The midpoint of the final bracket, or `QuantError::NoBracket` when `f`
+///   has the same sign at both ends.
+pub fn bisect<F: ag::Float, G: Fn(F) -> F>(f: G, lo: F, hi: F, tol: F) -> Result<F, QuantError> {
+    let two = F::from(2f64).unwrap();
+    let (mut lo, mut hi) = (lo, hi);
+    let f_lo = f(lo);
+    let f_hi = f(hi);
+    if f_lo == F::zero() {
+        return Ok(lo);
+    }
+    if f_hi == F::zero() {
+        return Ok(hi);
+    }
+    if (f_lo > F::zero()) == (f_hi > F::zero()) {
+        return Err(QuantError::NoBracket);
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        if (hi - lo).abs() <= tol {
+            break;
+        }
+        let mid = (lo + hi) / two;
+        let f_mid = f(mid);
+        if f_mid == F::zero() {
+            return Ok(mid);
+        }
+        if (f_mid > F::zero()) == (f_lo > F::zero()) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok((lo + hi) / two)
+}
+
+/// Find a root of `f` on [lo, hi] with Brent's method.
+///
+/// Brent's method combines inverse quadratic interpolation and secant steps with a
+/// fallback to bisection whenever they would leave the bracket or converge too
+/// slowly, so it keeps the safety of bisection with superlinear convergence on
+/// smooth functions.
+///
+/// * `f`: The function whose root is sought.
+/// * `lo`: The lower end of the bracket.
+/// * `hi`: The upper end of the bracket.
+/// * `tol`: The largest accepted distance to the root.
+///
+/// * `root`: The root, `QuantError::NoBracket` when `f` has the same sign at both
+///   ends, or `QuantError::NoConvergence` when the iteration budget runs out.
+pub fn brent<F: ag::Float, G: Fn(F) -> F>(f: G, lo: F, hi: F, tol: F) -> Result<F, QuantError> {
+    let (one, two, three) = (F::one(), F::from(2f64).unwrap(), F::from(3f64).unwrap());
+    let half = one / two;
+    let (mut a, mut b) = (lo, hi);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == F::zero() {
+        return Ok(a);
+    }
+    if fb == F::zero() {
+        return Ok(b);
+    }
+    if (fa > F::zero()) == (fb > F::zero()) {
+        return Err(QuantError::NoBracket);
+    }
+
+    // `b` is the best estimate, `c` the other end of the bracket, and `d` and `e`
+    // the last two steps.
+    let (mut c, mut fc) = (b, fb);
+    let (mut d, mut e) = (b - a, b - a);
+    for _ in 0..MAX_ITERATIONS {
+        if (fb > F::zero()) == (fc > F::zero()) {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tol1 = two * F::epsilon() * b.abs() + half * tol;
+        let xm = half * (c - b);
+        if xm.abs() <= tol1 || fb == F::zero() {
+            return Ok(b);
+        }
+
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (two * xm * s, one - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (two * xm * q * (q - r) - (b - a) * (r - one)),
+                    (q - one) * (r - one) * (s - one),
+                )
+            };
+            if p > F::zero() {
+                q = -q;
+            }
+            p = p.abs();
+            let bound = (three * xm * q - (tol1 * q).abs()).min((e * q).abs());
+            if two * p < bound {
+                // Accept the interpolation.
+                e = d;
+                d = p / q;
+            } else {
+                d = xm;
+                e = d;
+            }
+        } else {
+            d = xm;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b = if d.abs() > tol1 {
+            b + d
+        } else if xm > F::zero() {
+            b + tol1
+        } else {
+            b - tol1
+        };
+        fb = f(b);
+    }
+    Err(QuantError::NoConvergence)
+}
@@ -0,0 +1,203 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use crate::error::QuantError;
+use crate::models::gbm::simulate_gbm_paths;
+use crate::options::black_scholes::BlackScholesPricingModel;
+use crate::options::model::*;
+use crate::options::monte_carlo::MonteCarloEstimate;
+
+use autograd::rand::Rng;
+
+/// Estimate the price of european arithmetic average price options by Monte
+/// Carlo simulation of geometric brownian motion paths.
+///
+/// The average is taken over the `n_steps` equally spaced monitoring dates
+/// `t / n_steps, 2t / n_steps, ..., t`, excluding the initial spot price, so a
+/// single step prices a vanilla european option.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The option's strike price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n_steps`: The number of averaging dates on each path.
+/// * `n_paths`: The number of simulated paths.
+/// * `antithetic`: Whether to simulate antithetic pairs of paths, in which case
+///   the standard error is computed over the pair averages.
+/// * `control_variate`: Whether to correct each payoff with the payoff of the geometric
+///   average option on the same path, whose price `price_discrete_geometric_asian` knows
+///   exactly, using the regression coefficient of the two payoffs over the paths.
+/// * `rng`: The source of randomness for the simulated paths.
+///
+/// * `estimate`: The discounted mean payoff and its standard error, both NaN when
+///   `n_paths` is zero, or `QuantError::InvalidInput` when `n_steps` is zero.
+pub fn price_arithmetic_asian<F: ag::Float, R: Rng>(
+    ty: OptionType,
+    s: F,
+    k: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    n_steps: usize,
+    n_paths: usize,
+    antithetic: bool,
+    control_variate: bool,
+    rng: &mut R,
+) -> Result<MonteCarloEstimate<F>, QuantError> {
+    if n_steps == 0 {
+        return Err(QuantError::InvalidInput("need at least one averaging date".to_string()));
+    }
+    let discount = (-r * t).exp();
+    let n = F::from(n_steps).unwrap();
+    let paths = simulate_gbm_paths(s, vol, q, r, t, n_steps, n_paths, antithetic, rng);
+
+    // The discounted payoffs of the arithmetic and the geometric average on each path.
+    let payoffs = paths
+        .outer_iter()
+        .map(|path| {
+            let (total, log_total) = path
+                .iter()
+                .skip(1)
+                .fold((F::zero(), F::zero()), |(total, log_total), &st| (total + st, log_total + st.ln()));
+            let arithmetic = discount * ty.payoff(total / n, k);
+            let geometric = discount * ty.payoff((log_total / n).exp(), k);
+            (arithmetic, geometric)
+        })
+        .collect::<Vec<_>>();
+
+    let samples = if antithetic {
+        payoffs
+            .chunks(2)
+            .map(|pair| {
+                let m = F::from(pair.len()).unwrap();
+                let (x, y) = pair.iter().fold((F::zero(), F::zero()), |(x, y), &(a, g)| (x + a, y + g));
+                (x / m, y / m)
+            })
+            .collect::<Vec<_>>()
+    } else {
+        payoffs
+    };
+
+    if control_variate {
+        let geometric = ag::run(|ctx: &mut ag::Context<F>| {
+            let tensor = |x: F| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+            let (s, k, vol, q) = (tensor(s), tensor(k), tensor(vol), tensor(q));
+            price_discrete_geometric_asian(ty, &s, &k, &vol, &q, r, t, n_steps).map(|p| p.eval(ctx).unwrap()[0])
+        })?;
+        Ok(MonteCarloEstimate::from_samples(&controlled(&samples, geometric)))
+    } else {
+        Ok(MonteCarloEstimate::from_samples(&samples.iter().map(|&(x, _)| x).collect::<Vec<_>>()))
+    }
+}
+
+/// Correct each sample `x` by `β (y - mean)`, where `mean` is the known expectation of
+/// the control `y` and `β = Cov(x, y) / Var(y)` is fitted to the samples.
+fn controlled<F: ag::Float>(samples: &[(F, F)], mean: F) -> Vec<F> {
+    let n = F::from(samples.len()).unwrap();
+    let (x_total, y_total) = samples.iter().fold((F::zero(), F::zero()), |(xs, ys), &(x, y)| (xs + x, ys + y));
+    let (x_bar, y_bar) = (x_total / n, y_total / n);
+    let (cov, var) = samples.iter().fold((F::zero(), F::zero()), |(cov, var), &(x, y)| {
+        (cov + (x - x_bar) * (y - y_bar), var + (y - y_bar).powi(2))
+    });
+    let beta = if var > F::zero() { cov / var } else { F::zero() };
+    samples.iter().map(|&(x, y)| x - beta * (y - mean)).collect()
+}
+
+/// Calculate the price of european continuously monitored geometric average price
+/// options with the Kemna-Vorst closed form.
+///
+/// The geometric average of a geometric brownian motion is lognormal with
+/// volatility `vol / sqrt(3)` and carry `(r - q - vol^2 / 6) / 2`, so this is a
+/// Black-Scholes price with an adjusted volatility and dividend yield. It is the
+/// limit of `price_discrete_geometric_asian` as the number of averaging dates grows.
+///
+/// This function can price multiple options at once by inputing
+/// a multidimensional set of inputs. All multi dimensional inputs
+/// must have the same shape.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the options.
+pub fn price_geometric_asian<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    s: A,
+    k: A,
+    vol: A,
+    q: A,
+    r: F,
+    t: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let vol = vol.as_ref();
+    let half = F::from(0.5_f64).unwrap();
+    let sixth = F::from(1. / 6.).unwrap();
+    let adjusted_vol = vol * F::from(1. / 3_f64.sqrt()).unwrap();
+    // q' = r - b', where b' = (r - q - vol^2 / 6) / 2 is the carry of the average.
+    let adjusted_q = ((q.as_ref() + r) + math::square(vol) * sixth) * half;
+    BlackScholesPricingModel::price(ty, s.as_ref(), k.as_ref(), &adjusted_vol, &adjusted_q, r, t)
+}
+
+/// Calculate the price of european geometric average price options monitored on the
+/// `n_steps` equally spaced dates `t / n_steps, 2t / n_steps, ..., t`.
+///
+/// The log of the discrete geometric average is normal with mean
+/// `ln S + (r - q - vol^2 / 2) a t` and variance `vol^2 b t`, where
+/// `a = (n + 1) / 2n` and `b = (n + 1)(2n + 1) / 6n^2`, so this is a Black-Scholes
+/// price with the volatility `vol sqrt(b)` and the dividend yield
+/// `r - (r - q - vol^2 / 2) a - vol^2 b / 2`. A single date prices a vanilla european
+/// option. The geometric average never exceeds the arithmetic one over the same dates,
+/// which makes this the control variate of `price_arithmetic_asian`.
+///
+/// This function can price multiple options at once by inputing
+/// a multidimensional set of inputs. All multi dimensional inputs
+/// must have the same shape.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n_steps`: The number of averaging dates.
+///
+/// * `prices`: The price of the options, or `QuantError::InvalidInput` when `n_steps`
+///   is zero.
+pub fn price_discrete_geometric_asian<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    s: A,
+    k: A,
+    vol: A,
+    q: A,
+    r: F,
+    t: F,
+    n_steps: usize,
+) -> Result<ag::Tensor<'graph, F>, QuantError>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    if n_steps == 0 {
+        return Err(QuantError::InvalidInput("need at least one averaging date".to_string()));
+    }
+    let vol = vol.as_ref();
+    let n = F::from(n_steps).unwrap();
+    let (one, two) = (F::one(), F::from(2_f64).unwrap());
+    let a = (n + one) / (two * n);
+    let b = (n + one) * (two * n + one) / (F::from(6_f64).unwrap() * n * n);
+    let adjusted_vol = vol * b.sqrt();
+    let adjusted_q = math::square(vol) * ((a - b) / two) + q.as_ref() * a + r * (one - a);
+    Ok(BlackScholesPricingModel::price(ty, s.as_ref(), k.as_ref(), &adjusted_vol, &adjusted_q, r, t))
+}
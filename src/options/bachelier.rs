@@ -0,0 +1,46 @@
+use autograd as ag;
+
+use crate::stats::normal;
+
+/// Price european calls on forwards under the Bachelier (normal) model, in which the
+/// forward follows an arithmetic brownian motion and may become negative
+///
+/// C = e^{-rT} ((F - K) N(d) + σ√T n(d)), d = (F - K) / (σ√T).
+///
+/// * `f`: The forward prices of the underlying, which may be negative.
+/// * `k`: The options' strike prices, which may be negative.
+/// * `vol`: The normal volatility of the forward, in price units per square root year.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the calls.
+pub fn price_call_bachelier<'graph, A, F: ag::Float>(f: A, k: A, vol: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let std = vol.as_ref() * t.sqrt();
+    let moneyness = f.as_ref() - k.as_ref();
+    let d = moneyness / std;
+    (moneyness * normal::cdf(&d, F::zero(), F::one()) + std * normal::pdf(&d, F::zero(), F::one())) * (-r * t).exp()
+}
+
+/// Price european puts on forwards under the Bachelier (normal) model
+///
+/// P = e^{-rT} ((K - F) N(-d) + σ√T n(d)), d = (F - K) / (σ√T).
+///
+/// * `f`: The forward prices of the underlying, which may be negative.
+/// * `k`: The options' strike prices, which may be negative.
+/// * `vol`: The normal volatility of the forward, in price units per square root year.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the puts.
+pub fn price_put_bachelier<'graph, A, F: ag::Float>(f: A, k: A, vol: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let std = vol.as_ref() * t.sqrt();
+    let moneyness = k.as_ref() - f.as_ref();
+    let d = moneyness / std;
+    (moneyness * normal::cdf(&d, F::zero(), F::one()) + std * normal::pdf(&d, F::zero(), F::one())) * (-r * t).exp()
+}
@@ -0,0 +1,113 @@
+use autograd as ag;
+use autograd::tensor_ops as math;
+
+use crate::options::model::*;
+
+/// The direction of the barrier relative to the spot price and whether touching
+/// it activates (knock-in) or extinguishes (knock-out) the option.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BarrierType {
+    DownAndIn,
+    DownAndOut,
+    UpAndIn,
+    UpAndOut,
+}
+
+/// Calculate the price of european single barrier options with continuous
+/// monitoring and no rebate using the Reiner-Rubinstein reflection formulas.
+///
+/// The barrier is assumed not to have been touched yet, e.g. `s > h` for down
+/// barriers and `s < h` for up barriers. A knock-in and the matching knock-out
+/// always sum to the vanilla price.
+///
+/// This function can price multiple options at once by inputing
+/// a multidimensional set of inputs. All multi dimensional inputs
+/// must have the same shape.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `barrier`: The `BarrierType` of the options.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `h`: The options' barrier levels per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the options.
+pub fn price_barrier<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    barrier: BarrierType,
+    s: A,
+    k: A,
+    h: A,
+    vol: A,
+    q: A,
+    r: F,
+    t: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let s = s.as_ref();
+    let k = k.as_ref();
+    let h = h.as_ref();
+    let vol = vol.as_ref();
+    let q = q.as_ref();
+
+    let one = F::one();
+    let zero = F::zero();
+    let two = F::from(2_f64).unwrap();
+    let half = F::from(0.5_f64).unwrap();
+    let phi = match ty {
+        OptionType::Call => one,
+        OptionType::Put => -one,
+    };
+    let eta = match barrier {
+        BarrierType::DownAndIn | BarrierType::DownAndOut => one,
+        BarrierType::UpAndIn | BarrierType::UpAndOut => -one,
+    };
+
+    let vol_sqrt_t = vol * t.sqrt();
+    // mu = (r - q - vol^2 / 2) / vol^2
+    let mu = ((math::neg(q) + r) - math::square(vol) * half) / math::square(vol);
+    let drift = (mu + one) * vol_sqrt_t;
+    let x1 = math::ln(s / k) / vol_sqrt_t + drift;
+    let x2 = math::ln(s / h) / vol_sqrt_t + drift;
+    let y1 = math::ln((h * h) / (s * k)) / vol_sqrt_t + drift;
+    let y2 = math::ln(h / s) / vol_sqrt_t + drift;
+
+    let forward = s * math::exp(math::neg(q * t));
+    let discounted = k * (-r * t).exp();
+    let reflect_forward = math::exp(math::ln(h / s) * ((mu + one) * two));
+    let reflect_strike = math::exp(math::ln(h / s) * (mu * two));
+    let cdf = |x: ag::Tensor<'graph, F>| math::normal_cdf(&x, zero, one);
+
+    let a = (forward * cdf(x1 * phi) - discounted * cdf((x1 - vol_sqrt_t) * phi)) * phi;
+    let b = (forward * cdf(x2 * phi) - discounted * cdf((x2 - vol_sqrt_t) * phi)) * phi;
+    let c = (forward * reflect_forward * cdf(y1 * eta)
+        - discounted * reflect_strike * cdf((y1 - vol_sqrt_t) * eta))
+        * phi;
+    let d = (forward * reflect_forward * cdf(y2 * eta)
+        - discounted * reflect_strike * cdf((y2 - vol_sqrt_t) * eta))
+        * phi;
+
+    // 1 where the strike is above the barrier, 0 otherwise.
+    let above = math::greater(k, h);
+    let below = math::neg(above) + one;
+    let select = |strike_above: ag::Tensor<'graph, F>, strike_below: ag::Tensor<'graph, F>| {
+        above * strike_above + below * strike_below
+    };
+
+    match (ty, barrier) {
+        (OptionType::Call, BarrierType::DownAndIn) => select(c, a - b + d),
+        (OptionType::Call, BarrierType::UpAndIn) => select(a, b - c + d),
+        (OptionType::Call, BarrierType::DownAndOut) => select(a - c, b - d),
+        (OptionType::Call, BarrierType::UpAndOut) => below * (a - b + c - d),
+        (OptionType::Put, BarrierType::DownAndIn) => select(b - c + d, a),
+        (OptionType::Put, BarrierType::UpAndIn) => select(a - b + d, c),
+        (OptionType::Put, BarrierType::DownAndOut) => above * (a - b + c - d),
+        (OptionType::Put, BarrierType::UpAndOut) => select(b - d, a - c),
+    }
+}
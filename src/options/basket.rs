@@ -0,0 +1,67 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::options::monte_carlo::MonteCarloEstimate;
+use crate::stats::normal::sample_correlated_normals;
+
+use autograd::rand::Rng;
+
+/// Estimate the price of a european call on a weighted basket of stocks by Monte
+/// Carlo simulation of correlated geometric brownian motions
+///
+/// C = e^{-rT} E[max(Σ wᵢ Sᵢ(T) - K, 0)].
+///
+/// The terminal prices are sampled exactly, with the stocks' shocks correlated by
+/// the cholesky factor of `correlation`.
+///
+/// * `s`: The stocks' prices per share.
+/// * `weights`: The number of shares of each stock in the basket.
+/// * `vol`: The volatility of each stock in decimal.
+/// * `correlation`: The `[n_stocks, n_stocks]` correlation matrix of the stocks' returns.
+/// * `k`: The option's strike price on the basket.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n_paths`: The number of simulated paths.
+/// * `rng`: The source of randomness for the simulated paths.
+///
+/// * `estimate`: The discounted mean payoff and its standard error,
+///   `QuantError::ShapeMismatch` when the inputs' shapes differ,
+///   `QuantError::NotPositiveDefinite` when `correlation` is not positive definite, or
+///   `QuantError::InvalidInput` when `n_paths` is zero.
+pub fn price_basket_call<F: ag::Float, R: Rng>(
+    s: ag::NdArrayView<F>,
+    weights: ag::NdArrayView<F>,
+    vol: ag::NdArrayView<F>,
+    correlation: &ag::NdArray<F>,
+    k: F,
+    r: F,
+    t: F,
+    n_paths: usize,
+    rng: &mut R,
+) -> Result<MonteCarloEstimate<F>, QuantError> {
+    let n = s.len();
+    QuantError::check_shape(&[n], s.shape())?;
+    QuantError::check_shape(s.shape(), weights.shape())?;
+    QuantError::check_shape(s.shape(), vol.shape())?;
+    QuantError::check_shape(&[n, n], correlation.shape())?;
+    if n_paths == 0 {
+        return Err(QuantError::InvalidInput("need at least one path".to_string()));
+    }
+
+    let two = F::from(2f64).unwrap();
+    let discount = (-r * t).exp();
+    let sqrt_t = t.sqrt();
+    let drifts = vol.iter().map(|&v| (r - v * v / two) * t).collect::<Vec<_>>();
+
+    let z = sample_correlated_normals(correlation, n_paths, rng)?;
+    let samples = z
+        .outer_iter()
+        .map(|shocks| {
+            let basket = (0..n).fold(F::zero(), |acc, i| {
+                acc + weights[i] * s[i] * (drifts[i] + vol[i] * sqrt_t * shocks[i]).exp()
+            });
+            discount * (basket - k).max(F::zero())
+        })
+        .collect::<Vec<_>>();
+    Ok(MonteCarloEstimate::from_samples(&samples))
+}
@@ -0,0 +1,79 @@
+use autograd as ag;
+use autograd::tensor_ops as math;
+
+use crate::options::black_scholes::d1_d2;
+use crate::options::model::*;
+
+/// Calculate the price of european cash-or-nothing options, which pay a fixed
+/// `cash` amount at maturity if they finish in the money and nothing otherwise.
+///
+/// This function can price multiple options at once by inputing
+/// a multidimensional set of inputs. All multi dimensional inputs
+/// must have the same shape.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `cash`: The amount paid when the option finishes in the money.
+///
+/// * `prices`: `cash * e^(-rt) * N(d2)` for calls and `cash * e^(-rt) * N(-d2)` for puts.
+pub fn price_cash_or_nothing<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    s: A,
+    k: A,
+    vol: A,
+    q: A,
+    r: F,
+    t: F,
+    cash: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let (_, d2) = d1_d2(s, k, vol, q, r, t);
+    let d2 = match ty {
+        OptionType::Call => d2,
+        OptionType::Put => math::neg(d2),
+    };
+    math::normal_cdf(&d2, F::zero(), F::one()) * (cash * (-r * t).exp())
+}
+
+/// Calculate the price of european asset-or-nothing options, which deliver one
+/// share of the underlying at maturity if they finish in the money and nothing otherwise.
+///
+/// This function can price multiple options at once by inputing
+/// a multidimensional set of inputs. All multi dimensional inputs
+/// must have the same shape.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: `s * e^(-qt) * N(d1)` for calls and `s * e^(-qt) * N(-d1)` for puts.
+pub fn price_asset_or_nothing<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    s: A,
+    k: A,
+    vol: A,
+    q: A,
+    r: F,
+    t: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let (d1, _) = d1_d2(s, k, vol, q, r, t);
+    let d1 = match ty {
+        OptionType::Call => d1,
+        OptionType::Put => math::neg(d1),
+    };
+    (s.as_ref() * math::exp(math::neg(q.as_ref() * t))) * math::normal_cdf(&d1, F::zero(), F::one())
+}
@@ -3,6 +3,8 @@ use autograd::array_gen as gen;
 use autograd::ndarray as nd;
 use autograd::tensor_ops as math;
 
+use crate::error::QuantError;
+use crate::numerics::optimize::run_epochs;
 use crate::options::model::*;
 use autograd::prelude::*;
 
@@ -26,8 +28,8 @@ impl OptionPricingModel for BinomialPricingModel {
         let k = k.as_ref().expand_dims(&[dim]);
         let vol = vol.as_ref().expand_dims(&[dim]);
         let q = q.as_ref().expand_dims(&[dim]);
-        let rs = (s / s) * r;
-        let ts = (s / s) * t;
+        let rs = s * F::zero() + r;
+        let ts = s * F::zero() + t;
 
         let packed = math::concat(&[s, k, vol, q, rs, ts], 0);
         match ty {
@@ -64,7 +66,10 @@ impl OptionPricingModel for BinomialPricingModel {
         q: ag::NdArrayView<F>,
         r: F,
         t: F,
-    ) -> ag::NdArray<F> {
+    ) -> Result<ag::NdArray<F>, QuantError> {
+        QuantError::check_shape(p.shape(), s.shape())?;
+        QuantError::check_shape(p.shape(), k.shape())?;
+        QuantError::check_shape(p.shape(), q.shape())?;
         let mut env = ag::VariableEnvironment::new();
         let ret_id = env.name("vol").set(gen::ones(p.shape()));
         let adam = ag::optimizers::adam::Adam::default(
@@ -72,148 +77,143 @@ impl OptionPricingModel for BinomialPricingModel {
             env.default_namespace().current_var_ids(),
             &mut env,
         );
-        for _ in 0..1000 {
-            env.run(|ctx| {
-                let vol = ctx.variable("vol");
-                let price = ctx.placeholder("p", &[-1]);
-                let spot = ctx.placeholder("s", &[-1]);
-                let strike = ctx.placeholder("k", &[-1]);
-                let dividends = ctx.placeholder("q", &[-1]);
-
-                let h = F::from(0.05_f64).unwrap();
-
-                let m: i32 = 1;
-                let n: i32 = 2;
-
-                let losses = (-n / 2..n / 2 + 1)
-                    .map(|i| {
-                        let voli = vol + (h * F::from(i).unwrap());
-                        let pred = BinomialPricingModel::price(
-                            ty, &spot, &strike, &voli, &dividends, r, t,
-                        );
-                        math::abs(price - pred)
-                    })
-                    .collect::<Vec<_>>();
-                let grad = math::finite_difference(m as usize, n as usize, h, &losses[..]);
-
-                let mut feeder = ag::Feeder::new();
-                feeder
-                    .push(price, p.view())
-                    .push(spot, s.view())
-                    .push(strike, k.view())
-                    .push(dividends, q.view());
-
-                adam.update(&[vol], &[grad], ctx, feeder);
-            });
-        }
-        env.get_array_by_id(ret_id).unwrap().clone().into_inner()
+        env.run(|ctx| {
+            let vol = ctx.variable("vol");
+            let price = ctx.placeholder("p", &[-1]);
+            let spot = ctx.placeholder("s", &[-1]);
+            let strike = ctx.placeholder("k", &[-1]);
+            let dividends = ctx.placeholder("q", &[-1]);
+
+            let h = F::from(0.05_f64).unwrap();
+
+            let m: i32 = 1;
+            let n: i32 = 2;
+
+            let losses = (-n / 2..n / 2 + 1)
+                .map(|i| {
+                    let voli = vol + (h * F::from(i).unwrap());
+                    let pred = BinomialPricingModel::price(
+                        ty, &spot, &strike, &voli, &dividends, r, t,
+                    );
+                    math::abs(price - pred)
+                })
+                .collect::<Vec<_>>();
+            let grad = math::finite_difference(m as usize, n as usize, h, &losses[..]);
+            let update = adam.get_update_op(&[vol], &[grad], ctx);
+
+            let feeds = [(price, p.view()), (spot, s.view()), (strike, k.view()), (dividends, q.view())];
+            run_epochs(ctx, &update, &feeds, 1000);
+        });
+        Ok(env.get_array_by_id(ret_id).unwrap().clone().into_inner())
     }
 
-    fn delta<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F> 
+    fn delta<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
     where
-        A: AsRef<ag::Tensor<'graph, F>> + Copy 
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
     {
-        let stock_price = s.as_ref();
-
-        let m: i32 = 1;
-        let n: i32 = 2;
-        // Hacky way to get a scalar tensor.
         let h = F::from(0.05_f64).unwrap();
-
-        let stencil_points = (-n / 2..n / 2 + 1)
-            .map(|i| {
-                let stock_price_i = stock_price + (h * F::from(i).unwrap());
-                let pred = BinomialPricingModel::price(
-                    ty, stock_price_i.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r, t,
-                );
-                pred
-            })
-            .collect::<Vec<_>>();
-        let grad = math::finite_difference(m as usize, n as usize, h, &stencil_points[..]);
-        grad
+        central_difference(h, |bump| {
+            BinomialPricingModel::price(ty, &(s.as_ref() + bump), k.as_ref(), vol.as_ref(), q.as_ref(), r, t)
+        })
     }
 
     fn theta<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
     where
-        A: AsRef<ag::Tensor<'graph, F>> + Copy
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
     {
-        let m: i32 = 1;
-        let n: i32 = 2;
-        // Hacky way to get a scalar tensor.
-        let h =  F::from(0.05_f64).unwrap();
-
-        let stencil_points = (-n / 2..n / 2 + 1)
-            .map(|i| {
-                let ti = t - (F::from(i).unwrap() * F::from(0.05_f64).unwrap());
-                let pred = BinomialPricingModel::price(
-                    ty, s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r, ti,
-                );
-                pred
-            })
-            .collect::<Vec<_>>();
-        let grad = math::finite_difference(m as usize, n as usize, h, &stencil_points[..]);
-        grad
+        let h = F::from(0.05_f64).unwrap();
+        central_difference(h, |bump| {
+            BinomialPricingModel::price(ty, s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r, t - bump)
+        })
     }
 
     fn gamma<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
     where
-        A: AsRef<ag::Tensor<'graph, F>> + Copy
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
     {
-        let m: i32 = 1;
-        let n: i32 = 2;
-        // Hacky way to get a scalar tensor.
-        let h = F::from(0.05_f64).unwrap();
-
-        let stencil_points = (-n / 2..n / 2 + 1)
-            .map(|i| {
-                let ti = t - (F::from(i).unwrap() * F::from(0.05_f64).unwrap());
-                let pred = BinomialPricingModel::delta(
-                    ty, s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r, ti,
-                );
-                pred
-            })
-            .collect::<Vec<_>>();
-        let grad = math::finite_difference(m as usize, n as usize, h, &stencil_points[..]);
-        grad
+        // Five percent of the spot spans several lattice nodes, whose kinks would
+        // otherwise dominate a narrower difference.
+        let h = *s.as_ref() * F::from(0.05_f64).unwrap();
+        second_difference(&h, |bump| {
+            BinomialPricingModel::price(ty, &(s.as_ref() + bump), k.as_ref(), vol.as_ref(), q.as_ref(), r, t)
+        })
     }
 
     fn vega<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
     where
-        A: AsRef<ag::Tensor<'graph, F>> + Copy
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
     {
-        let m: i32 = 1;
-        let n: i32 = 2;
-        // Hacky way to get a scalar tensor.
-        let h =  F::from(0.05_f64).unwrap();
-
-        let stencil_points = (-n / 2..n / 2 + 1)
-            .map(|i| {
-                let vol_i = vol.as_ref() + (h * F::from(i).unwrap());
-                let pred = BinomialPricingModel::price(
-                    ty, s.as_ref(), k.as_ref(), vol_i.as_ref(), q.as_ref(), r, t,
-                );
-                pred
-            })
-            .collect::<Vec<_>>();
-        let grad = math::finite_difference(m as usize, n as usize, h, &stencil_points[..]);
-        grad
+        let h = F::from(0.05_f64).unwrap();
+        central_difference(h, |bump| {
+            BinomialPricingModel::price(ty, s.as_ref(), k.as_ref(), &(vol.as_ref() + bump), q.as_ref(), r, t)
+        })
+    }
+
+    fn rho<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+    where
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
+    {
+        // One basis point, small enough for the curvature in the rate not to matter.
+        let h = F::from(1e-4_f64).unwrap();
+        central_difference(h, |bump| {
+            BinomialPricingModel::price(ty, s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r + bump, t)
+        })
     }
 }
 
+/// Calculate the price of an american call option on a Cox-Ross-Rubinstein
+/// binomial lattice, checking for early exercise at every node.
+///
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The option's strike price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `steps`: The number of time steps in the lattice.
+///
+/// * `price`: The price of the option.
+pub fn price_american_call<F: ag::Float>(s: F, k: F, vol: F, q: F, r: F, t: F, steps: usize) -> F {
+    eval_one(s, k, vol, q, r, t, steps, OptionType::Call)
+}
+
+/// Calculate the price of an american put option on a Cox-Ross-Rubinstein
+/// binomial lattice, checking for early exercise at every node.
+///
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The option's strike price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `steps`: The number of time steps in the lattice.
+///
+/// * `price`: The price of the option.
+pub fn price_american_put<F: ag::Float>(s: F, k: F, vol: F, q: F, r: F, t: F, steps: usize) -> F {
+    eval_one(s, k, vol, q, r, t, steps, OptionType::Put)
+}
+
 fn eval_one_call<F: ag::Float>(s: F, k: F, vol: F, q: F, r: F, t: F) -> F {
-    eval_one(s, k, vol, q, r, t, OptionType::Call)
+    eval_one(s, k, vol, q, r, t, daily_steps(t), OptionType::Call)
 }
 
 fn eval_one_put<F: ag::Float>(s: F, k: F, vol: F, q: F, r: F, t: F) -> F {
-    eval_one(s, k, vol, q, r, t, OptionType::Put)
+    eval_one(s, k, vol, q, r, t, daily_steps(t), OptionType::Put)
+}
+
+/// One lattice step per day until maturity.
+fn daily_steps<F: ag::Float>(t: F) -> usize {
+    let steps: usize = ag::num::NumCast::from(t * F::from(365f64).unwrap()).unwrap();
+    steps.max(1)
 }
 
-fn eval_one<F: ag::Float>(s: F, k: F, vol: F, q: F, r: F, t: F, ty: OptionType) -> F {
-    let dt: F = F::one() / F::from(365f64).unwrap();
+fn eval_one<F: ag::Float>(s: F, k: F, vol: F, q: F, r: F, t: F, steps: usize, ty: OptionType) -> F {
+    let n = steps.max(1);
+    let dt: F = t / F::from(n).unwrap();
     let u: F = (vol * dt.sqrt()).exp();
     let d: F = (-vol * dt.sqrt()).exp();
     let p: F = (((r - q) * dt).exp() - d) / (u - d);
-    let n: usize = ag::num::NumCast::from(t / dt).unwrap();
+    let decay = (-r * dt).exp();
 
     let mut dp: ag::NdArray<F> = gen::zeros(&[n + 1, n + 1]);
 
@@ -221,23 +221,15 @@ fn eval_one<F: ag::Float>(s: F, k: F, vol: F, q: F, r: F, t: F, ty: OptionType)
         let us = j as i32;
         let steps = n as i32;
         let stock_price = s * u.powi(2 * us - steps);
-        let exercise_profit = match ty {
-            OptionType::Call => (stock_price - k).max(F::zero()),
-            OptionType::Put => (k - stock_price).max(F::zero()),
-        };
-        dp[[n, j]] = exercise_profit;
+        dp[[n, j]] = ty.payoff(stock_price, k);
     }
 
     for i in (0..n).rev() {
-        for j in (0..n).rev() {
+        for j in 0..i + 1 {
             let us = j as i32;
             let steps = i as i32;
             let stock_price = s * u.powi(2 * us - steps);
-            let exercise_profit = match ty {
-                OptionType::Call => (stock_price - k).max(F::zero()),
-                OptionType::Put => (k - stock_price).max(F::zero()),
-            };
-            let decay = (-r * dt).exp();
+            let exercise_profit = ty.payoff(stock_price, k);
             let expected = p * dp[[i + 1, j + 1]] + (F::one() - p) * dp[[i + 1, j]];
             let binom = decay * expected;
             dp[[i, j]] = binom.max(exercise_profit);
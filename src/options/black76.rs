@@ -0,0 +1,68 @@
+use autograd as ag;
+use autograd::tensor_ops as math;
+
+use crate::options::black_scholes::d1_d2;
+
+/// Price european calls on futures or forwards with the Black (1976) model
+///
+/// C = e^{-rT} (F N(d1) - K N(d2)), d1 = (ln(F/K) + σ²T/2) / (σ√T), d2 = d1 - σ√T.
+///
+/// The forward replaces the spot and the whole payoff is discounted at the risk free
+/// rate, which is the Black-Scholes-Merton price with a dividend yield equal to `r`.
+///
+/// * `f`: The forward prices of the underlying.
+/// * `k`: The options' strike prices.
+/// * `vol`: The volatility of the forwards in decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the calls.
+pub fn price_call_black76<'graph, A, F: ag::Float>(f: A, k: A, vol: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let one = F::one();
+    let zero = F::zero();
+    let (d1, d2) = black76_d1_d2(f, k, vol, r, t);
+    let nd1 = math::normal_cdf(&d1, zero, one);
+    let nd2 = math::normal_cdf(&d2, zero, one);
+    ((f.as_ref() * nd1) - (k.as_ref() * nd2)) * (-r * t).exp()
+}
+
+/// Price european puts on futures or forwards with the Black (1976) model
+///
+/// P = e^{-rT} (K N(-d2) - F N(-d1)).
+///
+/// * `f`: The forward prices of the underlying.
+/// * `k`: The options' strike prices.
+/// * `vol`: The volatility of the forwards in decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the puts.
+pub fn price_put_black76<'graph, A, F: ag::Float>(f: A, k: A, vol: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let one = F::one();
+    let zero = F::zero();
+    let (d1, d2) = black76_d1_d2(f, k, vol, r, t);
+    let nnegd1 = math::normal_cdf(&math::neg(d1), zero, one);
+    let nnegd2 = math::normal_cdf(&math::neg(d2), zero, one);
+    ((k.as_ref() * nnegd2) - (f.as_ref() * nnegd1)) * (-r * t).exp()
+}
+
+fn black76_d1_d2<'graph, A, F: ag::Float>(
+    f: A,
+    k: A,
+    vol: A,
+    r: F,
+    t: F,
+) -> (ag::Tensor<'graph, F>, ag::Tensor<'graph, F>)
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    // A forward has a cost of carry of zero, e.g. a dividend yield equal to the rate.
+    let carry = *f.as_ref() * F::zero() + r;
+    d1_d2(f.as_ref(), k.as_ref(), vol.as_ref(), &carry, r, t)
+}
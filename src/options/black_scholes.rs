@@ -1,9 +1,13 @@
 use autograd as ag;
 use autograd::array_gen as gen;
+use autograd::ndarray as nd;
 use autograd::tensor_ops as math;
 
 use autograd::prelude::*;
+use crate::error::QuantError;
+use crate::numerics::optimize::run_epochs_until;
 use crate::options::model::*;
+use crate::options::vol_surface::VolCurve;
 
 pub struct BlackScholesPricingModel;
 
@@ -25,100 +29,995 @@ impl OptionPricingModel for BlackScholesPricingModel {
         q: ag::NdArrayView<F>,
         r: F,
         t: F,
-    ) -> ag::NdArray<F> {
-        match ty {
-            OptionType::Call => call_iv(p, s, k, q, r, t),
-            OptionType::Put => put_iv(p, s, k, q, r, t),
-        }
+    ) -> Result<ag::NdArray<F>, QuantError> {
+        implied_volatility_early_stopping(ty, p, s, k, q, r, t, &EarlyStopping::default()).map(|(vol, _)| vol)
     }
 
     fn delta<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F> 
     where
         A: AsRef<ag::Tensor<'graph, F>> + Copy
     {
-        let stock_price = s.as_ref();
-        let price = BlackScholesPricingModel::price(ty, s, k, vol, q, r, t);
-        math::grad(&[price], &[stock_price])[0]
+        match ty {
+            OptionType::Call => call_delta(s, k, vol, q, r, t),
+            OptionType::Put => put_delta(s, k, vol, q, r, t),
+        }
     }
 
-    fn theta<'graph, A, F: ag::Float> (ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+    fn theta<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
     where
         A: AsRef<ag::Tensor<'graph, F>> + Copy
     {
-        let m: i32 = 1;
-        let n: i32 = 2;
-        // Hacky way to get a scalar tensor.
-        let h = F::from(0.05_f64).unwrap();
-
-        let stencil_points = (-n / 2..n / 2 + 1)
-            .map(|i| {
-                let ti = t - (F::from(i).unwrap() * F::from(0.05_f64).unwrap());
-                let pred = BlackScholesPricingModel::price(
-                    ty, s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r, ti,
-                );
-                pred
-            })
-            .collect::<Vec<_>>();
-        let grad = math::finite_difference(m as usize, n as usize, h, &stencil_points[..]);
-        grad
+        match ty {
+            OptionType::Call => call_theta(s, k, vol, q, r, t),
+            OptionType::Put => put_theta(s, k, vol, q, r, t),
+        }
     }
 
     fn gamma<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
     where
         A: AsRef<ag::Tensor<'graph, F>> + Copy
     {
-        let m: i32 = 1;
-        let n: i32 = 2;
-        // Hacky way to get a scalar tensor.
-        let h = F::from(0.05_f64).unwrap();
-
-        let stencil_points = (-n / 2..n / 2 + 1)
-            .map(|i| {
-                let ti = t - (F::from(i).unwrap() * F::from(0.05_f64).unwrap());
-                let pred = BlackScholesPricingModel::delta(
-                    ty, s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r, ti,
-                );
-                pred
-            })
-            .collect::<Vec<_>>();
-        let grad = math::finite_difference(m as usize, n as usize, h, &stencil_points[..]);
-        grad
+        match ty {
+            OptionType::Call => call_gamma(s, k, vol, q, r, t),
+            OptionType::Put => put_gamma(s, k, vol, q, r, t),
+        }
     }
 
     fn vega<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F> 
     where
         A: AsRef<ag::Tensor<'graph, F>> + Copy
     {
-        let volitility = s.as_ref();
-        let price = BlackScholesPricingModel::price(ty, s, k, vol, q, r, t);
-        math::grad(&[price], &[volitility])[0]
+        match ty {
+            OptionType::Call => call_vega(s, k, vol, q, r, t),
+            OptionType::Put => put_vega(s, k, vol, q, r, t),
+        }
+    }
+
+    fn rho<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+    where
+        A: AsRef<ag::Tensor<'graph, F>> + Copy
+    {
+        match ty {
+            OptionType::Call => call_rho(s, k, vol, q, r, t),
+            OptionType::Put => put_rho(s, k, vol, q, r, t),
+        }
     }
 }
 
-fn call<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+/// Calculate the `delta` of european call options, e.g. ∂V/∂S, by differentiating
+/// the Black-Scholes price graph with respect to the spot prices.
+///
+/// Each option's price only depends on its own spot price, so the gradient of
+/// the batch is the per element delta and has the same shape as `s`.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `delta`: ∂V/∂S for each option.
+pub fn call_delta<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
 where
     A: AsRef<ag::Tensor<'graph, F>> + Copy,
 {
-    let s = s.as_ref();
-    let k = k.as_ref();
-    let vol = vol.as_ref();
-    let q = q.as_ref();
+    let spot = s.as_ref();
+    let price = call(s, k, vol, q, r, t);
+    math::grad(&[price], &[spot])[0]
+}
+
+/// Calculate the `delta` of european put options, e.g. ∂V/∂S, by differentiating
+/// the Black-Scholes price graph with respect to the spot prices.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `delta`: ∂V/∂S for each option.
+pub fn put_delta<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let spot = s.as_ref();
+    let price = put(s, k, vol, q, r, t);
+    math::grad(&[price], &[spot])[0]
+}
+
+/// Calculate the `gamma` of european call options, e.g. ∂²V/∂S², by differentiating
+/// the autograd `delta` a second time with respect to the spot prices.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `gamma`: ∂²V/∂S² for each option.
+pub fn call_gamma<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let spot = s.as_ref();
+    let delta = call_delta(s, k, vol, q, r, t);
+    math::grad(&[delta], &[spot])[0]
+}
+
+/// Calculate the `gamma` of european put options, e.g. ∂²V/∂S², by differentiating
+/// the autograd `delta` a second time with respect to the spot prices.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `gamma`: ∂²V/∂S² for each option.
+pub fn put_gamma<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let spot = s.as_ref();
+    let delta = put_delta(s, k, vol, q, r, t);
+    math::grad(&[delta], &[spot])[0]
+}
+
+/// Calculate the `vega` of european call options, e.g. ∂V/∂σ, by differentiating
+/// the Black-Scholes price graph with respect to the volatilities.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `vega`: ∂V/∂σ for each option, per unit (not percent) of volatility.
+pub fn call_vega<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let volatility = vol.as_ref();
+    let price = call(s, k, vol, q, r, t);
+    math::grad(&[price], &[volatility])[0]
+}
+
+/// Calculate the `vega` of european put options, e.g. ∂V/∂σ, by differentiating
+/// the Black-Scholes price graph with respect to the volatilities.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `vega`: ∂V/∂σ for each option, per unit (not percent) of volatility.
+pub fn put_vega<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let volatility = vol.as_ref();
+    let price = put(s, k, vol, q, r, t);
+    math::grad(&[price], &[volatility])[0]
+}
+
+/// Calculate the `theta` of european call options, e.g. -∂V/∂t, the change in
+/// value per year as the option approaches expiration, by differentiating the price
+/// graph with respect to a tensor of the time to maturity.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `theta`: -∂V/∂t for each option.
+pub fn call_theta<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    sensitivities_to_rate_and_time(|r, t| call_tensor_rate(s, k, vol, q, r, t), s, r, t).0
+}
+
+/// Calculate the `theta` of european put options, e.g. -∂V/∂t, the change in
+/// value per year as the option approaches expiration.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `theta`: -∂V/∂t for each option.
+pub fn put_theta<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    sensitivities_to_rate_and_time(|r, t| put_tensor_rate(s, k, vol, q, r, t), s, r, t).0
+}
+
+/// Calculate the `rho` of european call options, e.g. ∂V/∂r, by differentiating the
+/// price graph with respect to a tensor of the risk free rate.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `rho`: ∂V/∂r for each option.
+pub fn call_rho<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    sensitivities_to_rate_and_time(|r, t| call_tensor_rate(s, k, vol, q, r, t), s, r, t).1
+}
+
+/// Calculate the `rho` of european put options, e.g. ∂V/∂r.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `rho`: ∂V/∂r for each option.
+pub fn put_rho<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    sensitivities_to_rate_and_time(|r, t| put_tensor_rate(s, k, vol, q, r, t), s, r, t).1
+}
+
+/// The `theta` and `rho` of a price graph built on broadcast tensors of the rate and
+/// the time to maturity.
+fn sensitivities_to_rate_and_time<'graph, A, P, F: ag::Float>(
+    price: P,
+    s: A,
+    r: F,
+    t: F,
+) -> (ag::Tensor<'graph, F>, ag::Tensor<'graph, F>)
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+    P: Fn(&ag::Tensor<'graph, F>, &ag::Tensor<'graph, F>) -> ag::Tensor<'graph, F>,
+{
+    let rate = *s.as_ref() * F::zero() + r;
+    let time = *s.as_ref() * F::zero() + t;
+    let grads = math::grad(&[price(&rate, &time)], &[time, rate]);
+    (math::neg(grads[0]), grads[1])
+}
+
+/// Price european options with the risk free rate and the time to maturity given as
+/// tensors, so the price graph can be differentiated with respect to them.
+///
+/// `theta` is the negated gradient with respect to `t` and `rho` the gradient with
+/// respect to `r`. Each option may have its own rate and maturity.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rates as decimal.
+/// * `t`: The times until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the options.
+pub fn price_with_tensor_rate_and_time<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    s: A,
+    k: A,
+    vol: A,
+    q: A,
+    r: A,
+    t: A,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    match ty {
+        OptionType::Call => call_tensor_rate(s, k, vol, q, r, t),
+        OptionType::Put => put_tensor_rate(s, k, vol, q, r, t),
+    }
+}
+
+/// Calculate the `vanna` of european options, e.g. ∂²V/∂S∂σ, by differentiating
+/// the autograd `delta` with respect to the volatilities.
+///
+/// Put-call parity is linear in the spot and independent of the volatility, so
+/// calls and puts with the same inputs share the same `vanna`.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `vanna`: The change in `delta` per unit of volatility, e.g. per 1.0 = 100 vol points.
+pub fn call_vanna<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let volatility = vol.as_ref();
+    let delta = call_delta(s, k, vol, q, r, t);
+    math::grad(&[delta], &[volatility])[0]
+}
+
+/// Calculate the `volga` (or vomma) of european options, e.g. ∂²V/∂σ², by
+/// differentiating the autograd `vega` a second time with respect to the volatilities.
+///
+/// Calls and puts with the same inputs share the same `volga`.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `volga`: The change in `vega` per unit of volatility, e.g. per 1.0 = 100 vol points.
+pub fn call_volga<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let volatility = vol.as_ref();
+    let vega = call_vega(s, k, vol, q, r, t);
+    math::grad(&[vega], &[volatility])[0]
+}
+
+/// Calculate every first order Greek and `gamma` of european call options while
+/// only building the Black-Scholes price graph once.
+///
+/// The rate and the time to maturity enter the graph as tensors, so `delta`, `vega`,
+/// `theta` and `rho` share a single gradient pass over the spot prices, the
+/// volatilities, the time and the rate, and `gamma` differentiates that `delta` again.
+///
+/// ```
+/// use autograd as ag;
+/// use autograd::ndarray as nd;
+/// use autograd::tensor_ops as math;
+/// use rquant::options::black_scholes::call_greeks;
+///
+/// ag::run(|ctx: &mut ag::Context<f64>| {
+///     let s = ctx.placeholder("s", &[-1]);
+///     let k = math::convert_to_tensor(nd::arr1(&[100., 100.]).into_dyn(), ctx);
+///     let vol = math::convert_to_tensor(nd::arr1(&[0.2, 0.2]).into_dyn(), ctx);
+///     let q = math::convert_to_tensor(nd::arr1(&[0., 0.]).into_dyn(), ctx);
+///     let greeks = call_greeks(&s, &k, &vol, &q, 0.05, 1.);
+///
+///     let spot = nd::arr1(&[95., 105.]).into_dyn();
+///     let values = ctx
+///         .evaluator()
+///         .extend(&[greeks.delta, greeks.gamma, greeks.vega, greeks.theta, greeks.rho])
+///         .feed(s, spot.view())
+///         .run();
+///     assert_eq!(values.len(), 5);
+/// });
+/// ```
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `greeks`: The `Greeks` of each option.
+pub fn call_greeks<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> Greeks<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    greeks_of(|r, t| call_tensor_rate(s, k, vol, q, r, t), s, vol, r, t)
+}
+
+/// Calculate every first order Greek and `gamma` of european put options while
+/// only building the Black-Scholes price graph once.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `greeks`: The `Greeks` of each option.
+pub fn put_greeks<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> Greeks<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    greeks_of(|r, t| put_tensor_rate(s, k, vol, q, r, t), s, vol, r, t)
+}
+
+fn greeks_of<'graph, A, P, F: ag::Float>(price: P, s: A, vol: A, r: F, t: F) -> Greeks<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+    P: Fn(&ag::Tensor<'graph, F>, &ag::Tensor<'graph, F>) -> ag::Tensor<'graph, F>,
+{
+    let spot = s.as_ref();
+    let volatility = vol.as_ref();
+    let rate = *spot * F::zero() + r;
+    let time = *spot * F::zero() + t;
+    let value = price(&rate, &time);
+
+    let grads = math::grad(&[value], &[*spot, *volatility, time, rate]);
+    let delta = grads[0];
+    let vega = grads[1];
+    let theta = math::neg(grads[2]);
+    let rho = grads[3];
+    let gamma = math::grad(&[delta], &[spot])[0];
+
+    Greeks { delta, gamma, vega, theta, rho }
+}
+
+/// Price european options on stocks paying known discrete cash dividends using
+/// the escrowed dividend approximation: the present value of every dividend paid
+/// before maturity is subtracted from the spot price before pricing.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the escrowed stocks in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `dividends`: The `(time, amount)` of each cash dividend, time as decimal of a year.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the options.
+pub fn price_with_discrete_dividends<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    s: A,
+    k: A,
+    vol: A,
+    q: A,
+    dividends: &[(F, F)],
+    r: F,
+    t: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let pv = present_value_of_dividends(dividends, r, t);
+    let escrowed = s.as_ref() - pv;
+    BlackScholesPricingModel::price(ty, &escrowed, k.as_ref(), vol.as_ref(), q.as_ref(), r, t)
+}
+
+/// Price european calls off a term structure of volatility, using the root mean
+/// square vol to maturity `√(∫₀ᵀ σ(u)² du / T)` in `d1` and `d2`.
+///
+/// Pricing a strip of maturities off one `VolCurve` keeps their vols consistent
+/// with a single volatility path. A `FlatVolCurve` reproduces the constant vol price
+/// exactly.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The term structure of the stocks' volatility.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the options.
+pub fn price_call_term_vol<'graph, A, F: ag::Float, V: VolCurve<F>>(
+    s: A,
+    k: A,
+    vol: &V,
+    q: A,
+    r: F,
+    t: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let rms_vol = *s.as_ref() * F::zero() + vol.rms_vol(t);
+    call(s.as_ref(), k.as_ref(), &rms_vol, q.as_ref(), r, t)
+}
+
+/// The present value of the cash dividends paid strictly before maturity.
+///
+/// * `dividends`: The `(time, amount)` of each cash dividend, time as decimal of a year.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+pub fn present_value_of_dividends<F: ag::Float>(dividends: &[(F, F)], r: F, t: F) -> F {
+    dividends
+        .iter()
+        .filter(|(time, _)| *time < t)
+        .fold(F::zero(), |pv, &(time, amount)| pv + amount * (-r * time).exp())
+}
+
+/// The amount by which european call and put prices violate put-call parity
+///
+/// C - P = S e^{-qT} - K e^{-rT}.
+///
+/// * `call`: The price of the call.
+/// * `put`: The price of the put with the same strike and maturity.
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The options' strike price per share.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `residual`: `C - P - (S e^{-qT} - K e^{-rT})`, zero for prices that satisfy parity.
+pub fn put_call_parity_residual<F: ag::Float>(call: F, put: F, s: F, k: F, q: F, r: F, t: F) -> F {
+    call - put - (s * (-q * t).exp() - k * (-r * t).exp())
+}
+
+/// Check that european call and put prices satisfy put-call parity within `tol`.
+///
+/// * `call`: The price of the call.
+/// * `put`: The price of the put with the same strike and maturity.
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The options' strike price per share.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `tol`: The largest accepted absolute parity residual.
+pub fn check_put_call_parity<F: ag::Float>(call: F, put: F, s: F, k: F, q: F, r: F, t: F, tol: F) -> bool {
+    put_call_parity_residual(call, put, s, k, q, r, t).abs() <= tol
+}
+
+/// Calculate the dividend yield implied by the prices of a european call and put with
+/// the same strike and maturity, by inverting put-call parity
+///
+/// q = -ln((C - P + K e^{-rT}) / S) / T.
+///
+/// * `call`: The price of the call.
+/// * `put`: The price of the put with the same strike and maturity.
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The options' strike price per share.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `q`: The continuous dividend yield per year as decimal, or `QuantError::InvalidInput`
+///   when the spot or maturity is not positive or the prices imply a forward that is
+///   not positive.
+pub fn implied_dividend_yield<F: ag::Float>(call: F, put: F, s: F, k: F, r: F, t: F) -> Result<F, QuantError> {
+    if !(s > F::zero()) || !(t > F::zero()) {
+        return Err(QuantError::InvalidInput("spot and maturity must be positive".to_string()));
+    }
+    // The discounted forward S e^{-qT} of the prices.
+    let forward = call - put + k * (-r * t).exp();
+    if !(forward > F::zero()) {
+        return Err(QuantError::InvalidInput("prices imply a forward that is not positive".to_string()));
+    }
+    Ok(-(forward / s).ln() / t)
+}
+
+/// Calculate the implied volatility of european options with Adam, stopping early
+/// once the fit is good enough or stops improving.
+///
+/// The loop behind `BlackScholesPricingModel::implied_volatility` always runs for
+/// `EarlyStopping::default().max_epochs`; most options converge well before then.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `p`: The price of the options.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `stopping`: When to stop the fit.
+///
+/// * `(volatility, epochs)`: The implied volatility of the options and the number of
+///   epochs run, or `QuantError::ShapeMismatch` when the inputs' shapes differ.
+pub fn implied_volatility_early_stopping<F: ag::Float>(
+    ty: OptionType,
+    p: ag::NdArrayView<F>,
+    s: ag::NdArrayView<F>,
+    k: ag::NdArrayView<F>,
+    q: ag::NdArrayView<F>,
+    r: F,
+    t: F,
+    stopping: &EarlyStopping<F>,
+) -> Result<(ag::NdArray<F>, usize), QuantError> {
+    QuantError::check_shape(p.shape(), s.shape())?;
+    QuantError::check_shape(p.shape(), k.shape())?;
+    QuantError::check_shape(p.shape(), q.shape())?;
+    Ok(match ty {
+        OptionType::Call => call_iv(p, s, k, q, r, t, stopping),
+        OptionType::Put => put_iv(p, s, k, q, r, t, stopping),
+    })
+}
+
+/// Calculate the implied volatility of european options with Newton-Raphson steps
+/// `σ -= (price(σ) - p) / vega(σ)`, using the autograd vega of the Black-Scholes price.
+///
+/// Iteration stops once every option is repriced within `tol` of its target price,
+/// or after `max_iter` steps. A step that would make a volatility non-positive halves
+/// it instead.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `p`: The price of the options.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `tol`: The largest accepted absolute pricing error.
+/// * `max_iter`: The maximum number of Newton steps.
+///
+/// * `(volatility, converged)`: The implied volatility of the options and whether
+///   every option was repriced within `tol`, or `QuantError::ShapeMismatch` when
+///   the inputs' shapes differ.
+pub fn implied_volatility_newton<F: ag::Float>(
+    ty: OptionType,
+    p: ag::NdArrayView<F>,
+    s: ag::NdArrayView<F>,
+    k: ag::NdArrayView<F>,
+    q: ag::NdArrayView<F>,
+    r: F,
+    t: F,
+    tol: F,
+    max_iter: usize,
+) -> Result<(ag::NdArray<F>, bool), QuantError> {
+    QuantError::check_shape(p.shape(), s.shape())?;
+    QuantError::check_shape(p.shape(), k.shape())?;
+    QuantError::check_shape(p.shape(), q.shape())?;
+
+    let half = F::from(0.5f64).unwrap();
+    let mut vol = ag::NdArray::from_elem(p.shape(), half);
+
+    for iteration in 0..=max_iter {
+        let (price, vega) = price_and_vega(ty, s, k, &vol, q, r, t);
+
+        let errors = &price - &p;
+        if errors.iter().all(|e| e.abs() < tol) {
+            return Ok((vol, true));
+        }
+        if iteration == max_iter {
+            break;
+        }
+
+        for ((v, &e), &g) in vol.iter_mut().zip(errors.iter()).zip(vega.iter()) {
+            if g <= F::epsilon() {
+                continue;
+            }
+            let next = *v - e / g;
+            *v = if next > F::zero() { next } else { *v * half };
+        }
+    }
+
+    Ok((vol, false))
+}
+
+/// Calculate the implied volatility of european options by bisection on the
+/// bracket `[1e-4, 5]`.
+///
+/// Slower than `implied_volatility_newton`, but it cannot diverge where vega is
+/// tiny, e.g. for deep in or out of the money options. Every target price must
+/// lie between the prices at the ends of the bracket.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `p`: The price of the options.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `tol`: The largest accepted absolute pricing error.
+///
+/// * `volatility`: The implied volatility of the options, `QuantError::NoBracket` when
+///   a target price cannot be reached on the bracket, or `QuantError::ShapeMismatch`
+///   when the inputs' shapes differ.
+pub fn implied_volatility_bisection<F: ag::Float>(
+    ty: OptionType,
+    p: ag::NdArrayView<F>,
+    s: ag::NdArrayView<F>,
+    k: ag::NdArrayView<F>,
+    q: ag::NdArrayView<F>,
+    r: F,
+    t: F,
+    tol: F,
+) -> Result<ag::NdArray<F>, QuantError> {
+    QuantError::check_shape(p.shape(), s.shape())?;
+    QuantError::check_shape(p.shape(), k.shape())?;
+    QuantError::check_shape(p.shape(), q.shape())?;
 
     let half = F::from(0.5f64).unwrap();
+    let mut lo = ag::NdArray::from_elem(p.shape(), F::from(1e-4f64).unwrap());
+    let mut hi = ag::NdArray::from_elem(p.shape(), F::from(5f64).unwrap());
+
+    let lo_price = price_array(ty, s, k, &lo, q, r, t);
+    let hi_price = price_array(ty, s, k, &hi, q, r, t);
+    let bracketed = p
+        .iter()
+        .zip(lo_price.iter().zip(hi_price.iter()))
+        .all(|(&target, (&low, &high))| low - tol <= target && target <= high + tol);
+    if !bracketed {
+        return Err(QuantError::NoBracket);
+    }
+
+    let mut mid = (&lo + &hi).mapv(|v| v * half);
+    for _ in 0..100 {
+        let errors = &price_array(ty, s, k, &mid, q, r, t) - &p;
+        if errors.iter().all(|e| e.abs() < tol) {
+            break;
+        }
+        // Prices increase with volatility, so an overpriced midpoint is too volatile.
+        for (((l, h), &m), &e) in lo.iter_mut().zip(hi.iter_mut()).zip(mid.iter()).zip(errors.iter()) {
+            if e > F::zero() {
+                *h = m;
+            } else {
+                *l = m;
+            }
+        }
+        mid = (&lo + &hi).mapv(|v| v * half);
+    }
+
+    Ok(mid)
+}
+
+/// Calculate the implied volatility of a chain of european options with differing
+/// maturities using Newton-Raphson steps.
+///
+/// Options are grouped by maturity and each step only reprices the options that
+/// have not yet converged, so easy points stop costing work while the harder ones
+/// keep iterating.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `p`: The price of the options.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `t`: The time until each option's maturity as decimal of a year.
+/// * `r`: The risk free interest rate as decimal.
+/// * `tol`: The largest accepted absolute pricing error.
+/// * `max_iter`: The maximum number of Newton steps.
+///
+/// * `(volatility, converged)`: The implied volatility of the options and whether each
+///   option was repriced within `tol`, or `QuantError::ShapeMismatch` when the inputs'
+///   shapes differ.
+pub fn implied_volatility_chain<F: ag::Float>(
+    ty: OptionType,
+    p: ag::NdArrayView<F>,
+    s: ag::NdArrayView<F>,
+    k: ag::NdArrayView<F>,
+    q: ag::NdArrayView<F>,
+    t: ag::NdArrayView<F>,
+    r: F,
+    tol: F,
+    max_iter: usize,
+) -> Result<(ag::NdArray<F>, nd::ArrayD<bool>), QuantError> {
+    QuantError::check_shape(p.shape(), s.shape())?;
+    QuantError::check_shape(p.shape(), k.shape())?;
+    QuantError::check_shape(p.shape(), q.shape())?;
+    QuantError::check_shape(p.shape(), t.shape())?;
+
+    let half = F::from(0.5f64).unwrap();
+    let targets = p.iter().cloned().collect::<Vec<_>>();
+    let spots = s.iter().cloned().collect::<Vec<_>>();
+    let strikes = k.iter().cloned().collect::<Vec<_>>();
+    let dividends = q.iter().cloned().collect::<Vec<_>>();
+    let maturities = t.iter().cloned().collect::<Vec<_>>();
+    let mut vol = vec![half; targets.len()];
+    let mut converged = vec![false; targets.len()];
+
+    for iteration in 0..=max_iter {
+        let mut groups: Vec<(F, Vec<usize>)> = Vec::new();
+        for i in (0..targets.len()).filter(|&i| !converged[i]) {
+            match groups.iter_mut().find(|(maturity, _)| *maturity == maturities[i]) {
+                Some((_, indices)) => indices.push(i),
+                None => groups.push((maturities[i], vec![i])),
+            }
+        }
+        if groups.is_empty() {
+            break;
+        }
+
+        for (maturity, indices) in groups {
+            let gather = |xs: &[F]| nd::Array1::from_shape_fn(indices.len(), |j| xs[indices[j]]).into_dyn();
+            let (price, vega) = price_and_vega(
+                ty,
+                gather(&spots).view(),
+                gather(&strikes).view(),
+                &gather(&vol),
+                gather(&dividends).view(),
+                r,
+                maturity,
+            );
+
+            for (j, &i) in indices.iter().enumerate() {
+                let error = price[j] - targets[i];
+                if error.abs() < tol {
+                    converged[i] = true;
+                } else if iteration < max_iter && vega[j] > F::epsilon() {
+                    let next = vol[i] - error / vega[j];
+                    vol[i] = if next > F::zero() { next } else { vol[i] * half };
+                }
+            }
+        }
+    }
+
+    Ok((
+        nd::Array1::from(vol).into_shape(p.shape()).unwrap(),
+        nd::Array1::from(converged).into_shape(p.shape()).unwrap(),
+    ))
+}
+
+/// Calculate a surface of european option prices over a grid of strikes and
+/// maturities on one stock.
+///
+/// Each maturity prices every strike at once, and the prices of all maturities are
+/// evaluated together in a single graph evaluation.
+///
+/// * `ty`: The type of the options, `Call` or `Put`.
+/// * `s`: The underlying stock's price per share.
+/// * `strikes`: The strike prices per share along the first axis of the surface.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `maturities`: The times until maturity along the second axis of the surface, as
+///   decimal of a year.
+///
+/// * `prices`: The `[strikes.len(), maturities.len()]` prices, where entry `[i, j]`
+///   is the option struck at `strikes[i]` maturing at `maturities[j]`.
+pub fn price_surface<F: ag::Float>(
+    ty: OptionType,
+    s: F,
+    strikes: &[F],
+    vol: F,
+    q: F,
+    r: F,
+    maturities: &[F],
+) -> ag::NdArray<F> {
+    let n = strikes.len();
+    let columns = ag::run(|ctx: &mut ag::Context<F>| {
+        let strike = math::convert_to_tensor(nd::Array1::from(strikes.to_vec()).into_dyn(), ctx);
+        let zeros = strike * F::zero();
+        let (spot, sigma, dividends) = (zeros + s, zeros + vol, zeros + q);
+        let prices = maturities
+            .iter()
+            .map(|&t| BlackScholesPricingModel::price(ty, &spot, &strike, &sigma, &dividends, r, t))
+            .collect::<Vec<_>>();
+
+        let mut evaluator = ctx.evaluator();
+        for price in prices.iter() {
+            evaluator.push(price);
+        }
+        evaluator.run().into_iter().map(|price| price.unwrap()).collect::<Vec<_>>()
+    });
+
+    nd::Array2::from_shape_fn((n, maturities.len()), |(i, j)| columns[j][i]).into_dyn()
+}
+
+/// Evaluate the Black-Scholes prices and vegas of a batch of options outside of a graph.
+fn price_and_vega<F: ag::Float>(
+    ty: OptionType,
+    s: ag::NdArrayView<F>,
+    k: ag::NdArrayView<F>,
+    vol: &ag::NdArray<F>,
+    q: ag::NdArrayView<F>,
+    r: F,
+    t: F,
+) -> (ag::NdArray<F>, ag::NdArray<F>) {
+    ag::run(|ctx: &mut ag::Context<F>| {
+        let spot = math::convert_to_tensor(s.to_owned(), ctx);
+        let strike = math::convert_to_tensor(k.to_owned(), ctx);
+        let sigma = math::convert_to_tensor(vol.clone(), ctx);
+        let dividends = math::convert_to_tensor(q.to_owned(), ctx);
+        let price = BlackScholesPricingModel::price(ty, &spot, &strike, &sigma, &dividends, r, t);
+        let vega = BlackScholesPricingModel::vega(ty, &spot, &strike, &sigma, &dividends, r, t);
+        let mut results = ctx.evaluator().push(&price).push(&vega).run().into_iter();
+        (results.next().unwrap().unwrap(), results.next().unwrap().unwrap())
+    })
+}
+
+/// Evaluate the Black-Scholes prices of a batch of options outside of a graph.
+fn price_array<F: ag::Float>(
+    ty: OptionType,
+    s: ag::NdArrayView<F>,
+    k: ag::NdArrayView<F>,
+    vol: &ag::NdArray<F>,
+    q: ag::NdArrayView<F>,
+    r: F,
+    t: F,
+) -> ag::NdArray<F> {
+    ag::run(|ctx: &mut ag::Context<F>| {
+        let spot = math::convert_to_tensor(s.to_owned(), ctx);
+        let strike = math::convert_to_tensor(k.to_owned(), ctx);
+        let sigma = math::convert_to_tensor(vol.clone(), ctx);
+        let dividends = math::convert_to_tensor(q.to_owned(), ctx);
+        BlackScholesPricingModel::price(ty, &spot, &strike, &sigma, &dividends, r, t)
+            .eval(ctx)
+            .unwrap()
+    })
+}
+
+fn call<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
     let one = F::one();
     let zero = F::zero();
-    // d1 = ln(s/k) + (vol^2 / 2 + r) * t
-    //      -----------------------------
-    //             vol * sqrt(t)
-    let d1 = (math::ln(s / k) + (((math::square(vol) * half) + r) - q) * t) / (vol * t.sqrt());
-    // d2 = d1 - vol * sqrt(t)
-    let d2 = d1 - (vol * t.sqrt());
+    let (d1, d2) = d1_d2(s, k, vol, q, r, t);
     let nd1 = math::normal_cdf(&d1, zero, one);
     let nd2 = math::normal_cdf(&d2, zero, one);
-    ((s * math::exp(math::neg(q * t))) * nd1) - ((k * (-t * r).exp()) * nd2)
+    ((s.as_ref() * math::exp(math::neg(q.as_ref() * t))) * nd1) - ((k.as_ref() * (-t * r).exp()) * nd2)
 }
 
 fn put<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let one = F::one();
+    let zero = F::zero();
+    let (d1, d2) = d1_d2(s, k, vol, q, r, t);
+    let nnegd1 = math::normal_cdf(&math::neg(d1), zero, one);
+    let nnegd2 = math::normal_cdf(&math::neg(d2), zero, one);
+    ((k.as_ref() * (-r * t).exp()) * nnegd2) - ((s.as_ref() * math::exp(math::neg(q.as_ref() * t))) * nnegd1)
+}
+
+fn call_tensor_rate<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: A, t: A) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let one = F::one();
+    let zero = F::zero();
+    let (r, t) = (r.as_ref(), t.as_ref());
+    let (d1, d2) = d1_d2_tensor_rate(s, k, vol, q, r, t);
+    let nd1 = math::normal_cdf(&d1, zero, one);
+    let nd2 = math::normal_cdf(&d2, zero, one);
+    ((s.as_ref() * math::exp(math::neg(q.as_ref() * t))) * nd1) - ((k.as_ref() * math::exp(math::neg(r * t))) * nd2)
+}
+
+fn put_tensor_rate<'graph, A, F: ag::Float>(s: A, k: A, vol: A, q: A, r: A, t: A) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let one = F::one();
+    let zero = F::zero();
+    let (r, t) = (r.as_ref(), t.as_ref());
+    let (d1, d2) = d1_d2_tensor_rate(s, k, vol, q, r, t);
+    let nnegd1 = math::normal_cdf(&math::neg(d1), zero, one);
+    let nnegd2 = math::normal_cdf(&math::neg(d2), zero, one);
+    let discounted_strike = k.as_ref() * math::exp(math::neg(r * t));
+    (discounted_strike * nnegd2) - ((s.as_ref() * math::exp(math::neg(q.as_ref() * t))) * nnegd1)
+}
+
+/// The `d1` and `d2` terms with the rate and the time to maturity as tensors.
+fn d1_d2_tensor_rate<'graph, A, F: ag::Float>(
+    s: A,
+    k: A,
+    vol: A,
+    q: A,
+    r: &ag::Tensor<'graph, F>,
+    t: &ag::Tensor<'graph, F>,
+) -> (ag::Tensor<'graph, F>, ag::Tensor<'graph, F>)
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let (s, k, vol, q) = (s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref());
+    let half = F::from(0.5f64).unwrap();
+    let sqrt_t = math::sqrt(t);
+    let d1 = (math::ln(s / k) + (((math::square(vol) * half) + r) - q) * t) / (vol * sqrt_t);
+    let d2 = d1 - (vol * sqrt_t);
+    (d1, d2)
+}
+
+/// The `d1` and `d2` terms shared by the Black-Scholes-Merton call and put prices.
+///
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+pub(crate) fn d1_d2<'graph, A, F: ag::Float>(
+    s: A,
+    k: A,
+    vol: A,
+    q: A,
+    r: F,
+    t: F,
+) -> (ag::Tensor<'graph, F>, ag::Tensor<'graph, F>)
 where
     A: AsRef<ag::Tensor<'graph, F>> + Copy,
 {
@@ -127,98 +1026,88 @@ where
     let vol = vol.as_ref();
     let q = q.as_ref();
     let half = F::from(0.5f64).unwrap();
-    let one = F::one();
-    let zero = F::zero();
     // d1 = ln(s/k) + (vol^2 / 2 + r - q) * t
-    //      -----------------------------
-    //             vol * sqrt(t)
-
+    //      ---------------------------------
+    //               vol * sqrt(t)
     let d1 = (math::ln(s / k) + (((math::square(vol) * half) + r) - q) * t) / (vol * t.sqrt());
     // d2 = d1 - vol * sqrt(t)
     let d2 = d1 - (vol * t.sqrt());
-    let nnegd1 = math::normal_cdf(&math::neg(d1), zero, one);
-    let nnegd2 = math::normal_cdf(&math::neg(d2), zero, one);
-    ((k * (-r * t).exp()) * nnegd2) - ((s * math::exp(math::neg(q * t))) * nnegd1)
+    (d1, d2)
 }
 
-fn call_iv<'graph, F: ag::Float>(
+fn call_iv<F: ag::Float>(
     c: ag::NdArrayView<F>,
     s: ag::NdArrayView<F>,
     k: ag::NdArrayView<F>,
     q: ag::NdArrayView<F>,
     r: F,
     t: F,
-) -> ag::NdArray<F> {
-    let mut env = ag::VariableEnvironment::new();
-    let ret_id = env.name("vol").set(gen::ones(c.shape()));
-
-    let adam = ag::optimizers::adam::Adam::default("AdamIV", env.default_namespace().current_var_ids(), &mut env);
-
-    for _ in 0..1000 {
-        env.run(|ctx| {
-            let vol = ctx.variable("vol");
-            let call_price = ctx.placeholder("c", &[-1]);
-            let spot = ctx.placeholder("s", &[-1]);
-            let strike = ctx.placeholder("k", &[-1]);
-            let dividends = ctx.placeholder("q", &[-1]);
-            let pred = call(&spot, &strike, &vol, &dividends, r, t);
-
-            let losses = math::abs(call_price - pred);
-            let grads = math::grad(&[losses], &[vol]);
-            
-            let mut feeder = ag::Feeder::new();
-            feeder.push(call_price, c.view())
-                  .push(spot, s.view())
-                  .push(strike, k.view())
-                  .push(dividends, q.view());
-
-            adam.update(&[vol], &grads, ctx, feeder);
-        });
-    }
-
-    env.get_array_by_id(ret_id)
-        .unwrap()
-        .clone()
-        .into_inner()
+    stopping: &EarlyStopping<F>,
+) -> (ag::NdArray<F>, usize) {
+    implied_volatility(|s, k, vol, q| call(s, k, vol, q, r, t), c, s, k, q, stopping)
 }
 
-fn put_iv<'graph, F: ag::Float>(
+fn put_iv<F: ag::Float>(
     p: ag::NdArrayView<F>,
     s: ag::NdArrayView<F>,
     k: ag::NdArrayView<F>,
     q: ag::NdArrayView<F>,
     r: F,
     t: F,
-) -> ag::NdArray<F> {
+    stopping: &EarlyStopping<F>,
+) -> (ag::NdArray<F>, usize) {
+    implied_volatility(|s, k, vol, q| put(s, k, vol, q, r, t), p, s, k, q, stopping)
+}
+
+/// Fit the volatilities at which `pricer` reproduces the target prices `p` by
+/// minimizing the absolute pricing error with Adam.
+///
+/// * `pricer`: Prices the options from the spots, strikes, volatilities and dividend yields.
+/// * `p`: The price of the options.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strike prices per share.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `stopping`: When to stop the fit.
+///
+/// * `(volatility, epochs)`: The fitted volatilities and the number of epochs run.
+fn implied_volatility<F: ag::Float, P>(
+    pricer: P,
+    p: ag::NdArrayView<F>,
+    s: ag::NdArrayView<F>,
+    k: ag::NdArrayView<F>,
+    q: ag::NdArrayView<F>,
+    stopping: &EarlyStopping<F>,
+) -> (ag::NdArray<F>, usize)
+where
+    P: for<'graph> Fn(
+        &ag::Tensor<'graph, F>,
+        &ag::Tensor<'graph, F>,
+        &ag::Tensor<'graph, F>,
+        &ag::Tensor<'graph, F>,
+    ) -> ag::Tensor<'graph, F>,
+{
     let mut env = ag::VariableEnvironment::new();
     let ret_id = env.name("vol").set(gen::ones(p.shape()));
 
     let adam = ag::optimizers::adam::Adam::default("AdamIV", env.default_namespace().current_var_ids(), &mut env);
 
-    for _ in 0..1000 {
-        env.run(|ctx| {
-            let vol = ctx.variable("vol");
-            let put_price = ctx.placeholder("p", &[-1]);
-            let spot = ctx.placeholder("s", &[-1]);
-            let strike = ctx.placeholder("k", &[-1]);
-            let dividends = ctx.placeholder("q", &[-1]);
-            let pred = call(&spot, &strike, &vol, &dividends, r, t);
-
-            let losses = math::abs(put_price - pred);
-            let grads = math::grad(&[losses], &[vol]);
-            
-            let mut feeder = ag::Feeder::new();
-            feeder.push(put_price, p.view())
-                  .push(spot, s.view())
-                  .push(strike, k.view())
-                  .push(dividends, q.view());
-
-            adam.update(&[vol], &grads, ctx, feeder);
-        });
-    }
+    let epochs = env.run(|ctx| {
+        let vol = ctx.variable("vol");
+        let price = ctx.placeholder("p", &[-1]);
+        let spot = ctx.placeholder("s", &[-1]);
+        let strike = ctx.placeholder("k", &[-1]);
+        let dividends = ctx.placeholder("q", &[-1]);
+        let pred = pricer(&spot, &strike, &vol, &dividends);
+
+        let losses = math::abs(price - pred);
+        let mean_loss = math::reduce_mean(losses, &[0], false);
+        let grads = math::grad(&[losses], &[vol]);
+        let update = adam.get_update_op(&[vol], &grads, ctx);
+
+        let feeds = [(price, p.view()), (spot, s.view()), (strike, k.view()), (dividends, q.view())];
+        let mut monitor = stopping.monitor();
+        run_epochs_until(ctx, &update, &mean_loss, &feeds, stopping.max_epochs, |loss| monitor.should_stop(loss))
+    });
 
-    env.get_array_by_id(ret_id)
-        .unwrap()
-        .clone()
-        .into_inner()
+    (env.get_array_by_id(ret_id).unwrap().clone().into_inner(), epochs)
 }
@@ -0,0 +1,75 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::numerics::root::brent;
+use crate::stats::normal::{bivariate_cdf, cdf_scalar};
+
+/// Calculate the price of a european call on a european call with Geske's (1979)
+/// formula.
+///
+/// The compound option gives the right to pay `k1` at `t1` for a call struck at `k2`
+/// that matures at `t2`. It is exercised when the underlying call is worth more than
+/// `k1` at `t1`, i.e. when the stock is above the critical price `S*` solving
+/// `C(S*, k2, t2 - t1) = k1`, which is found with Brent's method. Then
+///
+/// V = S e^{-qT₂} M(a1, b1; ρ) - k2 e^{-rT₂} M(a2, b2; ρ) - k1 e^{-rT₁} N(a2),
+///
+/// with ρ = √(T₁/T₂), `a` the d-terms of `S*` to `t1` and `b` those of `k2` to `t2`.
+/// Installment options are compound options with several payment dates.
+///
+/// * `s`: The underlying stock's price per share.
+/// * `k1`: The price paid for the underlying call at `t1`.
+/// * `t1`: The time until the compound option's maturity as decimal of a year.
+/// * `k2`: The underlying call's strike price per share.
+/// * `t2`: The time until the underlying call's maturity as decimal of a year.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+///
+/// * `price`: The price of the compound option, or `QuantError::InvalidInput` when a
+///   price, the volatility or a maturity is not positive or `t2` is not after `t1`.
+pub fn price_compound_call_on_call<F: ag::Float>(
+    s: F,
+    k1: F,
+    t1: F,
+    k2: F,
+    t2: F,
+    vol: F,
+    q: F,
+    r: F,
+) -> Result<F, QuantError> {
+    if [s, k1, k2, vol, t1].iter().any(|x| !(*x > F::zero())) || !(t2 > t1) {
+        return Err(QuantError::InvalidInput(
+            "prices, volatility and maturities must be positive with t1 < t2".to_string(),
+        ));
+    }
+    let two = F::from(2f64).unwrap();
+    let n = |x: F| F::from(cdf_scalar(x.to_f64().unwrap())).unwrap();
+    let call = |spot: F, t: F| {
+        if !(spot > F::zero()) {
+            return F::zero();
+        }
+        let d1 = ((spot / k2).ln() + (r - q + vol * vol / two) * t) / (vol * t.sqrt());
+        spot * (-q * t).exp() * n(d1) - k2 * (-r * t).exp() * n(d1 - vol * t.sqrt())
+    };
+
+    // The underlying call grows without bound in the spot, so double the bracket's
+    // upper end until it is worth more than `k1`.
+    let remaining = t2 - t1;
+    let mut hi = s.max(k2);
+    while call(hi, remaining) < k1 {
+        hi = hi * two;
+    }
+    let critical = brent(|spot| call(spot, remaining) - k1, F::zero(), hi, F::epsilon() * hi)?;
+
+    let d = |spot: F, t: F| ((s / spot).ln() + (r - q + vol * vol / two) * t) / (vol * t.sqrt());
+    let a1 = d(critical, t1);
+    let a2 = a1 - vol * t1.sqrt();
+    let b1 = d(k2, t2);
+    let b2 = b1 - vol * t2.sqrt();
+    let rho = (t1 / t2).sqrt();
+
+    Ok(s * (-q * t2).exp() * bivariate_cdf(a1, b1, rho)
+        - k2 * (-r * t2).exp() * bivariate_cdf(a2, b2, rho)
+        - k1 * (-r * t1).exp() * n(a2))
+}
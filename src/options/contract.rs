@@ -0,0 +1,103 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::options::model::*;
+
+/// The fixed terms of a european option, priced against market inputs that change.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `strike`: The option's strike price per share.
+/// * `maturity`: The time until option maturity as decimal of a year.
+/// * `dividend_yield`: The continuous dividend yield of the stock per year as decimal,
+///   `None` for a stock without dividends.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptionContract<F> {
+    pub ty: OptionType,
+    pub strike: F,
+    pub maturity: F,
+    pub dividend_yield: Option<F>,
+}
+
+impl<F: ag::Float> OptionContract<F> {
+    /// Start building a contract of type `ty`.
+    pub fn builder(ty: OptionType) -> OptionContractBuilder<F> {
+        OptionContractBuilder {
+            ty,
+            strike: None,
+            maturity: None,
+            dividend_yield: None,
+        }
+    }
+
+    /// Calculate the price of the contract with the pricing model `M`.
+    ///
+    /// * `s`: The underlying stocks' prices per share.
+    /// * `vol`: The volatility of the stocks in decimal, with the same shape as `s`.
+    /// * `r`: The risk free interest rate as decimal.
+    ///
+    /// * `prices`: The price of the contract at each spot and volatility.
+    pub fn price<'graph, M, A>(&self, s: A, vol: A, r: F) -> ag::Tensor<'graph, F>
+    where
+        M: OptionPricingModel,
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
+    {
+        let zeros = *s.as_ref() * F::zero();
+        let k = zeros + self.strike;
+        let q = zeros + self.dividend_yield.unwrap_or_else(F::zero);
+        M::price(self.ty, s.as_ref(), &k, vol.as_ref(), &q, r, self.maturity)
+    }
+}
+
+/// Sets the terms of an `OptionContract` one at a time.
+#[derive(Copy, Clone, Debug)]
+pub struct OptionContractBuilder<F> {
+    ty: OptionType,
+    strike: Option<F>,
+    maturity: Option<F>,
+    dividend_yield: Option<F>,
+}
+
+impl<F: ag::Float> OptionContractBuilder<F> {
+    /// Set the strike price per share.
+    pub fn strike(mut self, strike: F) -> Self {
+        self.strike = Some(strike);
+        self
+    }
+
+    /// Set the time until maturity as decimal of a year.
+    pub fn maturity(mut self, maturity: F) -> Self {
+        self.maturity = Some(maturity);
+        self
+    }
+
+    /// Set the continuous dividend yield per year as decimal.
+    pub fn dividend_yield(mut self, dividend_yield: F) -> Self {
+        self.dividend_yield = Some(dividend_yield);
+        self
+    }
+
+    /// Finish the contract.
+    ///
+    /// * `contract`: The contract, or `QuantError::InvalidInput` when the strike or
+    ///   maturity is missing or not positive.
+    pub fn build(self) -> Result<OptionContract<F>, QuantError> {
+        let strike = positive(self.strike, "strike")?;
+        let maturity = positive(self.maturity, "maturity")?;
+        Ok(OptionContract {
+            ty: self.ty,
+            strike,
+            maturity,
+            dividend_yield: self.dividend_yield,
+        })
+    }
+}
+
+/// Check that a required term was set to a positive value.
+fn positive<F: ag::Float>(value: Option<F>, name: &str) -> Result<F, QuantError> {
+    match value {
+        Some(value) if value > F::zero() => Ok(value),
+        Some(value) => Err(QuantError::InvalidInput(format!("{} {} must be positive", name, value))),
+        None => Err(QuantError::InvalidInput(format!("{} is required", name))),
+    }
+}
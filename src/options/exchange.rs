@@ -0,0 +1,43 @@
+use autograd as ag;
+use autograd::tensor_ops as math;
+
+use crate::stats::normal;
+
+/// Price european options to exchange one stock for another with Margrabe's (1978)
+/// formula
+///
+/// V = S₁ N(d1) - S₂ N(d2), d1 = (ln(S₁/S₂) + σ²T/2) / (σ√T), d2 = d1 - σ√T,
+///
+/// where σ = √(σ₁² + σ₂² - 2ρσ₁σ₂) is the volatility of the ratio S₁/S₂. The payoff
+/// `max(S₁ - S₂, 0)` takes the second stock as the numeraire, so the risk free rate
+/// drops out of the price.
+///
+/// * `s1`: The prices per share of the stocks received.
+/// * `s2`: The prices per share of the stocks given up.
+/// * `vol1`: The volatility of the stocks received in decimal.
+/// * `vol2`: The volatility of the stocks given up in decimal.
+/// * `rho`: The correlation between the two stocks' returns.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the exchange options.
+pub fn price_exchange_option<'graph, A, F: ag::Float>(
+    s1: A,
+    s2: A,
+    vol1: A,
+    vol2: A,
+    rho: A,
+    t: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let two = F::from(2f64).unwrap();
+    let half = F::from(0.5f64).unwrap();
+    let (s1, s2, vol1, vol2) = (s1.as_ref(), s2.as_ref(), vol1.as_ref(), vol2.as_ref());
+    let variance = math::square(vol1) + math::square(vol2) - vol1 * vol2 * rho.as_ref() * two;
+    let std = math::sqrt(variance * t);
+
+    let d1 = (math::ln(s1 / s2) + std * std * half) / std;
+    let d2 = d1 - std;
+    s1 * normal::cdf(&d1, F::zero(), F::one()) - s2 * normal::cdf(&d2, F::zero(), F::one())
+}
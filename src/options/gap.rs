@@ -0,0 +1,54 @@
+use autograd as ag;
+use autograd::tensor_ops as math;
+
+use crate::options::black_scholes::d1_d2;
+use crate::options::model::*;
+use crate::stats::normal;
+
+/// Calculate the price of european gap options, whose exercise is triggered by one
+/// strike while the payoff is struck at another: calls pay `S_T - K` when
+/// `S_T > trigger` and puts pay `K - S_T` when `S_T < trigger`.
+///
+/// The payoff can be negative when the strikes are on opposite sides of the
+/// terminal price. The d-terms are those of Black-Scholes at the trigger, so
+///
+/// C = S e^{-qT} N(d1) - K e^{-rT} N(d2), P = K e^{-rT} N(-d2) - S e^{-qT} N(-d1),
+///
+/// and with equal strikes the gap option is the vanilla option.
+///
+/// This function can price multiple options at once by inputing
+/// a multidimensional set of inputs. All multi dimensional inputs
+/// must have the same shape.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' payoff strikes per share.
+/// * `trigger`: The options' trigger strikes per share.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the options.
+pub fn price_gap<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    s: A,
+    k: A,
+    trigger: A,
+    vol: A,
+    q: A,
+    r: F,
+    t: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let (d1, d2) = d1_d2(s, trigger, vol, q, r, t);
+    let asset = s.as_ref() * math::exp(math::neg(q.as_ref()) * t);
+    let cash = k.as_ref() * (-r * t).exp();
+    let n01 = |x: &ag::Tensor<'graph, F>| normal::cdf(x, F::zero(), F::one());
+    match ty {
+        OptionType::Call => asset * n01(&d1) - cash * n01(&d2),
+        OptionType::Put => cash * n01(&math::neg(d2)) - asset * n01(&math::neg(d1)),
+    }
+}
@@ -0,0 +1,135 @@
+use autograd as ag;
+use autograd::tensor_ops as math;
+
+use crate::models::gbm::simulate_gbm_paths;
+use crate::options::model::*;
+use crate::options::monte_carlo::MonteCarloEstimate;
+use crate::stats::normal;
+
+use autograd::rand::Rng;
+
+/// Calculate the price of european floating strike lookback options with continuous
+/// monitoring using the reflection principle formulas of Goldman, Sosin and Gatto
+/// (1979), as given by Hull.
+///
+/// A call pays `S_T - min S` and a put `max S - S_T`, with the extreme taken over
+/// the option's life. The floating strike variants have closed forms; the fixed
+/// strike variants are priced by simulation in `price_fixed_lookback_mc`.
+///
+/// The formulas divide by the cost of carry `r - q`, so the rate and the dividend
+/// yield must differ.
+///
+/// This function can price multiple options at once by inputing
+/// a multidimensional set of inputs. All multi dimensional inputs
+/// must have the same shape.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stocks' prices per share.
+/// * `extreme`: The lowest price so far for calls or the highest for puts, equal to
+///   `s` for a new option.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the options.
+pub fn price_floating_lookback<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    s: A,
+    extreme: A,
+    vol: A,
+    q: A,
+    r: F,
+    t: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let s = s.as_ref();
+    let extreme = extreme.as_ref();
+    let vol = vol.as_ref();
+    let q = q.as_ref();
+
+    let one = F::one();
+    let zero = F::zero();
+    let two = F::from(2_f64).unwrap();
+    let half = F::from(0.5_f64).unwrap();
+
+    let vol_sqrt_t = vol * t.sqrt();
+    let variance = math::square(vol);
+    let carry = math::neg(q) + r;
+    // The weight σ² / 2(r - q) of the reflected terms.
+    let reflection = variance / (carry * two);
+    let dividend_discount = math::exp(math::neg(q) * t);
+    let n = |x: &ag::Tensor<'graph, F>| normal::cdf(x, zero, one);
+
+    match ty {
+        OptionType::Call => {
+            let log_ratio = math::ln(s / extreme);
+            let a1 = (log_ratio + (carry + variance * half) * t) / vol_sqrt_t;
+            let a2 = a1 - vol_sqrt_t;
+            let a3 = (log_ratio + (math::neg(carry) + variance * half) * t) / vol_sqrt_t;
+            let y1 = math::neg((carry - variance * half) * two * log_ratio / variance);
+            s * dividend_discount * (n(&a1) - reflection * n(&math::neg(a1)))
+                - extreme * (n(&a2) - reflection * math::exp(y1) * n(&math::neg(a3))) * (-r * t).exp()
+        }
+        OptionType::Put => {
+            let log_ratio = math::ln(extreme / s);
+            let b1 = (log_ratio + (math::neg(carry) + variance * half) * t) / vol_sqrt_t;
+            let b2 = b1 - vol_sqrt_t;
+            let b3 = (log_ratio + (carry - variance * half) * t) / vol_sqrt_t;
+            let y2 = (carry - variance * half) * two * log_ratio / variance;
+            extreme * (n(&b1) - reflection * math::exp(y2) * n(&math::neg(b3))) * (-r * t).exp()
+                + s * dividend_discount * (reflection * n(&math::neg(b2)) - n(&b2))
+        }
+    }
+}
+
+/// Estimate the price of european fixed strike lookback options by Monte Carlo
+/// simulation of geometric brownian motion paths.
+///
+/// A call pays `max(max S - K, 0)` and a put `max(K - min S, 0)`, with the extreme
+/// tracked along each path over the initial price and the `n_steps` equally spaced
+/// monitoring dates. Discrete monitoring misses the extremes between the dates, so
+/// the estimate sits below the continuously monitored price by an amount that
+/// shrinks with `√(t / n_steps)`.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The option's strike price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n_steps`: The number of monitoring dates on each path.
+/// * `n_paths`: The number of simulated paths.
+/// * `rng`: The source of randomness for the simulated paths.
+///
+/// * `estimate`: The discounted mean payoff and its standard error, both NaN when
+///   `n_paths` is zero.
+pub fn price_fixed_lookback_mc<F: ag::Float, R: Rng>(
+    ty: OptionType,
+    s: F,
+    k: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    n_steps: usize,
+    n_paths: usize,
+    rng: &mut R,
+) -> MonteCarloEstimate<F> {
+    let paths = simulate_gbm_paths(s, vol, q, r, t, n_steps, n_paths, false, rng);
+    let discount = (-r * t).exp();
+    let samples = paths
+        .outer_iter()
+        .map(|path| {
+            let extreme = match ty {
+                OptionType::Call => path.iter().fold(s, |max, &x| max.max(x)),
+                OptionType::Put => path.iter().fold(s, |min, &x| min.min(x)),
+            };
+            discount * ty.payoff(extreme, k)
+        })
+        .collect::<Vec<_>>();
+    MonteCarloEstimate::from_samples(&samples)
+}
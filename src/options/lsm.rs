@@ -0,0 +1,94 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+use crate::models::gbm::simulate_gbm_paths;
+use crate::numerics::linalg::least_squares;
+use crate::options::model::*;
+use crate::options::monte_carlo::MonteCarloEstimate;
+
+use autograd::rand::Rng;
+
+/// Estimate the price of an american option with the Longstaff-Schwartz least
+/// squares Monte Carlo method.
+///
+/// Paths are simulated with `simulate_gbm_paths` and each of the `n_steps`
+/// simulation dates is an exercise date. Working backwards, the discounted
+/// future cash flows of the in the money paths are regressed on the polynomial
+/// basis `1, x, ..., x^degree` of the moneyness `x = s / k`, and a path exercises
+/// whenever its immediate payoff beats the fitted continuation value.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The option's strike price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n_steps`: The number of exercise dates.
+/// * `n_paths`: The number of simulated paths.
+/// * `degree`: The degree of the regression polynomial.
+/// * `rng`: The source of randomness for the simulated paths.
+///
+/// * `estimate`: The discounted mean cash flow and its standard error, or
+///   `QuantError::InvalidInput` when `n_steps` is zero.
+pub fn price_american_lsm<F: ag::Float, R: Rng>(
+    ty: OptionType,
+    s: F,
+    k: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    n_steps: usize,
+    n_paths: usize,
+    degree: usize,
+    rng: &mut R,
+) -> Result<MonteCarloEstimate<F>, QuantError> {
+    if n_steps == 0 {
+        return Err(QuantError::InvalidInput("need at least one exercise date".to_string()));
+    }
+    let paths = simulate_gbm_paths(s, vol, q, r, t, n_steps, n_paths, false, rng);
+    let discount = (-r * t / F::from(n_steps).unwrap()).exp();
+    let payoff = |st: F| ty.payoff(st, k);
+
+    let mut cash_flows = (0..n_paths)
+        .map(|i| payoff(paths[[i, n_steps]]))
+        .collect::<Vec<_>>();
+
+    for j in (1..n_steps).rev() {
+        cash_flows.iter_mut().for_each(|cash_flow| *cash_flow = *cash_flow * discount);
+
+        let in_the_money = (0..n_paths)
+            .filter(|&i| payoff(paths[[i, j]]) > F::zero())
+            .collect::<Vec<_>>();
+        if in_the_money.len() <= degree {
+            continue;
+        }
+
+        let basis = nd::Array2::from_shape_fn((in_the_money.len(), degree + 1), |(row, power)| {
+            (paths[[in_the_money[row], j]] / k).powi(power as i32)
+        });
+        let observed = nd::Array1::from_shape_fn(in_the_money.len(), |row| cash_flows[in_the_money[row]]);
+        let beta = match least_squares(&basis, &observed) {
+            Some(beta) => beta,
+            None => continue,
+        };
+
+        let continuation = basis.dot(&beta);
+        for (row, &i) in in_the_money.iter().enumerate() {
+            let exercise = payoff(paths[[i, j]]);
+            if exercise > continuation[row] {
+                cash_flows[i] = exercise;
+            }
+        }
+    }
+    cash_flows.iter_mut().for_each(|cash_flow| *cash_flow = *cash_flow * discount);
+
+    let estimate = MonteCarloEstimate::from_samples(&cash_flows);
+    // The holder can always exercise immediately.
+    Ok(MonteCarloEstimate {
+        price: estimate.price.max(payoff(s)),
+        std_error: estimate.std_error,
+    })
+}
@@ -1,4 +1,22 @@
+pub mod asian;
+pub mod bachelier;
+pub mod barrier;
+pub mod basket;
+pub mod binary;
 pub mod binomial;
+pub mod black76;
 pub mod black_scholes;
+pub mod compound;
+pub mod contract;
+pub mod exchange;
+pub mod gap;
+pub mod lookback;
+pub mod lsm;
 pub mod model;
-pub mod monte_carlo;
\ No newline at end of file
+pub mod monte_carlo;
+pub mod power;
+pub mod quanto;
+pub mod simple;
+pub mod strategy;
+pub mod trinomial;
+pub mod vol_surface;
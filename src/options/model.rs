@@ -1,12 +1,136 @@
 use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
 
+use crate::error::QuantError;
+use crate::fixed_income::curve::DiscountCurve;
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OptionType {
     Call,
     Put,
 }
 
+impl OptionType {
+    /// The value of exercising the option, `max(s - k, 0)` for a call and
+    /// `max(k - s, 0)` for a put.
+    ///
+    /// * `s`: The underlying stock's price per share.
+    /// * `k`: The option's strike price per share.
+    pub fn payoff<F: ag::Float>(&self, s: F, k: F) -> F {
+        match self {
+            OptionType::Call => (s - k).max(F::zero()),
+            OptionType::Put => (k - s).max(F::zero()),
+        }
+    }
+}
+
+/// When an option can be exercised: only at maturity, or at any time until it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Exercise {
+    European,
+    American,
+}
+
+/// The sensitivities of a batch of options' prices, each with the same shape
+/// as the batch.
+///
+/// * `delta`: ∂V/∂S.
+/// * `gamma`: ∂²V/∂S².
+/// * `vega`: ∂V/∂σ.
+/// * `theta`: -∂V/∂t.
+/// * `rho`: ∂V/∂r.
+#[derive(Copy, Clone)]
+pub struct Greeks<'graph, F: ag::Float> {
+    pub delta: ag::Tensor<'graph, F>,
+    pub gamma: ag::Tensor<'graph, F>,
+    pub vega: ag::Tensor<'graph, F>,
+    pub theta: ag::Tensor<'graph, F>,
+    pub rho: ag::Tensor<'graph, F>,
+}
+
+/// Diagnostics of an implied volatility fit, each with the same shape as the
+/// batch of target prices.
+///
+/// * `loss`: The absolute error between the model price at the implied volatility
+///   and the target price.
+/// * `converged`: Whether `loss` is within the requested tolerance.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "F: serde::Serialize", deserialize = "F: serde::Deserialize<'de>"))
+)]
+pub struct ImpliedVolReport<F: ag::Float> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+    pub loss: ag::NdArray<F>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+    pub converged: nd::ArrayD<bool>,
+}
+
+/// When an iterative fit such as an Adam loop stops.
+///
+/// * `max_epochs`: The most epochs to run.
+/// * `tolerance`: Stop once the mean loss of an epoch is below this, if set.
+/// * `patience`: Stop once this many epochs in a row fail to improve on the best
+///   mean loss, if set.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EarlyStopping<F> {
+    pub max_epochs: usize,
+    pub tolerance: Option<F>,
+    pub patience: Option<usize>,
+}
+
+impl<F: ag::Float> Default for EarlyStopping<F> {
+    /// A fixed 1000 epochs.
+    fn default() -> Self {
+        EarlyStopping {
+            max_epochs: 1000,
+            tolerance: None,
+            patience: None,
+        }
+    }
+}
+
+impl<F: ag::Float> EarlyStopping<F> {
+    /// Start tracking the losses of a fit.
+    pub(crate) fn monitor(&self) -> LossMonitor<F> {
+        LossMonitor {
+            tolerance: self.tolerance,
+            patience: self.patience,
+            best: F::infinity(),
+            since_best: 0,
+        }
+    }
+}
+
+/// Tracks the mean loss of each epoch against the `EarlyStopping` criteria.
+pub(crate) struct LossMonitor<F> {
+    tolerance: Option<F>,
+    patience: Option<usize>,
+    best: F,
+    since_best: usize,
+}
+
+impl<F: ag::Float> LossMonitor<F> {
+    /// Record the mean loss of an epoch and decide whether the fit should stop.
+    pub(crate) fn should_stop(&mut self, loss: F) -> bool {
+        if self.tolerance.map_or(false, |tol| loss < tol) {
+            return true;
+        }
+        if loss < self.best {
+            self.best = loss;
+            self.since_best = 0;
+        } else {
+            self.since_best += 1;
+        }
+        self.patience.map_or(false, |patience| self.since_best >= patience)
+    }
+}
+
 pub trait OptionPricingModel {
     /// Calculate the price of an option based on the
     /// model's pricing solution.
@@ -20,13 +144,44 @@ pub trait OptionPricingModel {
     /// * `k`: The options' strike prices per share.
     /// * `vol`: The volatility of the stocks in decimal.
     /// * `r`: The risk free interest rate as decimal.
-    /// * `q`: The divided of the stock per year as decimal.
+    /// * `q`: The continuous dividend yield of the stock per year as decimal.
     /// * `t`: The time until option maturity as decimal of a year.
     ///
     /// * `prices`: The price of the options.
     fn price<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F> 
     where
         A: AsRef<ag::Tensor<'graph, F>> + Copy;
+
+    /// Calculate the price of an option based on the model's pricing solution,
+    /// discounting off a term structure of rates rather than a constant rate.
+    ///
+    /// With deterministic rates only the discounting to maturity matters, so the
+    /// price is the model's price at the curve's zero rate to `t`. A `FlatCurve`
+    /// reproduces `price` at its rate exactly.
+    ///
+    /// * `ty`: The type of the option, `Call` or `Put`.
+    /// * `s`: The underlying stocks' prices per share.
+    /// * `k`: The options' strike prices per share.
+    /// * `vol`: The volatility of the stocks in decimal.
+    /// * `q`: The continuous dividend yield of the stock per year as decimal.
+    /// * `curve`: The risk free discount curve.
+    /// * `t`: The time until option maturity as decimal of a year.
+    ///
+    /// * `prices`: The price of the options.
+    fn price_with_curve<'graph, A, F: ag::Float, C: DiscountCurve<F>>(
+        ty: OptionType,
+        s: A,
+        k: A,
+        vol: A,
+        q: A,
+        curve: &C,
+        t: F,
+    ) -> ag::Tensor<'graph, F>
+    where
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
+    {
+        Self::price(ty, s, k, vol, q, curve.zero_rate(t), t)
+    }
     
     /// Calculate the implied volatility based on the
     /// model's pricing solution.
@@ -40,10 +195,11 @@ pub trait OptionPricingModel {
     /// * `s`: The underlying stocks' prices per share.
     /// * `k`: The options' strike prices per share.
     /// * `r`: The risk free interest rate as decimal.
-    /// * `q`: The divided of the stock per year as decimal.
+    /// * `q`: The continuous dividend yield of the stock per year as decimal.
     /// * `t`: The time until option maturity as decimal of a year.
     ///
-    /// * `volatility`: The implied volatility of the options.
+    /// * `volatility`: The implied volatility of the options, or
+    ///   `QuantError::ShapeMismatch` when the inputs' shapes differ.
     fn implied_volatility<F: ag::Float>(
         ty: OptionType, 
         p: ag::NdArrayView<F>,
@@ -52,7 +208,48 @@ pub trait OptionPricingModel {
         q: ag::NdArrayView<F>,
         r: F,
         t: F,
-    ) -> ag::NdArray<F>;
+    ) -> Result<ag::NdArray<F>, QuantError>;
+
+    /// Calculate the implied volatility based on the model's pricing solution,
+    /// along with per option diagnostics of how well the fit reprices the targets.
+    ///
+    /// Options whose report is not `converged` should be discarded when fitting
+    /// a surface.
+    ///
+    /// * `ty`: The type of the option, `Call` or `Put`.
+    /// * `p`: The price of the options.
+    /// * `s`: The underlying stocks' prices per share.
+    /// * `k`: The options' strike prices per share.
+    /// * `r`: The risk free interest rate as decimal.
+    /// * `q`: The continuous dividend yield of the stock per year as decimal.
+    /// * `t`: The time until option maturity as decimal of a year.
+    /// * `tol`: The largest absolute pricing error considered converged.
+    ///
+    /// * `(volatility, report)`: The implied volatility of the options and the fit diagnostics,
+    ///   or `QuantError::ShapeMismatch` when the inputs' shapes differ.
+    fn implied_volatility_with_report<F: ag::Float>(
+        ty: OptionType,
+        p: ag::NdArrayView<F>,
+        s: ag::NdArrayView<F>,
+        k: ag::NdArrayView<F>,
+        q: ag::NdArrayView<F>,
+        r: F,
+        t: F,
+        tol: F,
+    ) -> Result<(ag::NdArray<F>, ImpliedVolReport<F>), QuantError> {
+        let vol = Self::implied_volatility(ty, p.view(), s.view(), k.view(), q.view(), r, t)?;
+        let price = ag::run(|ctx: &mut ag::Context<F>| {
+            let spot = math::convert_to_tensor(s.to_owned(), ctx);
+            let strike = math::convert_to_tensor(k.to_owned(), ctx);
+            let sigma = math::convert_to_tensor(vol.clone(), ctx);
+            let dividends = math::convert_to_tensor(q.to_owned(), ctx);
+            Self::price(ty, &spot, &strike, &sigma, &dividends, r, t).eval(ctx).unwrap()
+        });
+
+        let loss = (&price - &p).mapv(|e| e.abs());
+        let converged = loss.mapv(|e| e < tol);
+        Ok((vol, ImpliedVolReport { loss, converged }))
+    }
 
     /// Calculate the `delta` e.g. change in option price per change in underlying
     /// stock price.
@@ -66,7 +263,7 @@ pub trait OptionPricingModel {
     /// * `k`: The options' strike prices per share.
     /// * `vol`: The volatility of the stocks in decimal.
     /// * `r`: The risk free interest rate as decimal.
-    /// * `q`: The divided of the stock per year as decimal.
+    /// * `q`: The continuous dividend yield of the stock per year as decimal.
     /// * `t`: The time until option maturity as decimal of a year.
     ///
     /// * `delta`: The change in option value per change in underlying stock price.
@@ -86,7 +283,7 @@ pub trait OptionPricingModel {
     /// * `k`: The options' strike prices per share.
     /// * `vol`: The volatility of the stocks in decimal.
     /// * `r`: The risk free interest rate as decimal.
-    /// * `q`: The divided of the stock per year as decimal.
+    /// * `q`: The continuous dividend yield of the stock per year as decimal.
     /// * `t`: The time until option maturity as decimal of a year.
     ///
     /// * `theta`: The change in option value per change in time to experiation.
@@ -106,7 +303,7 @@ pub trait OptionPricingModel {
     /// * `k`: The options' strike prices per share.
     /// * `vol`: The volatility of the stocks in decimal.
     /// * `r`: The risk free interest rate as decimal.
-    /// * `q`: The divided of the stock per year as decimal.
+    /// * `q`: The continuous dividend yield of the stock per year as decimal.
     /// * `t`: The time until option maturity as decimal of a year.
     ///
     /// * `gamma`: The change in option `delta` per change in time to expiration.
@@ -126,11 +323,56 @@ pub trait OptionPricingModel {
     /// * `k`: The options' strike prices per share.
     /// * `vol`: The volatility of the stocks in decimal.
     /// * `r`: The risk free interest rate as decimal.
-    /// * `q`: The divided of the stock per year as decimal.
+    /// * `q`: The continuous dividend yield of the stock per year as decimal.
     /// * `t`: The time until option maturity as decimal of a year.
     ///
     /// * `vega`: The change in option price per change in volatility.
     fn vega<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F> 
     where
         A: AsRef<ag::Tensor<'graph, F>> + Copy;
-}
\ No newline at end of file
+
+    /// Calculate the `rho` e.g. change in option price per
+    /// change in the risk free interest rate.
+    /// 
+    /// This function can price multiple options at once by inputing
+    /// a multidimensional set of inputs. All multi dimensional inputs
+    /// must have the same shape.
+    ///
+    /// * `ty`: The type of the option, `Call` or `Put`.
+    /// * `s`: The underlying stocks' prices per share.
+    /// * `k`: The options' strike prices per share.
+    /// * `vol`: The volatility of the stocks in decimal.
+    /// * `r`: The risk free interest rate as decimal.
+    /// * `q`: The continuous dividend yield of the stock per year as decimal.
+    /// * `t`: The time until option maturity as decimal of a year.
+    ///
+    /// * `rho`: The change in option price per change in the risk free interest rate.
+    fn rho<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F> 
+    where
+        A: AsRef<ag::Tensor<'graph, F>> + Copy;
+}
+
+/// The central difference `(f(h) - f(-h)) / 2h` of a price graph rebuilt at bumped
+/// inputs, for the Greeks of pricers autograd cannot differentiate through.
+///
+/// * `h`: The bump of the input.
+/// * `f`: The price graph with the input bumped by its argument.
+pub(crate) fn central_difference<'graph, P, F: ag::Float>(h: F, f: P) -> ag::Tensor<'graph, F>
+where
+    P: Fn(F) -> ag::Tensor<'graph, F>,
+{
+    (f(h) - f(-h)) * (F::one() / (h + h))
+}
+
+/// The second central difference `(f(h) - 2f(0) + f(-h)) / h²` of a price graph
+/// rebuilt at bumped inputs, with a bump per option so it can scale with the input.
+///
+/// * `h`: The bump of each option's input.
+/// * `f`: The price graph with the input bumped by its argument.
+pub(crate) fn second_difference<'graph, P, F: ag::Float>(h: &ag::Tensor<'graph, F>, f: P) -> ag::Tensor<'graph, F>
+where
+    P: Fn(&ag::Tensor<'graph, F>) -> ag::Tensor<'graph, F>,
+{
+    let two = F::from(2f64).unwrap();
+    (f(h) - f(&(*h * F::zero())) * two + f(&math::neg(h))) / math::square(h)
+}
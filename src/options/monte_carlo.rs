@@ -3,15 +3,49 @@ use autograd::array_gen as gen;
 use autograd::ndarray as nd;
 use autograd::tensor_ops as math;
 
+use crate::error::QuantError;
+use crate::numerics::optimize::run_epochs;
 use crate::options::model::*;
+use crate::stats::normal;
 use autograd::prelude::*;
+#[cfg(feature = "rayon")]
 use autograd::rayon::prelude::*;
 
-use autograd::rand::{thread_rng, distributions::Distribution};
-use autograd::statrs::distribution::{Continuous, Normal};
+use autograd::rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+/// The number of paths `price_european_mc` simulates from each random stream.
+const PATHS_PER_STREAM: usize = 4096;
 
 pub struct MonteCarloPricingModel;
 
+/// A Monte Carlo price along with the standard error of the estimate so callers
+/// can judge whether enough paths were simulated.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonteCarloEstimate<F: ag::Float> {
+    pub price: F,
+    pub std_error: F,
+}
+
+impl<F: ag::Float> MonteCarloEstimate<F> {
+    /// Estimate the price as the mean of independent discounted payoff samples,
+    /// with the standard error taken from their sample variance.
+    ///
+    /// An empty slice has no mean, so both the price and the standard error are NaN.
+    pub fn from_samples(samples: &[F]) -> Self {
+        let n = F::from(samples.len()).unwrap();
+        let mean = samples.iter().fold(F::zero(), |acc, &x| acc + x) / n;
+        let var = samples
+            .iter()
+            .fold(F::zero(), |acc, &x| acc + (x - mean).powi(2))
+            / (n - F::one()).max(F::one());
+        MonteCarloEstimate {
+            price: mean,
+            std_error: (var / n).sqrt(),
+        }
+    }
+}
+
 impl OptionPricingModel for MonteCarloPricingModel {
     fn price<'graph, A, F: ag::Float>(
         ty: OptionType,
@@ -25,39 +59,7 @@ impl OptionPricingModel for MonteCarloPricingModel {
     where
         A: AsRef<ag::Tensor<'graph, F>> + Copy,
     {
-        let dim: i32 = -1;
-        let s = s.as_ref().expand_dims(&[dim]);
-        let k = k.as_ref().expand_dims(&[dim]);
-        let vol = vol.as_ref().expand_dims(&[dim]);
-        let q = q.as_ref().expand_dims(&[dim]);
-        let rs = (s / s) * r;
-        let ts = (s / s) * t;
-
-        let packed = math::concat(&[s, k, vol, q, rs, ts], 0);
-        match ty {
-            OptionType::Call => packed.map(|packed| {
-                packed.map_axis(nd::Axis(0), |col| {
-                    let s = col[0];
-                    let k = col[1];
-                    let vol = col[2];
-                    let q = col[3];
-                    let r = col[4];
-                    let t = col[5];
-                    eval_one_call(s, k, vol, q, r, t, 500)
-                })
-            }),
-            OptionType::Put => packed.map(|packed| {
-                packed.map_axis(nd::Axis(0), |col| {
-                    let s = col[0];
-                    let k = col[1];
-                    let vol = col[2];
-                    let q = col[3];
-                    let r = col[4];
-                    let t = col[5];
-                    eval_one_put(s, k, vol, q, r, t, 500)
-                })
-            }),
-        }
+        price_with_seed(ty, s, k, vol, q, r, t, None)
     }
 
     fn implied_volatility<F: ag::Float>(
@@ -68,7 +70,10 @@ impl OptionPricingModel for MonteCarloPricingModel {
         q: ag::NdArrayView<F>,
         r: F,
         t: F,
-    ) -> ag::NdArray<F> {
+    ) -> Result<ag::NdArray<F>, QuantError> {
+        QuantError::check_shape(p.shape(), s.shape())?;
+        QuantError::check_shape(p.shape(), k.shape())?;
+        QuantError::check_shape(p.shape(), q.shape())?;
         let mut env = ag::VariableEnvironment::new();
         let ret_id = env.name("vol").set(gen::ones(p.shape()));
         let adam = ag::optimizers::adam::Adam::default(
@@ -76,164 +81,375 @@ impl OptionPricingModel for MonteCarloPricingModel {
             env.default_namespace().current_var_ids(),
             &mut env,
         );
-        for _ in 0..1000 {
-            env.run(|ctx| {
-                let vol = ctx.variable("vol");
-                let price = ctx.placeholder("p", &[-1]);
-                let spot = ctx.placeholder("s", &[-1]);
-                let strike = ctx.placeholder("k", &[-1]);
-                let dividends = ctx.placeholder("q", &[-1]);
-
-                let h = F::from(0.05_f64).unwrap();
-
-                let m: i32 = 1;
-                let n: i32 = 2;
-
-                let losses = (-n / 2..n / 2 + 1)
-                    .map(|i| {
-                        let voli = vol + (h * F::from(i).unwrap());
-                        let pred = MonteCarloPricingModel::price(
-                            ty, &spot, &strike, &voli, &dividends, r, t,
-                        );
-                        math::abs(price - pred)
-                    })
-                    .collect::<Vec<_>>();
-                let grad = math::finite_difference(m as usize, n as usize, h, &losses[..]);
-
-                let mut feeder = ag::Feeder::new();
-                feeder
-                    .push(price, p.view())
-                    .push(spot, s.view())
-                    .push(strike, k.view())
-                    .push(dividends, q.view());
-
-                adam.update(&[vol], &[grad], ctx, feeder);
-            });
-        }
-        env.get_array_by_id(ret_id).unwrap().clone().into_inner()
+        env.run(|ctx| {
+            let vol = ctx.variable("vol");
+            let price = ctx.placeholder("p", &[-1]);
+            let spot = ctx.placeholder("s", &[-1]);
+            let strike = ctx.placeholder("k", &[-1]);
+            let dividends = ctx.placeholder("q", &[-1]);
+
+            let h = F::from(0.05_f64).unwrap();
+
+            let m: i32 = 1;
+            let n: i32 = 2;
+
+            let losses = (-n / 2..n / 2 + 1)
+                .map(|i| {
+                    let voli = vol + (h * F::from(i).unwrap());
+                    let pred = MonteCarloPricingModel::price(
+                        ty, &spot, &strike, &voli, &dividends, r, t,
+                    );
+                    math::abs(price - pred)
+                })
+                .collect::<Vec<_>>();
+            let grad = math::finite_difference(m as usize, n as usize, h, &losses[..]);
+            let update = adam.get_update_op(&[vol], &[grad], ctx);
+
+            let feeds = [(price, p.view()), (spot, s.view()), (strike, k.view()), (dividends, q.view())];
+            run_epochs(ctx, &update, &feeds, 1000);
+        });
+        Ok(env.get_array_by_id(ret_id).unwrap().clone().into_inner())
     }
 
-    fn delta<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F> 
+    fn delta<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
     where
-        A: AsRef<ag::Tensor<'graph, F>> + Copy 
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
     {
-        let stock_price = s.as_ref();
-
-        let m: i32 = 1;
-        let n: i32 = 2;
-        // Hacky way to get a scalar tensor.
+        // Every bumped price reuses one seed so the stencil differences common random numbers.
+        let seed = thread_rng().gen();
         let h = F::from(0.05_f64).unwrap();
-
-        let stencil_points = (-n / 2..n / 2 + 1)
-            .map(|i| {
-                let stock_price_i = stock_price + (h * F::from(i).unwrap());
-                let pred = MonteCarloPricingModel::price(
-                    ty, stock_price_i.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r, t,
-                );
-                pred
-            })
-            .collect::<Vec<_>>();
-        let grad = math::finite_difference(m as usize, n as usize, h, &stencil_points[..]);
-        grad
+        central_difference(h, |bump| {
+            price_with_seed(ty, &(s.as_ref() + bump), k.as_ref(), vol.as_ref(), q.as_ref(), r, t, Some(seed))
+        })
     }
 
     fn theta<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
     where
-        A: AsRef<ag::Tensor<'graph, F>> + Copy
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
     {
-        let m: i32 = 1;
-        let n: i32 = 2;
-        // Hacky way to get a scalar tensor.
-        let h =  F::from(0.05_f64).unwrap();
-
-        let stencil_points = (-n / 2..n / 2 + 1)
-            .map(|i| {
-                let ti = t - (F::from(i).unwrap() * F::from(0.05_f64).unwrap());
-                let pred = MonteCarloPricingModel::price(
-                    ty, s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r, ti,
-                );
-                pred
-            })
-            .collect::<Vec<_>>();
-        let grad = math::finite_difference(m as usize, n as usize, h, &stencil_points[..]);
-        grad
+        // Every bumped price reuses one seed so the stencil differences common random numbers.
+        let seed = thread_rng().gen();
+        let h = F::from(0.05_f64).unwrap();
+        central_difference(h, |bump| {
+            price_with_seed(ty, s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r, t - bump, Some(seed))
+        })
     }
 
     fn gamma<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
     where
-        A: AsRef<ag::Tensor<'graph, F>> + Copy
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
     {
-        let m: i32 = 1;
-        let n: i32 = 2;
-        // Hacky way to get a scalar tensor.
-        let h = F::from(0.05_f64).unwrap();
-
-        let stencil_points = (-n / 2..n / 2 + 1)
-            .map(|i| {
-                let ti = t - (F::from(i).unwrap() * F::from(0.05_f64).unwrap());
-                let pred = MonteCarloPricingModel::delta(
-                    ty, s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r, ti,
-                );
-                pred
-            })
-            .collect::<Vec<_>>();
-        let grad = math::finite_difference(m as usize, n as usize, h, &stencil_points[..]);
-        grad
+        // Every bumped price reuses one seed so the stencil differences common random numbers.
+        let seed = thread_rng().gen();
+        let h = *s.as_ref() * F::from(0.05_f64).unwrap();
+        second_difference(&h, |bump| {
+            price_with_seed(ty, &(s.as_ref() + bump), k.as_ref(), vol.as_ref(), q.as_ref(), r, t, Some(seed))
+        })
     }
 
     fn vega<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
     where
-        A: AsRef<ag::Tensor<'graph, F>> + Copy
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
     {
-        let m: i32 = 1;
-        let n: i32 = 2;
-        // Hacky way to get a scalar tensor.
-        let h =  F::from(0.05_f64).unwrap();
-
-        let stencil_points = (-n / 2..n / 2 + 1)
-            .map(|i| {
-                let vol_i = vol.as_ref() + (h * F::from(i).unwrap());
-                let pred = MonteCarloPricingModel::price(
-                    ty, s.as_ref(), k.as_ref(), vol_i.as_ref(), q.as_ref(), r, t,
-                );
-                pred
-            })
-            .collect::<Vec<_>>();
-        let grad = math::finite_difference(m as usize, n as usize, h, &stencil_points[..]);
-        grad
+        // Every bumped price reuses one seed so the stencil differences common random numbers.
+        let seed = thread_rng().gen();
+        let h = F::from(0.05_f64).unwrap();
+        central_difference(h, |bump| {
+            price_with_seed(ty, s.as_ref(), k.as_ref(), &(vol.as_ref() + bump), q.as_ref(), r, t, Some(seed))
+        })
+    }
+
+    fn rho<'graph, A, F: ag::Float>(ty: OptionType, s: A, k: A, vol: A, q: A, r: F, t: F) -> ag::Tensor<'graph, F>
+    where
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
+    {
+        // Every bumped price reuses one seed so the stencil differences common random numbers.
+        let seed = thread_rng().gen();
+        // One basis point, small enough for the curvature in the rate not to matter.
+        let h = F::from(1e-4_f64).unwrap();
+        central_difference(h, |bump| {
+            price_with_seed(ty, s.as_ref(), k.as_ref(), vol.as_ref(), q.as_ref(), r + bump, t, Some(seed))
+        })
+    }
+}
+
+/// The model's price graph, simulating every option from `seed` when given so that
+/// prices at bumped inputs share their random numbers, or from a fresh seed per
+/// option otherwise.
+fn price_with_seed<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    s: A,
+    k: A,
+    vol: A,
+    q: A,
+    r: F,
+    t: F,
+    seed: Option<u32>,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let dim: i32 = -1;
+    let s = s.as_ref().expand_dims(&[dim]);
+    let k = k.as_ref().expand_dims(&[dim]);
+    let vol = vol.as_ref().expand_dims(&[dim]);
+    let q = q.as_ref().expand_dims(&[dim]);
+    let rs = s * F::zero() + r;
+    let ts = s * F::zero() + t;
+    // The seed rides along as a column, negative for a fresh seed per option.
+    let seeds = s * F::zero() + seed.map(|seed| F::from(seed).unwrap()).unwrap_or(-F::one());
+
+    let packed = math::concat(&[s, k, vol, q, rs, ts, seeds], 0);
+    match ty {
+        OptionType::Call => packed.map(|packed| packed.map_axis(nd::Axis(0), |col| eval_one(col, OptionType::Call))),
+        OptionType::Put => packed.map(|packed| packed.map_axis(nd::Axis(0), |col| eval_one(col, OptionType::Put))),
     }
 }
 
-fn eval_one_call<F: ag::Float>(s: F, k: F, vol: F, q: F, r: F, t: F, paths: usize) -> F {
-    eval_one(s, k, vol, q, r, t, paths, OptionType::Call)
+/// Price one packed option `[s, k, vol, q, r, t, seed]` from 500 paths.
+fn eval_one<F: ag::Float>(col: nd::ArrayView1<F>, ty: OptionType) -> F {
+    let seed = if col[6] < F::zero() {
+        thread_rng().gen()
+    } else {
+        col[6].to_u64().unwrap()
+    };
+    price_european_mc(ty, col[0], col[1], col[2], col[3], col[4], col[5], 500, seed).price
 }
 
-fn eval_one_put<F: ag::Float>(s: F, k: F, vol: F, q: F, r: F, t: F, paths: usize) -> F {
-    eval_one(s, k, vol, q, r, t, paths, OptionType::Put)
+/// Price a european option by Monte Carlo, sampling the terminal stock price of
+/// geometric brownian motion exactly
+///
+/// S_T = S e^{(r - q - σ²/2)T + σ√T Z}.
+///
+/// The paths are split into streams of a fixed size, each with its own random number
+/// generator seeded from `seed`. The streams are simulated in parallel with the
+/// `rayon` feature, and because the split does not depend on the number of threads
+/// the estimate is the same with or without it.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The option's strike price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n_paths`: The number of simulated paths.
+/// * `seed`: The master seed of the random streams.
+///
+/// * `estimate`: The discounted mean payoff and its standard error.
+pub fn price_european_mc<F: ag::Float>(
+    ty: OptionType,
+    s: F,
+    k: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    n_paths: usize,
+    seed: u64,
+) -> MonteCarloEstimate<F> {
+    let discount = (-r * t).exp();
+    simulate_terminal(s, vol, q, r, t, n_paths, seed, |st, _| discount * ty.payoff(st, k))
 }
 
-fn eval_one<F: ag::Float>(s: F, k: F, vol: F, q: F, r: F, t: F, paths: usize, ty: OptionType) -> F {
-    let dt: F = F::one() / F::from(365f64).unwrap();
-    let two = F::from(2_f64).unwrap();
-
-    (0..paths)
-        .into_par_iter()
-        .map(|_| {
-            let mut st = s;
-            let mut ts = F::zero();
-            let mut rng = thread_rng();
-            let normal = Normal::new(0., 1.).unwrap();
-            while ts < t {
-                let x = F::from(normal.sample(&mut rng)).unwrap();
-                let epsilon = F::one() / (F::one() + (-x).exp());
-                st = st * ((r - (vol.powi(2) / two)) * dt + (vol * epsilon * dt.sqrt()));
-                ts += dt;
-            }
-            match ty {
-                OptionType::Call => (-r * t).exp() * (st - k).max(F::zero()),
-                OptionType::Put => (-r * t).exp() * (k - st).max(F::zero()),
-            }
+/// Estimate the `delta` of a european call by Monte Carlo with the pathwise method
+///
+/// ∂/∂S e^{-rT} max(S_T - K, 0) = e^{-rT} 1{S_T > K} S_T / S,
+///
+/// which differentiates the discounted payoff along each simulated path instead of
+/// bumping the spot and repricing.
+///
+/// The pathwise method needs a payoff that is continuous in the parameter, like the
+/// call's, so that the derivative of the expectation is the expectation of the
+/// derivative. Payoffs with jumps such as digitals and barriers need the
+/// likelihood ratio method of `mc_delta_lr` instead.
+///
+/// The paths are those of `price_european_mc` with the same `n_paths` and `seed`.
+///
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The option's strike price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n_paths`: The number of simulated paths.
+/// * `seed`: The master seed of the random streams.
+///
+/// * `estimate`: The mean pathwise delta and its standard error.
+pub fn mc_call_delta<F: ag::Float>(
+    s: F,
+    k: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    n_paths: usize,
+    seed: u64,
+) -> MonteCarloEstimate<F> {
+    let discount = (-r * t).exp();
+    simulate_terminal(s, vol, q, r, t, n_paths, seed, |st, _| {
+        if st > k {
+            discount * st / s
+        } else {
+            F::zero()
+        }
+    })
+}
+
+/// Estimate the `vega` of a european call by Monte Carlo with the pathwise method
+///
+/// ∂/∂σ e^{-rT} max(S_T - K, 0) = e^{-rT} 1{S_T > K} S_T (√T Z - σT).
+///
+/// See `mc_call_delta` for when the pathwise method applies.
+///
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The option's strike price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n_paths`: The number of simulated paths.
+/// * `seed`: The master seed of the random streams.
+///
+/// * `estimate`: The mean pathwise vega and its standard error.
+pub fn mc_call_vega<F: ag::Float>(
+    s: F,
+    k: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    n_paths: usize,
+    seed: u64,
+) -> MonteCarloEstimate<F> {
+    let discount = (-r * t).exp();
+    let sqrt_t = t.sqrt();
+    simulate_terminal(s, vol, q, r, t, n_paths, seed, |st, z| {
+        if st > k {
+            discount * st * (sqrt_t * z - vol * t)
+        } else {
+            F::zero()
+        }
+    })
+}
+
+/// Estimate the `delta` of a european payoff by Monte Carlo with the likelihood ratio
+/// method
+///
+/// ∂/∂S E[e^{-rT} h(S_T)] = E[e^{-rT} h(S_T) Z / (Sσ√T)],
+///
+/// which differentiates the lognormal density of `S_T` instead of the payoff, so each
+/// path's discounted payoff is weighted by the score `∂ ln p(S_T) / ∂S`.
+///
+/// The payoff only needs to be integrable, so the method handles the jumps of
+/// digitals and barriers where the pathwise `mc_call_delta` fails. The price is a
+/// higher variance, which grows like `1 / (σ√T)` for short maturities or low vols, so
+/// prefer the pathwise estimators for continuous payoffs such as calls and puts.
+///
+/// The paths are those of `price_european_mc` with the same `n_paths` and `seed`.
+///
+/// * `payoff`: The payoff `h(S_T)` at maturity.
+/// * `s`: The underlying stock's price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n_paths`: The number of simulated paths.
+/// * `seed`: The master seed of the random streams.
+///
+/// * `estimate`: The mean likelihood ratio delta and its standard error.
+pub fn mc_delta_lr<F: ag::Float, P>(
+    payoff: P,
+    s: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    n_paths: usize,
+    seed: u64,
+) -> MonteCarloEstimate<F>
+where
+    P: Fn(F) -> F + Send + Sync,
+{
+    let discount = (-r * t).exp();
+    let scale = s * vol * t.sqrt();
+    simulate_terminal(s, vol, q, r, t, n_paths, seed, |st, z| discount * payoff(st) * z / scale)
+}
+
+/// Estimate the `vega` of a european payoff by Monte Carlo with the likelihood ratio
+/// method
+///
+/// ∂/∂σ E[e^{-rT} h(S_T)] = E[e^{-rT} h(S_T) ((Z² - 1) / σ - Z√T)].
+///
+/// See `mc_delta_lr` for when the likelihood ratio method applies.
+///
+/// * `payoff`: The payoff `h(S_T)` at maturity.
+/// * `s`: The underlying stock's price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n_paths`: The number of simulated paths.
+/// * `seed`: The master seed of the random streams.
+///
+/// * `estimate`: The mean likelihood ratio vega and its standard error.
+pub fn mc_vega_lr<F: ag::Float, P>(
+    payoff: P,
+    s: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    n_paths: usize,
+    seed: u64,
+) -> MonteCarloEstimate<F>
+where
+    P: Fn(F) -> F + Send + Sync,
+{
+    let discount = (-r * t).exp();
+    let sqrt_t = t.sqrt();
+    simulate_terminal(s, vol, q, r, t, n_paths, seed, |st, z| {
+        discount * payoff(st) * ((z * z - F::one()) / vol - z * sqrt_t)
+    })
+}
+
+/// Average `sample(S_T, Z)` over exactly sampled terminal stock prices, simulated in
+/// fixed size streams seeded from `seed`.
+fn simulate_terminal<F: ag::Float, G>(
+    s: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    n_paths: usize,
+    seed: u64,
+    sample: G,
+) -> MonteCarloEstimate<F>
+where
+    G: Fn(F, F) -> F + Send + Sync,
+{
+    let two = F::from(2f64).unwrap();
+    let drift = (r - q - vol * vol / two) * t;
+    let diffusion = vol * t.sqrt();
+
+    let mut master = StdRng::seed_from_u64(seed);
+    let streams = (0..(n_paths + PATHS_PER_STREAM - 1) / PATHS_PER_STREAM)
+        .map(|stream| {
+            let size = PATHS_PER_STREAM.min(n_paths - stream * PATHS_PER_STREAM);
+            (size, master.gen::<u64>())
         })
-        .reduce(|| F::zero(), |a, b| a + b)
-        / F::from(paths).unwrap()
+        .collect::<Vec<_>>();
+    let simulate = |&(size, seed): &(usize, u64)| {
+        let z = normal::sample(&[size], F::zero(), F::one(), &mut StdRng::seed_from_u64(seed));
+        z.iter()
+            .map(|&z| sample(s * (drift + diffusion * z).exp(), z))
+            .collect::<Vec<_>>()
+    };
+
+    #[cfg(feature = "rayon")]
+    let samples = streams.par_iter().map(simulate).collect::<Vec<_>>();
+    #[cfg(not(feature = "rayon"))]
+    let samples = streams.iter().map(simulate).collect::<Vec<_>>();
+
+    MonteCarloEstimate::from_samples(&samples.concat())
 }
@@ -0,0 +1,64 @@
+use autograd as ag;
+use autograd::tensor_ops as math;
+
+use crate::options::model::*;
+use crate::stats::normal;
+
+/// Calculate the price of european power options, which pay `max(S_T^n - K, 0)` for
+/// calls and `max(K - S_T^n, 0)` for puts.
+///
+/// Under geometric brownian motion `S_T^n` is lognormal with log variance `n²σ²T`
+/// and mean
+///
+/// F_n = Sⁿ e^{n(r - q - σ²/2)T + n²σ²T/2},
+///
+/// so the price is the Black formula on `F_n` with volatility `nσ`. With `n = 1` it
+/// is the Black-Scholes price.
+///
+/// This function can price multiple options at once by inputing
+/// a multidimensional set of inputs. All multi dimensional inputs
+/// must have the same shape.
+///
+/// * `ty`: The type of the option, `Call` or `Put`.
+/// * `s`: The underlying stocks' prices per share.
+/// * `k`: The options' strikes, in units of the powered price.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `n`: The positive power applied to the terminal price.
+///
+/// * `prices`: The price of the options.
+pub fn price_power<'graph, A, F: ag::Float>(
+    ty: OptionType,
+    s: A,
+    k: A,
+    vol: A,
+    q: A,
+    r: F,
+    t: F,
+    n: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let s = s.as_ref();
+    let k = k.as_ref();
+    let q = q.as_ref();
+    let half = F::from(0.5_f64).unwrap();
+
+    let variance = math::square(vol.as_ref());
+    let log_forward =
+        math::ln(s) * n + ((math::neg(q) + r) - variance * half) * (n * t) + variance * (n * n * t * half);
+    let forward = math::exp(log_forward);
+    let std = vol.as_ref() * (n * t.sqrt());
+    let d1 = (math::ln(forward / k) + math::square(std) * half) / std;
+    let d2 = d1 - std;
+
+    let n01 = |x: &ag::Tensor<'graph, F>| normal::cdf(x, F::zero(), F::one());
+    let undiscounted = match ty {
+        OptionType::Call => forward * n01(&d1) - k * n01(&d2),
+        OptionType::Put => k * n01(&math::neg(d2)) - forward * n01(&math::neg(d1)),
+    };
+    undiscounted * (-r * t).exp()
+}
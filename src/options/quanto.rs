@@ -0,0 +1,51 @@
+use autograd as ag;
+
+use crate::options::black_scholes::BlackScholesPricingModel;
+use crate::options::model::*;
+
+/// Calculate the price of european quanto calls, which pay `max(S_T - K, 0)` on a
+/// foreign stock converted to the domestic currency at a rate fixed in advance.
+///
+/// In the domestic risk neutral measure the foreign stock drifts at
+///
+/// μ = r_f - q - ρσ_S σ_X,
+///
+/// where the last term compensates the correlation between the stock and the
+/// exchange rate. The payoff is otherwise a Black-Scholes call, discounted at the
+/// domestic rate, so the price is the Black-Scholes price with the dividend yield
+/// `q + r_d - r_f + ρσ_S σ_X`. Multiply by the fixed conversion rate for payoffs
+/// that are not one domestic unit per foreign unit.
+///
+/// This function can price multiple options at once by inputing
+/// a multidimensional set of inputs. All multi dimensional inputs
+/// must have the same shape.
+///
+/// * `s`: The foreign stocks' prices per share, in the foreign currency.
+/// * `k`: The options' strikes, in the foreign currency.
+/// * `vol`: The volatility of the stocks in decimal.
+/// * `fx_vol`: The volatility of the exchange rate, in domestic per foreign units.
+/// * `rho`: The correlation between the stocks' and the exchange rate's returns.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r_domestic`: The domestic risk free interest rate as decimal.
+/// * `r_foreign`: The foreign risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+///
+/// * `prices`: The price of the options in the domestic currency.
+pub fn price_quanto_call<'graph, A, F: ag::Float>(
+    s: A,
+    k: A,
+    vol: A,
+    fx_vol: A,
+    rho: A,
+    q: A,
+    r_domestic: F,
+    r_foreign: F,
+    t: F,
+) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let adjustment = vol.as_ref() * fx_vol.as_ref() * rho.as_ref();
+    let adjusted_q = q.as_ref() + adjustment + (r_domestic - r_foreign);
+    BlackScholesPricingModel::price(OptionType::Call, s.as_ref(), k.as_ref(), vol.as_ref(), &adjusted_q, r_domestic, t)
+}
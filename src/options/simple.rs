@@ -0,0 +1,112 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use crate::options::black_scholes::*;
+use crate::options::model::*;
+
+/// The Greeks of a single option as plain numbers.
+///
+/// * `delta`: ∂V/∂S.
+/// * `gamma`: ∂²V/∂S².
+/// * `vega`: ∂V/∂σ.
+/// * `theta`: -∂V/∂t.
+/// * `rho`: ∂V/∂r.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScalarGreeks {
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    pub rho: f64,
+}
+
+/// The Black-Scholes price of a european call on a stock without dividends.
+///
+/// * `spot`: The underlying stock's price per share.
+/// * `strike`: The option's strike price per share.
+/// * `time`: The time until option maturity as decimal of a year.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `rate`: The risk free interest rate as decimal.
+pub fn bs_call_price(spot: f64, strike: f64, time: f64, vol: f64, rate: f64) -> f64 {
+    price(OptionType::Call, spot, strike, time, vol, rate)
+}
+
+/// The Black-Scholes price of a european put on a stock without dividends.
+///
+/// * `spot`: The underlying stock's price per share.
+/// * `strike`: The option's strike price per share.
+/// * `time`: The time until option maturity as decimal of a year.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `rate`: The risk free interest rate as decimal.
+pub fn bs_put_price(spot: f64, strike: f64, time: f64, vol: f64, rate: f64) -> f64 {
+    price(OptionType::Put, spot, strike, time, vol, rate)
+}
+
+/// The Black-Scholes Greeks of a european call on a stock without dividends.
+///
+/// * `spot`: The underlying stock's price per share.
+/// * `strike`: The option's strike price per share.
+/// * `time`: The time until option maturity as decimal of a year.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `rate`: The risk free interest rate as decimal.
+pub fn bs_call_greeks(spot: f64, strike: f64, time: f64, vol: f64, rate: f64) -> ScalarGreeks {
+    greeks(OptionType::Call, spot, strike, time, vol, rate)
+}
+
+/// The Black-Scholes Greeks of a european put on a stock without dividends.
+///
+/// * `spot`: The underlying stock's price per share.
+/// * `strike`: The option's strike price per share.
+/// * `time`: The time until option maturity as decimal of a year.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `rate`: The risk free interest rate as decimal.
+pub fn bs_put_greeks(spot: f64, strike: f64, time: f64, vol: f64, rate: f64) -> ScalarGreeks {
+    greeks(OptionType::Put, spot, strike, time, vol, rate)
+}
+
+fn price(ty: OptionType, spot: f64, strike: f64, time: f64, vol: f64, rate: f64) -> f64 {
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = scalar(spot, ctx);
+        let k = scalar(strike, ctx);
+        let sigma = scalar(vol, ctx);
+        let q = scalar(0., ctx);
+        BlackScholesPricingModel::price(ty, &s, &k, &sigma, &q, rate, time).eval(ctx).unwrap()[0]
+    })
+}
+
+fn greeks(ty: OptionType, spot: f64, strike: f64, time: f64, vol: f64, rate: f64) -> ScalarGreeks {
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = scalar(spot, ctx);
+        let k = scalar(strike, ctx);
+        let sigma = scalar(vol, ctx);
+        let q = scalar(0., ctx);
+        let greeks = match ty {
+            OptionType::Call => call_greeks(&s, &k, &sigma, &q, rate, time),
+            OptionType::Put => put_greeks(&s, &k, &sigma, &q, rate, time),
+        };
+        let values = ctx
+            .evaluator()
+            .push(&greeks.delta)
+            .push(&greeks.gamma)
+            .push(&greeks.vega)
+            .push(&greeks.theta)
+            .push(&greeks.rho)
+            .run()
+            .into_iter()
+            .map(|value| value.unwrap()[0])
+            .collect::<Vec<_>>();
+        ScalarGreeks {
+            delta: values[0],
+            gamma: values[1],
+            vega: values[2],
+            theta: values[3],
+            rho: values[4],
+        }
+    })
+}
+
+fn scalar<'graph>(x: f64, g: &'graph ag::Graph<f64>) -> ag::Tensor<'graph, f64> {
+    math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), g)
+}
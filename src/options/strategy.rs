@@ -0,0 +1,163 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::options::contract::OptionContract;
+use crate::options::model::*;
+
+/// A position in one option of a strategy.
+///
+/// * `contract`: The option held.
+/// * `quantity`: The number of options held, negative for a short position.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Leg<F> {
+    pub contract: OptionContract<F>,
+    pub quantity: F,
+}
+
+/// A combination of option positions on the same underlying, valued as a whole.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Strategy<F> {
+    pub legs: Vec<Leg<F>>,
+}
+
+impl<F: ag::Float> Strategy<F> {
+    /// Create a strategy from its legs.
+    pub fn new(legs: Vec<Leg<F>>) -> Self {
+        Strategy { legs }
+    }
+
+    /// Add `quantity` of `contract` to the strategy, negative to sell it.
+    pub fn with_leg(mut self, contract: OptionContract<F>, quantity: F) -> Self {
+        self.legs.push(Leg { contract, quantity });
+        self
+    }
+
+    /// A long call and a long put at the same strike, which profits from a large move
+    /// in either direction.
+    ///
+    /// * `strike`: The strike price of both options.
+    /// * `maturity`: The time until maturity as decimal of a year.
+    pub fn straddle(strike: F, maturity: F) -> Result<Self, QuantError> {
+        Self::strangle(strike, strike, maturity)
+    }
+
+    /// A long put and a long call above it, which profits from a move outside the
+    /// strikes and costs less than a straddle.
+    ///
+    /// * `put_strike`: The strike price of the put.
+    /// * `call_strike`: The strike price of the call, at least `put_strike`.
+    /// * `maturity`: The time until maturity as decimal of a year.
+    pub fn strangle(put_strike: F, call_strike: F, maturity: F) -> Result<Self, QuantError> {
+        ordered(&[put_strike, call_strike])?;
+        Ok(Strategy::new(Vec::new())
+            .with_leg(contract(OptionType::Put, put_strike, maturity)?, F::one())
+            .with_leg(contract(OptionType::Call, call_strike, maturity)?, F::one()))
+    }
+
+    /// A long call and a short call at a higher strike, which profits from a rise up
+    /// to the higher strike.
+    ///
+    /// * `low_strike`: The strike price of the long call.
+    /// * `high_strike`: The strike price of the short call, at least `low_strike`.
+    /// * `maturity`: The time until maturity as decimal of a year.
+    pub fn bull_call_spread(low_strike: F, high_strike: F, maturity: F) -> Result<Self, QuantError> {
+        ordered(&[low_strike, high_strike])?;
+        Ok(Strategy::new(Vec::new())
+            .with_leg(contract(OptionType::Call, low_strike, maturity)?, F::one())
+            .with_leg(contract(OptionType::Call, high_strike, maturity)?, -F::one()))
+    }
+
+    /// A short strangle between the middle strikes protected by a long strangle at the
+    /// outer strikes, which collects a premium while the underlying stays between the
+    /// middle strikes and caps the loss outside them.
+    ///
+    /// * `strikes`: The increasing strikes of the long put, short put, short call and
+    ///   long call.
+    /// * `maturity`: The time until maturity as decimal of a year.
+    pub fn iron_condor(strikes: [F; 4], maturity: F) -> Result<Self, QuantError> {
+        ordered(&strikes)?;
+        Ok(Strategy::new(Vec::new())
+            .with_leg(contract(OptionType::Put, strikes[0], maturity)?, F::one())
+            .with_leg(contract(OptionType::Put, strikes[1], maturity)?, -F::one())
+            .with_leg(contract(OptionType::Call, strikes[2], maturity)?, -F::one())
+            .with_leg(contract(OptionType::Call, strikes[3], maturity)?, F::one()))
+    }
+
+    /// The total payoff of the legs at expiry if the underlying ends at `spot`.
+    pub fn payoff_at(&self, spot: F) -> F {
+        self.legs.iter().fold(F::zero(), |acc, leg| {
+            acc + leg.quantity * leg.contract.ty.payoff(spot, leg.contract.strike)
+        })
+    }
+
+    /// Calculate the price of the strategy with the pricing model `M`, as the sum of
+    /// its legs' prices weighted by their quantities.
+    ///
+    /// * `s`: The underlying stocks' prices per share.
+    /// * `vol`: The volatility of the stocks in decimal, with the same shape as `s`.
+    /// * `r`: The risk free interest rate as decimal.
+    ///
+    /// * `prices`: The price of the strategy at each spot and volatility.
+    pub fn price<'graph, M, A>(&self, s: A, vol: A, r: F) -> ag::Tensor<'graph, F>
+    where
+        M: OptionPricingModel,
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
+    {
+        self.legs.iter().fold(*s.as_ref() * F::zero(), |acc, leg| {
+            acc + leg.contract.price::<M, A>(s, vol, r) * leg.quantity
+        })
+    }
+
+    /// Calculate the net Greeks of the strategy with the pricing model `M`, as the sum
+    /// of its legs' Greeks weighted by their quantities, so short legs count against
+    /// the long ones.
+    ///
+    /// * `s`: The underlying stocks' prices per share.
+    /// * `vol`: The volatility of the stocks in decimal, with the same shape as `s`.
+    /// * `r`: The risk free interest rate as decimal.
+    ///
+    /// * `greeks`: The net `Greeks` of the strategy at each spot and volatility.
+    pub fn greeks<'graph, M, A>(&self, s: A, vol: A, r: F) -> Greeks<'graph, F>
+    where
+        M: OptionPricingModel,
+        A: AsRef<ag::Tensor<'graph, F>> + Copy,
+    {
+        let zeros = *s.as_ref() * F::zero();
+        let net = Greeks {
+            delta: zeros,
+            gamma: zeros,
+            vega: zeros,
+            theta: zeros,
+            rho: zeros,
+        };
+        self.legs.iter().fold(net, |net, leg| {
+            let OptionContract { ty, strike, maturity: t, dividend_yield } = leg.contract;
+            let k = zeros + strike;
+            let q = zeros + dividend_yield.unwrap_or_else(F::zero);
+            let (s, vol, n) = (s.as_ref(), vol.as_ref(), leg.quantity);
+            Greeks {
+                delta: net.delta + M::delta(ty, s, &k, vol, &q, r, t) * n,
+                gamma: net.gamma + M::gamma(ty, s, &k, vol, &q, r, t) * n,
+                vega: net.vega + M::vega(ty, s, &k, vol, &q, r, t) * n,
+                theta: net.theta + M::theta(ty, s, &k, vol, &q, r, t) * n,
+                rho: net.rho + M::rho(ty, s, &k, vol, &q, r, t) * n,
+            }
+        })
+    }
+}
+
+/// Build a contract without dividends for a strategy's leg.
+fn contract<F: ag::Float>(ty: OptionType, strike: F, maturity: F) -> Result<OptionContract<F>, QuantError> {
+    OptionContract::builder(ty).strike(strike).maturity(maturity).build()
+}
+
+/// Check that a strategy's strikes are in non-decreasing order.
+fn ordered<F: ag::Float>(strikes: &[F]) -> Result<(), QuantError> {
+    if strikes.iter().zip(strikes.iter().skip(1)).all(|(a, b)| a <= b) {
+        Ok(())
+    } else {
+        Err(QuantError::InvalidInput("strategy strikes must be in increasing order".to_string()))
+    }
+}
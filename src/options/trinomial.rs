@@ -0,0 +1,69 @@
+use autograd as ag;
+
+use crate::options::model::*;
+
+/// Calculate the price of an option on a trinomial lattice in the log of the stock
+/// price.
+///
+/// Each step moves the log price up or down by `σ√(3Δt)` or leaves it unchanged, with
+///
+/// p_u = 1/6 + ν√(Δt / 12σ²), p_m = 2/3, p_d = 1/6 - ν√(Δt / 12σ²),
+///
+/// and ν = r - q - σ²/2, which match the first two moments of the log price. The
+/// middle move keeps a layer of nodes at a fixed price, so the price converges more
+/// smoothly in the number of steps than on the binomial lattice, and a grid aligned
+/// with a level, e.g. a barrier, stays aligned at every step. American options are
+/// checked for early exercise at every node. The probabilities stay positive as long
+/// as `|ν|√(3Δt) < σ`, so coarse lattices of high drift need more steps.
+///
+/// * `ty`: The type of option, either call or put.
+/// * `exercise`: The option's exercise style.
+/// * `s`: The underlying stock's price per share.
+/// * `k`: The option's strike price per share.
+/// * `vol`: The volatility of the stock in decimal.
+/// * `q`: The continuous dividend yield of the stock per year as decimal.
+/// * `r`: The risk free interest rate as decimal.
+/// * `t`: The time until option maturity as decimal of a year.
+/// * `steps`: The number of time steps in the lattice.
+///
+/// * `price`: The price of the option.
+pub fn price_trinomial<F: ag::Float>(
+    ty: OptionType,
+    exercise: Exercise,
+    s: F,
+    k: F,
+    vol: F,
+    q: F,
+    r: F,
+    t: F,
+    steps: usize,
+) -> F {
+    let n = steps.max(1);
+    let half = F::from(0.5f64).unwrap();
+    let dt = t / F::from(n).unwrap();
+    let dx = vol * (F::from(3f64).unwrap() * dt).sqrt();
+    let nu = r - q - half * vol * vol;
+    let p_middle = F::from(2f64 / 3f64).unwrap();
+    let p_up = F::from(1f64 / 6f64).unwrap() + nu * (dt / (F::from(12f64).unwrap() * vol * vol)).sqrt();
+    let p_down = F::one() - p_up - p_middle;
+    let decay = (-r * dt).exp();
+
+    // Node `j` of step `i` lies `j - i` moves above the spot.
+    let stock_price = |i: usize, j: usize| s * (dx * (F::from(j).unwrap() - F::from(i).unwrap())).exp();
+    let mut values = (0..2 * n + 1)
+        .map(|j| ty.payoff(stock_price(n, j), k))
+        .collect::<Vec<_>>();
+
+    for i in (0..n).rev() {
+        for j in 0..2 * i + 1 {
+            let expected = p_up * values[j + 2] + p_middle * values[j + 1] + p_down * values[j];
+            values[j] = match exercise {
+                Exercise::European => decay * expected,
+                Exercise::American => (decay * expected).max(ty.payoff(stock_price(i, j), k)),
+            };
+        }
+        values.truncate(2 * i + 1);
+    }
+
+    values[0]
+}
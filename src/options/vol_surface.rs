@@ -0,0 +1,225 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::numerics::interp::CubicSpline;
+
+/// A term structure of at the money volatility.
+pub trait VolCurve<F: ag::Float> {
+    /// The total variance `∫₀ᵗ σ(u)² du` accumulated up to time `t`, as decimal of a year.
+    fn variance_to(&self, t: F) -> F;
+
+    /// The root mean square vol `√(variance_to(t) / t)` to time `t`, the constant vol
+    /// that prices an option maturing at `t` off the curve. Times that are not
+    /// positive take the limit at `t = 0`.
+    fn rms_vol(&self, t: F) -> F {
+        let t = t.max(F::epsilon());
+        (self.variance_to(t).max(F::zero()) / t).sqrt()
+    }
+}
+
+/// A curve with the same vol at every maturity, the term structure behind the
+/// pricers' constant `vol`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlatVolCurve<F> {
+    pub vol: F,
+}
+
+impl<F: ag::Float> FlatVolCurve<F> {
+    /// Create a curve at the constant `vol` in decimal.
+    pub fn new(vol: F) -> Self {
+        FlatVolCurve { vol }
+    }
+}
+
+impl<F: ag::Float> VolCurve<F> for FlatVolCurve<F> {
+    fn variance_to(&self, t: F) -> F {
+        self.vol * self.vol * t
+    }
+
+    fn rms_vol(&self, _t: F) -> F {
+        self.vol
+    }
+}
+
+/// A vol term structure from implied vols at increasing maturities.
+///
+/// As in `VolSurface`, the total variance σ²T is interpolated linearly between
+/// pillars, so the forward vol is constant between them. Before the first pillar the
+/// first vol applies, and beyond the last pillar the forward vol of the last interval
+/// carries on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TermVolCurve<F> {
+    maturities: Vec<F>,
+    variances: Vec<F>,
+}
+
+impl<F: ag::Float> TermVolCurve<F> {
+    /// Create a curve from its pillars.
+    ///
+    /// * `maturities`: The positive, strictly increasing maturities as decimal of a year.
+    /// * `vols`: The implied vol in decimal at each maturity.
+    ///
+    /// * `curve`: The curve, `QuantError::ShapeMismatch` when the lengths differ, or
+    ///   `QuantError::InvalidInput` when the maturities are unordered, a vol is not
+    ///   positive, or the total variance decreases, which is a calendar arbitrage.
+    pub fn new(maturities: &[F], vols: &[F]) -> Result<Self, QuantError> {
+        QuantError::check_shape(&[maturities.len()], &[vols.len()])?;
+        let increasing = maturities.iter().zip(maturities.iter().skip(1)).all(|(a, b)| a < b);
+        if maturities.is_empty() || !(maturities[0] > F::zero()) || !increasing {
+            return Err(QuantError::InvalidInput(
+                "maturities must be positive and strictly increasing".to_string(),
+            ));
+        }
+        if vols.iter().any(|vol| !(*vol > F::zero())) {
+            return Err(QuantError::InvalidInput("implied vols must be positive".to_string()));
+        }
+        let variances = maturities
+            .iter()
+            .zip(vols.iter())
+            .map(|(&t, &vol)| vol * vol * t)
+            .collect::<Vec<_>>();
+        if variances.iter().zip(variances.iter().skip(1)).any(|(a, b)| b < a) {
+            return Err(QuantError::InvalidInput(
+                "total variance decreases between the maturities".to_string(),
+            ));
+        }
+        Ok(TermVolCurve {
+            maturities: maturities.to_vec(),
+            variances,
+        })
+    }
+
+    /// The pillar maturities as decimal of a year.
+    pub fn maturities(&self) -> &[F] {
+        &self.maturities
+    }
+}
+
+impl<F: ag::Float> VolCurve<F> for TermVolCurve<F> {
+    fn variance_to(&self, t: F) -> F {
+        let last = self.maturities.len() - 1;
+        if t <= self.maturities[0] || last == 0 {
+            return self.variances[0] * t / self.maturities[0];
+        }
+        // The interval holding `t`, or the last one beyond the last pillar.
+        let j = (self.maturities.partition_point(|&time| time < t) - 1).min(last - 1);
+        let (t0, t1) = (self.maturities[j], self.maturities[j + 1]);
+        let (v0, v1) = (self.variances[j], self.variances[j + 1]);
+        v0 + (v1 - v0) * (t - t0) / (t1 - t0)
+    }
+}
+
+/// An implied volatility surface over a grid of strikes and maturities.
+///
+/// Each maturity's smile is a natural cubic spline in strike. Between maturities the
+/// total implied variance σ²T is interpolated linearly in time, which keeps it
+/// increasing whenever it increases across the grid's maturities. Queries outside the
+/// grid are clamped to it: strikes beyond the grid take the smile's end vols, and
+/// maturities before the first or after the last take that maturity's smile.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VolSurface<F> {
+    strikes: Vec<F>,
+    maturities: Vec<F>,
+    smiles: Vec<CubicSpline<F>>,
+}
+
+impl<F: ag::Float> VolSurface<F> {
+    /// Build a surface from implied vols on a grid.
+    ///
+    /// * `strikes`: The strictly increasing strikes of the grid.
+    /// * `maturities`: The positive, strictly increasing maturities of the grid, as
+    ///   decimal of a year.
+    /// * `vols`: The `[strikes, maturities]` implied vols in decimal, e.g. the layout
+    ///   of `black_scholes::price_surface`.
+    ///
+    /// * `surface`: The surface, `QuantError::ShapeMismatch` when `vols` does not match
+    ///   the grid, or `QuantError::InvalidInput` when the grid is empty or unordered or a
+    ///   vol is not positive.
+    pub fn new(strikes: &[F], maturities: &[F], vols: ag::NdArrayView<F>) -> Result<Self, QuantError> {
+        QuantError::check_shape(&[strikes.len(), maturities.len()], vols.shape())?;
+        let increasing = |xs: &[F]| xs.iter().zip(xs.iter().skip(1)).all(|(a, b)| a < b);
+        if strikes.is_empty() || !increasing(strikes) {
+            return Err(QuantError::InvalidInput("strikes must be strictly increasing".to_string()));
+        }
+        if maturities.is_empty() || !(maturities[0] > F::zero()) || !increasing(maturities) {
+            return Err(QuantError::InvalidInput(
+                "maturities must be positive and strictly increasing".to_string(),
+            ));
+        }
+        if vols.iter().any(|vol| !(*vol > F::zero())) {
+            return Err(QuantError::InvalidInput("implied vols must be positive".to_string()));
+        }
+
+        let smiles = (0..maturities.len())
+            .map(|j| {
+                let column = (0..strikes.len()).map(|i| vols[[i, j]]).collect();
+                CubicSpline::natural(strikes.to_vec(), column)
+            })
+            .collect();
+        Ok(VolSurface {
+            strikes: strikes.to_vec(),
+            maturities: maturities.to_vec(),
+            smiles,
+        })
+    }
+
+    /// The strikes of the grid.
+    pub fn strikes(&self) -> &[F] {
+        &self.strikes
+    }
+
+    /// The maturities of the grid as decimal of a year.
+    pub fn maturities(&self) -> &[F] {
+        &self.maturities
+    }
+
+    /// The implied vol at a strike and maturity, interpolated on the grid and clamped
+    /// outside of it.
+    ///
+    /// * `strike`: The option's strike price.
+    /// * `maturity`: The time until option maturity as decimal of a year.
+    pub fn vol_at(&self, strike: F, maturity: F) -> F {
+        let last = self.maturities.len() - 1;
+        if maturity <= self.maturities[0] {
+            return self.smiles[0].eval(strike);
+        }
+        if maturity >= self.maturities[last] {
+            return self.smiles[last].eval(strike);
+        }
+
+        let j = self.maturities.partition_point(|&t| t <= maturity) - 1;
+        let (t0, t1) = (self.maturities[j], self.maturities[j + 1]);
+        if maturity == t0 {
+            return self.smiles[j].eval(strike);
+        }
+        let (v0, v1) = (self.smiles[j].eval(strike), self.smiles[j + 1].eval(strike));
+        let weight = (maturity - t0) / (t1 - t0);
+        let variance = v0 * v0 * t0 + (v1 * v1 * t1 - v0 * v0 * t0) * weight;
+        (variance / maturity).max(F::zero()).sqrt()
+    }
+
+    /// The forward implied vol at a strike between two maturities
+    ///
+    /// σ_f = √((σ₂² T₂ - σ₁² T₁) / (T₂ - T₁)).
+    ///
+    /// * `strike`: The option's strike price.
+    /// * `t1`: The start of the forward period as decimal of a year.
+    /// * `t2`: The end of the forward period as decimal of a year.
+    ///
+    /// * `vol`: The forward vol, or `QuantError::InvalidInput` when `t2` is not after
+    ///   `t1` or the total variance falls between them, which is a calendar arbitrage.
+    pub fn forward_vol(&self, strike: F, t1: F, t2: F) -> Result<F, QuantError> {
+        if !(t2 > t1) || t1 < F::zero() {
+            return Err(QuantError::InvalidInput("forward period must satisfy 0 <= t1 < t2".to_string()));
+        }
+        let (v1, v2) = (self.vol_at(strike, t1), self.vol_at(strike, t2));
+        let variance = (v2 * v2 * t2 - v1 * v1 * t1) / (t2 - t1);
+        if variance < F::zero() {
+            return Err(QuantError::InvalidInput(
+                "total variance decreases between the maturities".to_string(),
+            ));
+        }
+        Ok(variance.sqrt())
+    }
+}
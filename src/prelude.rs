@@ -0,0 +1,21 @@
+//! The commonly used types and functions of the crate, for a single glob import.
+//!
+//! ```
+//! use rquant::prelude::*;
+//! ```
+//!
+//! The distributions are brought in as modules, e.g. `normal::cdf`, since every
+//! distribution has a `cdf` and a `pdf`. Everything else is available under its own
+//! name from the module that defines it.
+
+pub use crate::error::QuantError;
+pub use crate::fixed_income::curve::{DiscountCurve, FlatCurve, ZeroCurve};
+pub use crate::options::binomial::{price_american_call, price_american_put, BinomialPricingModel};
+pub use crate::options::black_scholes::{call_greeks, implied_volatility_newton, put_greeks, BlackScholesPricingModel};
+pub use crate::options::contract::{OptionContract, OptionContractBuilder};
+pub use crate::options::model::{EarlyStopping, Greeks, ImpliedVolReport, OptionPricingModel, OptionType};
+pub use crate::options::monte_carlo::{price_european_mc, MonteCarloEstimate, MonteCarloPricingModel};
+pub use crate::options::simple::{bs_call_greeks, bs_call_price, bs_put_greeks, bs_put_price, ScalarGreeks};
+pub use crate::options::strategy::{Leg, Strategy};
+pub use crate::options::vol_surface::{FlatVolCurve, TermVolCurve, VolCurve, VolSurface};
+pub use crate::stats::{lognormal, normal, sample_correlated_normals, student_t};
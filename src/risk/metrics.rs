@@ -0,0 +1,138 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+/// The Sharpe ratio of a series of returns: the mean excess return over its sample
+/// standard deviation.
+///
+/// * `returns`: The returns per period as decimal.
+/// * `risk_free`: The risk free rate per period as decimal.
+///
+/// * `sharpe`: The per period Sharpe ratio, infinite when the excess returns are
+///   constant and positive.
+pub fn sharpe_ratio<F: ag::Float>(returns: &ag::NdArray<F>, risk_free: F) -> F {
+    let n = F::from(returns.len()).unwrap();
+    let mean = mean(returns) - risk_free;
+    let sum_squares = returns
+        .iter()
+        .fold(F::zero(), |acc, &r| acc + (r - risk_free - mean).powi(2));
+    ratio(mean, (sum_squares / (n - F::one())).sqrt())
+}
+
+/// The Sortino ratio of a series of returns: the mean excess return over the downside
+/// deviation.
+///
+/// The downside deviation is the root mean square of the excess returns below zero,
+/// with the returns above the risk free rate counted as zero deviation.
+///
+/// * `returns`: The returns per period as decimal.
+/// * `risk_free`: The risk free rate per period as decimal.
+///
+/// * `sortino`: The per period Sortino ratio, infinite when no return falls below
+///   the risk free rate and the mean excess return is positive.
+pub fn sortino_ratio<F: ag::Float>(returns: &ag::NdArray<F>, risk_free: F) -> F {
+    let n = F::from(returns.len()).unwrap();
+    let downside = returns
+        .iter()
+        .fold(F::zero(), |acc, &r| acc + (r - risk_free).min(F::zero()).powi(2));
+    ratio(mean(returns) - risk_free, (downside / n).sqrt())
+}
+
+/// The Calmar ratio of a series of returns: the mean return over the maximum
+/// drawdown of the compounded equity curve.
+///
+/// * `returns`: The returns per period as decimal.
+///
+/// * `calmar`: The per period Calmar ratio, infinite when the equity curve never
+///   falls and the mean return is positive.
+pub fn calmar_ratio<F: ag::Float>(returns: &ag::NdArray<F>) -> F {
+    ratio(mean(returns), max_drawdown(&equity_curve(returns)))
+}
+
+/// The pain ratio of a series of returns: the mean excess return over the pain index,
+/// the mean drawdown of the compounded equity curve.
+///
+/// Unlike the Calmar ratio it penalizes every period spent below a peak, not only the
+/// worst one, so a long shallow drawdown can cost as much as a short deep one.
+///
+/// * `returns`: The returns per period as decimal.
+/// * `risk_free`: The risk free rate per period as decimal.
+///
+/// * `pain`: The per period pain ratio, infinite when the equity curve never falls
+///   and the mean excess return is positive.
+pub fn pain_ratio<F: ag::Float>(returns: &ag::NdArray<F>, risk_free: F) -> F {
+    let drawdowns = drawdowns(&equity_curve(returns));
+    let pain_index = drawdowns.iter().fold(F::zero(), |acc, &d| acc + d) / F::from(drawdowns.len()).unwrap();
+    ratio(mean(returns) - risk_free, pain_index)
+}
+
+/// The maximum drawdown of an equity curve: the largest decline from a running peak
+/// to a later trough.
+///
+/// * `equity_curve`: The positive values of the equity over time.
+///
+/// * `drawdown`: The largest peak to trough decline as a positive fraction of the
+///   peak, zero when the curve never falls.
+pub fn max_drawdown<F: ag::Float>(equity_curve: &ag::NdArray<F>) -> F {
+    let mut peak = F::neg_infinity();
+    equity_curve.iter().fold(F::zero(), |worst, &value| {
+        peak = peak.max(value);
+        worst.max(F::one() - value / peak)
+    })
+}
+
+/// The Ulcer index of an equity curve: the root mean square of the drawdowns from
+/// the running peak at every point.
+///
+/// Squaring weights deep drawdowns more than shallow ones, and every period below a
+/// peak adds to the index, so it measures both the depth and the duration of the
+/// drawdowns where the maximum drawdown only sees the worst one.
+///
+/// * `equity_curve`: The positive values of the equity over time.
+///
+/// * `ulcer`: The index as a fraction of the peaks, zero when the curve never falls.
+pub fn ulcer_index<F: ag::Float>(equity_curve: &ag::NdArray<F>) -> F {
+    let drawdowns = drawdowns(equity_curve);
+    let sum_squares = drawdowns.iter().fold(F::zero(), |acc, &d| acc + d * d);
+    (sum_squares / F::from(drawdowns.len()).unwrap()).sqrt()
+}
+
+/// The decline of each point of an equity curve from its running peak, as a fraction
+/// of the peak.
+fn drawdowns<F: ag::Float>(equity_curve: &ag::NdArray<F>) -> Vec<F> {
+    let mut peak = F::neg_infinity();
+    equity_curve
+        .iter()
+        .map(|&value| {
+            peak = peak.max(value);
+            F::one() - value / peak
+        })
+        .collect()
+}
+
+/// The equity curve compounding a series of returns from an initial value of 1.
+fn equity_curve<F: ag::Float>(returns: &ag::NdArray<F>) -> ag::NdArray<F> {
+    let curve = std::iter::once(F::one())
+        .chain(returns.iter().scan(F::one(), |equity, &r| {
+            *equity = *equity * (F::one() + r);
+            Some(*equity)
+        }))
+        .collect::<Vec<_>>();
+    nd::Array1::from(curve).into_dyn()
+}
+
+/// The mean of a series of returns.
+fn mean<F: ag::Float>(returns: &ag::NdArray<F>) -> F {
+    returns.iter().fold(F::zero(), |acc, &r| acc + r) / F::from(returns.len()).unwrap()
+}
+
+/// A performance ratio whose zero denominator means there is no risk to reward, so
+/// the ratio is infinite with the sign of the numerator, or zero for no reward.
+fn ratio<F: ag::Float>(reward: F, risk: F) -> F {
+    if risk > F::zero() {
+        reward / risk
+    } else if reward == F::zero() {
+        F::zero()
+    } else {
+        reward.signum() * F::infinity()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod metrics;
+pub mod portfolio;
+pub mod var;
@@ -0,0 +1,309 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use autograd::prelude::*;
+use crate::error::QuantError;
+use crate::numerics::linalg::solve;
+use crate::numerics::optimize::run_epochs;
+use crate::risk::var::check_confidence;
+use crate::stats::normal;
+
+/// The variance `wᵀΣw` of a portfolio's return.
+///
+/// * `weights`: The `[n_assets]` portfolio weights.
+/// * `cov`: The `[n_assets, n_assets]` covariance matrix of the assets' returns.
+///
+/// * `variance`: The variance of the portfolio's return, or `QuantError::ShapeMismatch`
+///   when the shapes of the weights and the covariance do not match.
+pub fn portfolio_variance<F: ag::Float>(weights: &ag::NdArray<F>, cov: &ag::NdArray<F>) -> Result<F, QuantError> {
+    let (w, cov) = as_vector_and_matrix(weights, cov)?;
+    Ok(w.dot(&cov.dot(&w)))
+}
+
+/// The volatility `√(wᵀΣw)` of a portfolio's return.
+///
+/// * `weights`: The `[n_assets]` portfolio weights.
+/// * `cov`: The `[n_assets, n_assets]` covariance matrix of the assets' returns.
+///
+/// * `volatility`: The standard deviation of the portfolio's return, or
+///   `QuantError::ShapeMismatch` when the shapes of the weights and the covariance do
+///   not match.
+pub fn portfolio_volatility<F: ag::Float>(weights: &ag::NdArray<F>, cov: &ag::NdArray<F>) -> Result<F, QuantError> {
+    portfolio_variance(weights, cov).map(|variance| variance.sqrt())
+}
+
+/// Decompose the one period parametric Value-at-Risk `z√(wᵀΣw)` of a portfolio with
+/// zero mean returns into the contributions of its assets.
+///
+/// The contribution of asset `i` is its weight times its marginal VaR,
+/// `wᵢ z (Σw)ᵢ / √(wᵀΣw)`. The VaR is homogeneous of degree one in the weights, so by
+/// Euler's theorem the contributions sum to the total. A negative contribution marks
+/// an asset that hedges the rest of the portfolio.
+///
+/// * `weights`: The `[n_assets]` portfolio weights, or positions in currency for a VaR
+///   in currency.
+/// * `cov`: The `[n_assets, n_assets]` covariance matrix of the assets' returns.
+/// * `confidence`: The confidence level strictly between 0 and 1, e.g. `0.99`.
+///
+/// * `components`: The `[n_assets]` contributions to the VaR,
+///   `QuantError::ShapeMismatch` when the shapes of the weights and the covariance do
+///   not match, or `QuantError::InvalidInput` when `confidence` is out of range or
+///   the portfolio has no risk.
+pub fn component_var<F: ag::Float>(
+    weights: &ag::NdArray<F>,
+    cov: &ag::NdArray<F>,
+    confidence: F,
+) -> Result<ag::NdArray<F>, QuantError> {
+    let volatility = portfolio_volatility(weights, cov)?;
+    let z = var_multiplier(confidence)?;
+    if !(volatility > F::zero()) {
+        return Err(QuantError::InvalidInput("the portfolio has no risk".to_string()));
+    }
+    let (w, cov) = as_vector_and_matrix(weights, cov)?;
+    let marginal = cov.dot(&w).mapv(|x| x * z / volatility);
+    Ok((&w * &marginal).into_dyn())
+}
+
+/// The change in the one period parametric Value-at-Risk `z√(wᵀΣw)` of a portfolio
+/// with zero mean returns when a trade changes one asset's weight by `delta`.
+///
+/// The VaR is revalued in full at the new weights rather than through the marginal
+/// VaR, so the result is exact for large trades too.
+///
+/// * `weights`: The `[n_assets]` portfolio weights.
+/// * `cov`: The `[n_assets, n_assets]` covariance matrix of the assets' returns.
+/// * `confidence`: The confidence level strictly between 0 and 1, e.g. `0.99`.
+/// * `asset`: The index of the traded asset.
+/// * `delta`: The change in the asset's weight, negative for a sale.
+///
+/// * `incremental`: The VaR after the trade less the VaR before it,
+///   `QuantError::ShapeMismatch` when the shapes of the weights and the covariance do
+///   not match, or `QuantError::InvalidInput` when `confidence` is out of range or
+///   `asset` is not an index of the weights.
+pub fn incremental_var<F: ag::Float>(
+    weights: &ag::NdArray<F>,
+    cov: &ag::NdArray<F>,
+    confidence: F,
+    asset: usize,
+    delta: F,
+) -> Result<F, QuantError> {
+    let before = portfolio_volatility(weights, cov)?;
+    let z = var_multiplier(confidence)?;
+    if asset >= weights.len() {
+        return Err(QuantError::InvalidInput(format!(
+            "asset {} is not one of the {} assets",
+            asset,
+            weights.len()
+        )));
+    }
+    let mut traded = weights.clone();
+    traded[asset] = traded[asset] + delta;
+    let after = portfolio_volatility(&traded, cov)?;
+    Ok(z * (after - before))
+}
+
+/// Find the fully invested portfolio maximizing the mean-variance utility
+/// `wᵀμ - λ/2·wᵀΣw` with Adam.
+///
+/// The weights are the softmax of unconstrained logits, so they always sum to 1 and
+/// the portfolio is long only.
+///
+/// * `expected_returns`: The `[n_assets]` expected returns `μ` of the assets.
+/// * `cov`: The `[n_assets, n_assets]` covariance matrix `Σ` of the assets' returns.
+/// * `risk_aversion`: The risk aversion `λ`, zero to maximize the expected return alone.
+///
+/// * `weights`: The `[n_assets]` optimal weights, or `QuantError::ShapeMismatch` when
+///   the shapes of the expected returns and the covariance do not match.
+pub fn optimize_mean_variance<F: ag::Float>(
+    expected_returns: &ag::NdArray<F>,
+    cov: &ag::NdArray<F>,
+    risk_aversion: F,
+) -> Result<ag::NdArray<F>, QuantError> {
+    let (mu, _) = as_vector_and_matrix(expected_returns, cov)?;
+    let n = mu.len();
+    let mu = mu.to_owned().into_shape(nd::IxDyn(&[1, n])).unwrap();
+    let half_lambda = risk_aversion / F::from(2f64).unwrap();
+
+    let mut env = ag::VariableEnvironment::new();
+    let logits_id = env.name("logits").set(nd::ArrayD::zeros(nd::IxDyn(&[1, n])));
+
+    let adam = ag::optimizers::adam::Adam::default("AdamMV", env.default_namespace().current_var_ids(), &mut env);
+
+    env.run(|ctx| {
+        let logits = ctx.variable("logits");
+        let returns = ctx.placeholder("mu", &[1, -1]);
+        let covariance = ctx.placeholder("cov", &[-1, -1]);
+
+        let w = math::softmax(logits, 1);
+        let expected = math::reduce_sum(w * returns, &[1], false);
+        let variance = math::reduce_sum(math::matmul(w, covariance) * w, &[1], false);
+        let loss = variance * half_lambda - expected;
+        let grads = math::grad(&[loss], &[logits]);
+        let update = adam.get_update_op(&[logits], &grads, ctx);
+
+        run_epochs(ctx, &update, &[(returns, mu.view()), (covariance, cov.view())], 5000);
+    });
+
+    let logits = env.get_array_by_id(logits_id).unwrap().clone().into_inner();
+    let max = logits.iter().fold(F::neg_infinity(), |acc, &z| acc.max(z));
+    let exp = logits.mapv(|z| (z - max).exp()).into_shape(nd::IxDyn(&[n])).unwrap();
+    let total = exp.iter().fold(F::zero(), |acc, &e| acc + e);
+    Ok(exp.mapv(|e| e / total))
+}
+
+/// Trace the efficient frontier of fully invested portfolios that may sell short.
+///
+/// For each target return `m` the minimum variance weights have the closed form
+///
+/// w = ((C - mB) Σ⁻¹1 + (mA - B) Σ⁻¹μ) / D,
+///
+/// with `A = 1ᵀΣ⁻¹1`, `B = 1ᵀΣ⁻¹μ`, `C = μᵀΣ⁻¹μ` and `D = AC - B²`, and variance
+/// `(Am² - 2Bm + C) / D`, a parabola in the target return. The targets are spaced
+/// evenly from the return `B / A` of the minimum variance portfolio up by the spread
+/// between the highest and lowest expected returns, covering the efficient upper
+/// branch where risk rises with return.
+///
+/// * `expected_returns`: The `[n_assets]` expected returns `μ` of the assets.
+/// * `cov`: The `[n_assets, n_assets]` covariance matrix `Σ` of the assets' returns.
+/// * `n_points`: The number of portfolios on the frontier, at least 2.
+///
+/// * `frontier`: The `(volatility, expected return, [n_assets] weights)` of each
+///   portfolio in increasing return, `QuantError::ShapeMismatch` when the shapes of
+///   the expected returns and the covariance do not match,
+///   `QuantError::NotPositiveDefinite` when the covariance is singular, or
+///   `QuantError::InvalidInput` when `n_points` is less than 2 or all expected returns
+///   are equal.
+pub fn efficient_frontier<F: ag::Float>(
+    expected_returns: &ag::NdArray<F>,
+    cov: &ag::NdArray<F>,
+    n_points: usize,
+) -> Result<Vec<(F, F, ag::NdArray<F>)>, QuantError> {
+    if n_points < 2 {
+        return Err(QuantError::InvalidInput("need at least 2 frontier points".to_string()));
+    }
+    let frontier = Frontier::new(expected_returns, cov)?;
+    // `D` vanishes when the expected returns are equal, up to the rounding of `AC`.
+    if !(frontier.d > F::from(100f64).unwrap() * F::epsilon() * frontier.a * frontier.c) {
+        return Err(QuantError::InvalidInput("the expected returns are all equal".to_string()));
+    }
+    let (lowest, highest) = frontier
+        .mu
+        .iter()
+        .fold((F::infinity(), F::neg_infinity()), |(lo, hi), &m| (lo.min(m), hi.max(m)));
+    let start = frontier.b / frontier.a;
+    let step = (highest - lowest) / F::from(n_points - 1).unwrap();
+    let two = F::from(2f64).unwrap();
+
+    Ok((0..n_points)
+        .map(|i| {
+            let target = start + step * F::from(i).unwrap();
+            let weights = (&frontier.inv_ones * ((frontier.c - target * frontier.b) / frontier.d)
+                + &frontier.inv_mu * ((target * frontier.a - frontier.b) / frontier.d))
+                .into_dyn();
+            let variance = (frontier.a * target * target - two * frontier.b * target + frontier.c) / frontier.d;
+            (variance.sqrt(), target, weights)
+        })
+        .collect())
+}
+
+/// The fully invested portfolio of least variance, `Σ⁻¹1 / 1ᵀΣ⁻¹1`, which may sell
+/// short.
+///
+/// * `cov`: The `[n_assets, n_assets]` covariance matrix of the assets' returns.
+///
+/// * `weights`: The `[n_assets]` weights, `QuantError::ShapeMismatch` when the
+///   covariance is not square, or `QuantError::NotPositiveDefinite` when it is
+///   singular.
+pub fn min_variance_portfolio<F: ag::Float>(cov: &ag::NdArray<F>) -> Result<ag::NdArray<F>, QuantError> {
+    let n = cov.shape().first().cloned().unwrap_or(0);
+    let frontier = Frontier::new(&nd::ArrayD::zeros(nd::IxDyn(&[n])), cov)?;
+    Ok(frontier.inv_ones.mapv(|x| x / frontier.a).into_dyn())
+}
+
+/// The fully invested portfolio of greatest Sharpe ratio, `Σ⁻¹(μ - r_f) / 1ᵀΣ⁻¹(μ - r_f)`,
+/// where the capital market line from the risk free rate touches the efficient frontier.
+///
+/// * `expected_returns`: The `[n_assets]` expected returns `μ` of the assets.
+/// * `cov`: The `[n_assets, n_assets]` covariance matrix `Σ` of the assets' returns.
+/// * `risk_free`: The risk free rate `r_f` per period as decimal.
+///
+/// * `weights`: The `[n_assets]` weights, `QuantError::ShapeMismatch` when the shapes
+///   of the expected returns and the covariance do not match,
+///   `QuantError::NotPositiveDefinite` when the covariance is singular, or
+///   `QuantError::InvalidInput` when the risk free rate is not below the return of
+///   the minimum variance portfolio, so the line touches no efficient portfolio.
+pub fn tangency_portfolio<F: ag::Float>(
+    expected_returns: &ag::NdArray<F>,
+    cov: &ag::NdArray<F>,
+    risk_free: F,
+) -> Result<ag::NdArray<F>, QuantError> {
+    let frontier = Frontier::new(expected_returns, cov)?;
+    let excess = &frontier.inv_mu - &frontier.inv_ones.mapv(|x| x * risk_free);
+    let total = excess.iter().fold(F::zero(), |acc, &x| acc + x);
+    if !(total > F::zero()) {
+        return Err(QuantError::InvalidInput(format!(
+            "risk free rate {} is not below the minimum variance return",
+            risk_free
+        )));
+    }
+    Ok(excess.mapv(|x| x / total).into_dyn())
+}
+
+/// The solutions `Σ⁻¹1` and `Σ⁻¹μ` and the scalars `A`, `B`, `C` and `D` of the
+/// efficient frontier's closed form.
+struct Frontier<F> {
+    mu: nd::Array1<F>,
+    inv_ones: nd::Array1<F>,
+    inv_mu: nd::Array1<F>,
+    a: F,
+    b: F,
+    c: F,
+    d: F,
+}
+
+impl<F: ag::Float> Frontier<F> {
+    fn new(expected_returns: &ag::NdArray<F>, cov: &ag::NdArray<F>) -> Result<Self, QuantError> {
+        let (mu, cov) = as_vector_and_matrix(expected_returns, cov)?;
+        let (mu, cov) = (mu.to_owned(), cov.to_owned());
+        let ones = nd::Array1::<F>::ones(mu.len());
+        let inv_ones = solve(&cov, &ones).ok_or(QuantError::NotPositiveDefinite)?;
+        let inv_mu = solve(&cov, &mu).ok_or(QuantError::NotPositiveDefinite)?;
+        let a = inv_ones.iter().fold(F::zero(), |acc, &x| acc + x);
+        if !(a > F::zero()) {
+            return Err(QuantError::NotPositiveDefinite);
+        }
+        let b = inv_mu.iter().fold(F::zero(), |acc, &x| acc + x);
+        let c = mu.dot(&inv_mu);
+        let d = a * c - b * b;
+        Ok(Frontier {
+            mu,
+            inv_ones,
+            inv_mu,
+            a,
+            b,
+            c,
+            d,
+        })
+    }
+}
+
+/// View the weights as a vector and the covariance as a matching square matrix.
+pub(crate) fn as_vector_and_matrix<'a, F: ag::Float>(
+    weights: &'a ag::NdArray<F>,
+    cov: &'a ag::NdArray<F>,
+) -> Result<(nd::ArrayView1<'a, F>, nd::ArrayView2<'a, F>), QuantError> {
+    let n = weights.len();
+    QuantError::check_shape(&[n], weights.shape())?;
+    QuantError::check_shape(&[n, n], cov.shape())?;
+    Ok((
+        weights.view().into_dimensionality::<nd::Ix1>().unwrap(),
+        cov.view().into_dimensionality::<nd::Ix2>().unwrap(),
+    ))
+}
+
+/// The standard normal quantile of `confidence`, the VaR per unit of volatility.
+fn var_multiplier<F: ag::Float>(confidence: F) -> Result<F, QuantError> {
+    check_confidence(confidence)?;
+    Ok(F::from(normal::inverse_cdf_scalar(confidence.to_f64().unwrap())).unwrap())
+}
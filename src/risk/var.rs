@@ -0,0 +1,206 @@
+use autograd as ag;
+
+use crate::error::QuantError;
+use crate::stats::{normal, student_t};
+
+/// The historical Value-at-Risk of a series of returns.
+///
+/// VaR is reported as a positive loss: the negated `1 - confidence` quantile of the
+/// empirical return distribution. The quantile interpolates linearly between order
+/// statistics, placing the `i`-th smallest of `n` returns at probability `i / (n - 1)`.
+///
+/// * `returns`: The observed returns as decimal.
+/// * `confidence`: The confidence level strictly between 0 and 1, e.g. `0.99`.
+///
+/// * `var`: The loss exceeded with probability `1 - confidence`, or
+///   `QuantError::InvalidInput` when `returns` is empty or not finite or `confidence`
+///   is out of range.
+pub fn historical_var<F: ag::Float>(returns: &ag::NdArray<F>, confidence: F) -> Result<F, QuantError> {
+    let sorted = sorted_returns(returns, confidence)?;
+    Ok(-quantile(&sorted, F::one() - confidence))
+}
+
+/// The parametric Value-at-Risk of normally distributed returns over a horizon.
+///
+/// The per period mean and standard deviation scale to `μh` and `σ√h` over `h`
+/// periods, and the VaR is the positive loss `z σ√h - μh`, where `z` is the standard
+/// normal quantile of `confidence`.
+///
+/// * `mean`: The mean return per period as decimal.
+/// * `std`: The standard deviation of the return per period as decimal.
+/// * `confidence`: The confidence level strictly between 0 and 1, e.g. `0.99`.
+/// * `horizon`: The number of periods `h` the VaR covers.
+///
+/// * `var`: The loss exceeded with probability `1 - confidence`, or
+///   `QuantError::InvalidInput` when `confidence` is out of range.
+pub fn parametric_var<F: ag::Float>(mean: F, std: F, confidence: F, horizon: F) -> Result<F, QuantError> {
+    check_confidence(confidence)?;
+    let z = F::from(normal::inverse_cdf_scalar(confidence.to_f64().unwrap())).unwrap();
+    Ok(z * std * horizon.sqrt() - mean * horizon)
+}
+
+/// The parametric Value-at-Risk of returns following a Student's t distribution,
+/// which has fatter tails than the normal.
+///
+/// The t distribution is rescaled by `√((ν - 2) / ν)` so that its standard deviation
+/// is `std`, then scaled over the horizon like `parametric_var`.
+///
+/// * `mean`: The mean return per period as decimal.
+/// * `std`: The standard deviation of the return per period as decimal.
+/// * `confidence`: The confidence level strictly between 0 and 1, e.g. `0.99`.
+/// * `horizon`: The number of periods `h` the VaR covers.
+/// * `df`: The degrees of freedom ν, greater than 2 so the variance is finite.
+///
+/// * `var`: The loss exceeded with probability `1 - confidence`, or
+///   `QuantError::InvalidInput` when `confidence` or `df` is out of range.
+pub fn parametric_var_student_t<F: ag::Float>(
+    mean: F,
+    std: F,
+    confidence: F,
+    horizon: F,
+    df: F,
+) -> Result<F, QuantError> {
+    check_confidence(confidence)?;
+    let two = F::from(2f64).unwrap();
+    if !(df > two) {
+        return Err(QuantError::InvalidInput(format!(
+            "degrees of freedom {} must exceed 2",
+            df
+        )));
+    }
+    let t = F::from(student_t::quantile_scalar(confidence.to_f64().unwrap(), df.to_f64().unwrap())).unwrap();
+    let scale = ((df - two) / df).sqrt();
+    Ok(t * scale * std * horizon.sqrt() - mean * horizon)
+}
+
+/// Adjust a standard normal quantile for the skewness and excess kurtosis of a
+/// distribution with the Cornish-Fisher expansion
+///
+/// z_cf = z + (z² - 1)S/6 + (z³ - 3z)K/24 - (2z³ - 5z)S²/36.
+///
+/// The expansion is accurate for moderate skewness and kurtosis. Far from normal it
+/// can stop increasing in `z`, so the adjusted quantiles are no longer ordered.
+///
+/// * `z`: The standard normal quantile.
+/// * `skew`: The skewness `S` of the distribution.
+/// * `kurtosis`: The excess kurtosis `K` of the distribution, 0 for the normal.
+///
+/// * `quantile`: The adjusted quantile of the standardized distribution.
+pub fn cornish_fisher_quantile<F: ag::Float>(z: F, skew: F, kurtosis: F) -> F {
+    let z2 = z * z;
+    let z3 = z2 * z;
+    let (two, three, five) = (F::from(2f64).unwrap(), F::from(3f64).unwrap(), F::from(5f64).unwrap());
+    z + (z2 - F::one()) * skew / F::from(6f64).unwrap() + (z3 - three * z) * kurtosis / F::from(24f64).unwrap()
+        - (two * z3 - five * z) * skew * skew / F::from(36f64).unwrap()
+}
+
+/// The Value-at-Risk of returns with a given skewness and excess kurtosis, the
+/// parametric VaR with its normal quantile adjusted by `cornish_fisher_quantile`.
+///
+/// The `1 - confidence` quantile of the returns is `μ + z_cf σ`, with `z_cf` the
+/// adjusted standard normal quantile of `1 - confidence`, and the VaR is its negation.
+/// Negative skew and positive excess kurtosis fatten the left tail and raise the VaR
+/// at high confidence. Without skew and excess kurtosis it is the one period
+/// `parametric_var`.
+///
+/// * `mean`: The mean return as decimal.
+/// * `std`: The standard deviation of the return as decimal.
+/// * `skew`: The skewness of the returns.
+/// * `kurtosis`: The excess kurtosis of the returns.
+/// * `confidence`: The confidence level strictly between 0 and 1, e.g. `0.99`.
+///
+/// * `var`: The loss exceeded with probability `1 - confidence`, or
+///   `QuantError::InvalidInput` when `confidence` is out of range.
+pub fn cornish_fisher_var<F: ag::Float>(
+    mean: F,
+    std: F,
+    skew: F,
+    kurtosis: F,
+    confidence: F,
+) -> Result<F, QuantError> {
+    check_confidence(confidence)?;
+    let z = -F::from(normal::inverse_cdf_scalar(confidence.to_f64().unwrap())).unwrap();
+    Ok(-(mean + cornish_fisher_quantile(z, skew, kurtosis) * std))
+}
+
+/// The historical expected shortfall, or conditional VaR, of a series of returns.
+///
+/// The mean loss over the returns at or below the historical VaR quantile, reported
+/// as a positive loss like `historical_var`, so it is never less than the VaR.
+///
+/// * `returns`: The observed returns as decimal.
+/// * `confidence`: The confidence level strictly between 0 and 1, e.g. `0.975`.
+///
+/// * `es`: The expected loss given the VaR is exceeded, or `QuantError::InvalidInput`
+///   when `returns` is empty or not finite or `confidence` is out of range.
+pub fn expected_shortfall<F: ag::Float>(returns: &ag::NdArray<F>, confidence: F) -> Result<F, QuantError> {
+    let sorted = sorted_returns(returns, confidence)?;
+    let cutoff = quantile(&sorted, F::one() - confidence);
+    let (total, count) = sorted
+        .iter()
+        .take_while(|&&x| x <= cutoff)
+        .fold((F::zero(), 0), |(total, count), &x| (total + x, count + 1));
+    Ok(-total / F::from(count).unwrap())
+}
+
+/// The parametric expected shortfall of normally distributed returns over a horizon
+///
+/// ES = σ√h · φ(z) / (1 - α) - μh,
+///
+/// where `z` is the standard normal quantile of the confidence `α`, scaled over the
+/// horizon like `parametric_var` and reported as a positive loss.
+///
+/// * `mean`: The mean return per period as decimal.
+/// * `std`: The standard deviation of the return per period as decimal.
+/// * `confidence`: The confidence level strictly between 0 and 1, e.g. `0.975`.
+/// * `horizon`: The number of periods `h` the expected shortfall covers.
+///
+/// * `es`: The expected loss given the VaR is exceeded, or `QuantError::InvalidInput`
+///   when `confidence` is out of range.
+pub fn parametric_expected_shortfall<F: ag::Float>(
+    mean: F,
+    std: F,
+    confidence: F,
+    horizon: F,
+) -> Result<F, QuantError> {
+    check_confidence(confidence)?;
+    let z = normal::inverse_cdf_scalar(confidence.to_f64().unwrap());
+    let density = F::from((-0.5 * z * z).exp() / (2. * std::f64::consts::PI).sqrt()).unwrap();
+    Ok(std * horizon.sqrt() * density / (F::one() - confidence) - mean * horizon)
+}
+
+/// The returns sorted in increasing order, after validating the inputs.
+fn sorted_returns<F: ag::Float>(returns: &ag::NdArray<F>, confidence: F) -> Result<Vec<F>, QuantError> {
+    if returns.is_empty() {
+        return Err(QuantError::InvalidInput("no returns".to_string()));
+    }
+    if returns.iter().any(|r| !r.is_finite()) {
+        return Err(QuantError::InvalidInput("returns must be finite".to_string()));
+    }
+    check_confidence(confidence)?;
+    let mut sorted = returns.iter().cloned().collect::<Vec<_>>();
+    // Every return is finite, so the comparison is total.
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(sorted)
+}
+
+/// Check that a confidence level lies strictly between 0 and 1.
+pub(crate) fn check_confidence<F: ag::Float>(confidence: F) -> Result<(), QuantError> {
+    if confidence > F::zero() && confidence < F::one() {
+        Ok(())
+    } else {
+        Err(QuantError::InvalidInput(format!(
+            "confidence {} is not in (0, 1)",
+            confidence
+        )))
+    }
+}
+
+/// The linearly interpolated `p` quantile of sorted values.
+fn quantile<F: ag::Float>(sorted: &[F], p: F) -> F {
+    let position = p * F::from(sorted.len() - 1).unwrap();
+    let lower = position.floor().to_usize().unwrap();
+    let upper = (lower + 1).min(sorted.len() - 1);
+    let fraction = position - F::from(lower).unwrap();
+    sorted[lower] + (sorted[upper] - sorted[lower]) * fraction
+}
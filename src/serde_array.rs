@@ -0,0 +1,32 @@
+//! Serialize dynamically shaped arrays as their shape and their elements in row major
+//! order, for use with `#[serde(with = "crate::serde_array")]`.
+
+use autograd::ndarray as nd;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+struct Array<T> {
+    shape: Vec<usize>,
+    data: Vec<T>,
+}
+
+pub(crate) fn serialize<S, T>(array: &nd::ArrayD<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + Clone,
+{
+    Array {
+        shape: array.shape().to_vec(),
+        data: array.iter().cloned().collect(),
+    }
+    .serialize(serializer)
+}
+
+pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<nd::ArrayD<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let array = Array::<T>::deserialize(deserializer)?;
+    nd::ArrayD::from_shape_vec(array.shape, array.data).map_err(serde::de::Error::custom)
+}
@@ -0,0 +1,52 @@
+use autograd as ag;
+use autograd::tensor_ops as math;
+
+use autograd::rand::Rng;
+
+use crate::stats::normal;
+
+/// The cumulative distribution function of the lognormal distribution, evaluated
+/// elementwise as a differentiable tensor.
+///
+/// cdf(x) = Φ((ln x - μ) / σ)
+///
+/// * `x`: The positive points at which to evaluate the cdf.
+/// * `mu`: The mean of the logarithm of the variable.
+/// * `sigma`: The standard deviation of the logarithm of the variable.
+///
+/// * `cdf`: P(X <= x) for each point.
+pub fn cdf<'graph, A, F: ag::Float>(x: A, mu: F, sigma: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    normal::cdf(&math::ln(x.as_ref()), mu, sigma)
+}
+
+/// The probability density function of the lognormal distribution, evaluated
+/// elementwise as a differentiable tensor.
+///
+/// pdf(x) = 1 / (xσ√(2π)) · exp(-(ln x - μ)² / (2σ²))
+///
+/// * `x`: The positive points at which to evaluate the density.
+/// * `mu`: The mean of the logarithm of the variable.
+/// * `sigma`: The standard deviation of the logarithm of the variable.
+///
+/// * `pdf`: The density at each point.
+pub fn pdf<'graph, A, F: ag::Float>(x: A, mu: F, sigma: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    normal::pdf(&math::ln(x.as_ref()), mu, sigma) / *x.as_ref()
+}
+
+/// Draw iid samples from a lognormal distribution by exponentiating normal draws.
+///
+/// * `shape`: The shape of the returned array.
+/// * `mu`: The mean of the logarithm of the variable.
+/// * `sigma`: The standard deviation of the logarithm of the variable.
+/// * `rng`: The source of randomness.
+///
+/// * `samples`: The lognormal draws.
+pub fn sample<F: ag::Float, R: Rng>(shape: &[usize], mu: F, sigma: F, rng: &mut R) -> ag::NdArray<F> {
+    normal::sample(shape, mu, sigma, rng).mapv(|x| x.exp())
+}
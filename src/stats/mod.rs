@@ -0,0 +1,8 @@
+pub mod lognormal;
+pub mod normal;
+pub mod special;
+pub mod student_t;
+pub mod tests;
+pub mod timeseries;
+
+pub use normal::sample_correlated_normals;
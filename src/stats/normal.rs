@@ -0,0 +1,268 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use autograd::rand::{distributions::Distribution, Rng};
+use autograd::statrs::distribution::Normal;
+use autograd::statrs::function::erf::erfc;
+
+use crate::error::QuantError;
+use crate::numerics::integrate::gauss_legendre_nodes;
+use crate::numerics::linalg::cholesky;
+
+/// The cumulative distribution function of the normal distribution, evaluated
+/// elementwise as a differentiable tensor.
+///
+/// * `x`: The points at which to evaluate the cdf.
+/// * `mean`: The mean of the distribution.
+/// * `std`: The standard deviation of the distribution.
+///
+/// * `cdf`: P(X <= x) for each point.
+pub fn cdf<'graph, A, F: ag::Float>(x: A, mean: F, std: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    math::normal_cdf(x.as_ref(), mean, std)
+}
+
+/// The probability density function of the normal distribution, evaluated
+/// elementwise as a differentiable tensor.
+///
+/// pdf(x) = 1 / (σ√(2π)) · exp(-(x - μ)² / (2σ²))
+///
+/// * `x`: The points at which to evaluate the density.
+/// * `mean`: The mean of the distribution.
+/// * `std`: The standard deviation of the distribution.
+///
+/// * `pdf`: The density at each point.
+pub fn pdf<'graph, A, F: ag::Float>(x: A, mean: F, std: F) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let half = F::from(0.5f64).unwrap();
+    let tau = F::from(2. * std::f64::consts::PI).unwrap();
+    let z = (x.as_ref() - mean) / std;
+    math::exp(math::neg(math::square(z) * half)) * (F::one() / (std * tau.sqrt()))
+}
+
+/// Draw iid samples from a normal distribution.
+///
+/// Pass a seeded rng, e.g. `StdRng::seed_from_u64`, for reproducible draws.
+///
+/// * `shape`: The shape of the returned array.
+/// * `mean`: The mean of the distribution.
+/// * `std`: The standard deviation of the distribution.
+/// * `rng`: The source of randomness.
+///
+/// * `samples`: The normal draws.
+pub fn sample<F: ag::Float, R: Rng>(shape: &[usize], mean: F, std: F, rng: &mut R) -> ag::NdArray<F> {
+    let standard = Normal::new(0., 1.).unwrap();
+    nd::ArrayD::from_shape_simple_fn(shape, || mean + std * F::from(standard.sample(rng)).unwrap())
+}
+
+/// Draw iid samples of a zero mean multivariate normal distribution, e.g. the
+/// correlated shocks of a basket of assets.
+///
+/// The covariance is cholesky factored as `l lᵀ` and applied to independent
+/// standard normal draws, so each row is distributed as `N(0, cov)`.
+///
+/// * `cov`: The `[n_assets, n_assets]` covariance or correlation matrix.
+/// * `n_samples`: The number of draws.
+/// * `rng`: The source of randomness.
+///
+/// * `samples`: The `[n_samples, n_assets]` draws, or `QuantError::NotPositiveDefinite`
+///   when `cov` is not positive definite.
+pub fn sample_correlated_normals<F: ag::Float, R: Rng>(
+    cov: &ag::NdArray<F>,
+    n_samples: usize,
+    rng: &mut R,
+) -> Result<ag::NdArray<F>, QuantError> {
+    let n_assets = cov.shape().first().cloned().unwrap_or(0);
+    QuantError::check_shape(&[n_assets, n_assets], cov.shape())?;
+    let cov = cov.view().into_dimensionality::<nd::Ix2>().unwrap().to_owned();
+    let l = cholesky(&cov)?;
+
+    let z = sample(&[n_samples, n_assets], F::zero(), F::one(), rng)
+        .into_dimensionality::<nd::Ix2>()
+        .unwrap();
+    Ok(z.dot(&l.t()).into_dyn())
+}
+
+/// The quantile function (probit) of the standard normal distribution.
+///
+/// Uses Acklam's rational approximation followed by a single Halley refinement
+/// step, which brings the absolute error well below 1e-9 across the whole domain.
+///
+/// * `p`: The probabilities, each strictly between 0 and 1.
+///
+/// * `z`: The z-score of each probability, or `QuantError::InvalidInput` when a
+///   probability is outside of (0, 1).
+pub fn inverse_cdf<F: ag::Float>(p: &ag::NdArray<F>) -> Result<ag::NdArray<F>, QuantError> {
+    if let Some(bad) = p.iter().find(|&&x| !(x > F::zero() && x < F::one())) {
+        return Err(QuantError::InvalidInput(format!(
+            "probability {} is not in (0, 1)",
+            bad
+        )));
+    }
+    Ok(p.mapv(|x| F::from(inverse_cdf_scalar(x.to_f64().unwrap())).unwrap()))
+}
+
+/// The standard normal quantile of a single probability in (0, 1).
+pub(crate) fn inverse_cdf_scalar(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.383577518672690e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    };
+    let x = if p < P_LOW {
+        tail((-2. * p.ln()).sqrt())
+    } else if p <= 1. - P_LOW {
+        let q = p - 0.5;
+        let rr = q * q;
+        (((((A[0] * rr + A[1]) * rr + A[2]) * rr + A[3]) * rr + A[4]) * rr + A[5]) * q
+            / (((((B[0] * rr + B[1]) * rr + B[2]) * rr + B[3]) * rr + B[4]) * rr + 1.)
+    } else {
+        -tail((-2. * (1. - p).ln()).sqrt())
+    };
+
+    // Halley step on cdf(x) - p.
+    let e = 0.5 * erfc(-x / std::f64::consts::SQRT_2) - p;
+    let u = e * (2. * std::f64::consts::PI).sqrt() * (0.5 * x * x).exp();
+    x - u / (1. + 0.5 * x * u)
+}
+
+/// The standard normal cdf of a single point.
+pub(crate) fn cdf_scalar(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// The cumulative distribution function of the standard bivariate normal
+/// distribution with correlation `rho`, e.g. P(X <= x, Y <= y).
+///
+/// Uses the Drezner-Wesolowsky (1990) method as refined by Genz (2004): for
+/// |ρ| < 0.925 Gauss-Legendre quadrature of the correlation integral after the
+/// substitution r = sin θ, with 6, 12 or 20 points as |ρ| grows, and for larger |ρ|
+/// an asymptotic expansion around ρ = ±1 plus a quadrature of its remainder. The
+/// absolute error is below 1e-14, well inside the 1e-7 of the original method.
+///
+/// Infinite bounds reduce to the marginal cdfs, and ρ = ±1 gives the exact
+/// degenerate probabilities P(X <= min(x, y)) and P(-y <= X <= x).
+///
+/// * `x`: The bound on the first variable.
+/// * `y`: The bound on the second variable.
+/// * `rho`: The correlation between the variables, between -1 and 1.
+///
+/// * `cdf`: The joint probability.
+pub fn bivariate_cdf<F: ag::Float>(x: F, y: F, rho: F) -> F {
+    let (x, y, rho) = (x.to_f64().unwrap(), y.to_f64().unwrap(), rho.to_f64().unwrap());
+    let cdf = if x == f64::NEG_INFINITY || y == f64::NEG_INFINITY {
+        0.
+    } else if x == f64::INFINITY {
+        cdf_scalar(y)
+    } else if y == f64::INFINITY {
+        cdf_scalar(x)
+    } else {
+        upper_orthant(-x, -y, rho.max(-1.).min(1.))
+    };
+    F::from(cdf).unwrap()
+}
+
+/// P(X > h, Y > k) for standard bivariate normals with correlation `r`, after Genz's
+/// BVND routine.
+fn upper_orthant(h: f64, k: f64, r: f64) -> f64 {
+    let two_pi = 2. * std::f64::consts::PI;
+    let n_points = if r.abs() < 0.3 {
+        6
+    } else if r.abs() < 0.75 {
+        12
+    } else {
+        20
+    };
+    let (nodes, weights) = gauss_legendre_nodes::<f64>(n_points);
+    let points = nodes.iter().zip(weights.iter());
+
+    if r.abs() < 0.925 {
+        let hk = h * k;
+        let hs = (h * h + k * k) / 2.;
+        let asr = r.asin();
+        let integral = points.fold(0., |acc, (&x, &w)| {
+            let sn = (asr * (1. + x) / 2.).sin();
+            acc + w * ((sn * hk - hs) / (1. - sn * sn)).exp()
+        });
+        return integral * asr / (2. * two_pi) + cdf_scalar(-h) * cdf_scalar(-k);
+    }
+
+    // Expand around the perfectly (anti) correlated case.
+    let k = if r < 0. { -k } else { k };
+    let hk = h * k;
+    let mut bvn = 0.;
+    if r.abs() < 1. {
+        let one_minus = (1. - r) * (1. + r);
+        let a = one_minus.sqrt();
+        let bs = (h - k) * (h - k);
+        let c = (4. - hk) / 8.;
+        let d = (12. - hk) / 16.;
+        let exponent = -(bs / one_minus + hk) / 2.;
+        if exponent > -100. {
+            let series = 1. - c * (bs - one_minus) * (1. - d * bs / 5.) / 3. + c * d * one_minus * one_minus / 5.;
+            bvn = a * exponent.exp() * series;
+        }
+        if -hk < 100. {
+            let b = bs.sqrt();
+            bvn -= (-hk / 2.).exp() * two_pi.sqrt() * cdf_scalar(-b / a) * b * (1. - c * bs * (1. - d * bs / 5.) / 3.);
+        }
+        let a = a / 2.;
+        bvn += points.fold(0., |acc, (&x, &w)| {
+            let xs = (a * (x + 1.)).powi(2);
+            let rs = (1. - xs).sqrt();
+            let exponent = -(bs / xs + hk) / 2.;
+            if exponent > -100. {
+                let remainder = (-hk * (1. - rs) / (2. * (1. + rs))).exp() / rs - (1. + c * xs * (1. + d * xs));
+                acc + a * w * exponent.exp() * remainder
+            } else {
+                acc
+            }
+        });
+        bvn = -bvn / two_pi;
+    }
+
+    if r > 0. {
+        bvn + cdf_scalar(-h.max(k))
+    } else if k > h {
+        // P(h < X < -k) with k already negated.
+        let band = if h < 0. { cdf_scalar(k) - cdf_scalar(h) } else { cdf_scalar(-h) - cdf_scalar(-k) };
+        band - bvn
+    } else {
+        -bvn
+    }
+}
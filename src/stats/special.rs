@@ -0,0 +1,51 @@
+use autograd as ag;
+use autograd::op::{ComputeContext, GradientContext, Op, OpError};
+use autograd::tensor_ops as math;
+
+use autograd::statrs::function::erf;
+
+/// The error function, evaluated elementwise as a differentiable tensor.
+///
+/// erf(x) = 2/√π ∫₀ˣ e^{-u²} du, with derivative 2/√π · e^{-x²}.
+///
+/// * `x`: The points at which to evaluate the error function.
+///
+/// * `erf`: The error function at each point.
+pub fn erf<'graph, A, F: ag::Float>(x: A) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    let x = x.as_ref();
+    ag::Tensor::builder(x.graph()).append_input(x, false).build(Erf)
+}
+
+/// The complementary error function `1 - erf(x)`, evaluated elementwise as a
+/// differentiable tensor.
+///
+/// * `x`: The points at which to evaluate the complementary error function.
+///
+/// * `erfc`: The complementary error function at each point.
+pub fn erfc<'graph, A, F: ag::Float>(x: A) -> ag::Tensor<'graph, F>
+where
+    A: AsRef<ag::Tensor<'graph, F>> + Copy,
+{
+    math::neg(erf(x)) + F::one()
+}
+
+struct Erf;
+
+impl<F: ag::Float> Op<F> for Erf {
+    fn compute(&self, ctx: &mut ComputeContext<F>) -> Result<(), OpError> {
+        let y = ctx.input(0).mapv(|x| F::from(erf::erf(x.to_f64().unwrap())).unwrap());
+        ctx.append_output(y);
+        Ok(())
+    }
+
+    fn grad(&self, ctx: &mut GradientContext<F>) {
+        let x = ctx.input(0);
+        let gy = ctx.output_grad();
+        let scale = F::from(2. / std::f64::consts::PI.sqrt()).unwrap();
+        let gx = math::exp(math::neg(math::square(x))) * scale * gy;
+        ctx.append_input_grad(Some(gx));
+    }
+}
@@ -0,0 +1,100 @@
+use autograd as ag;
+
+use autograd::statrs::function::beta::beta_reg;
+use autograd::statrs::function::gamma::ln_gamma;
+
+use crate::error::QuantError;
+use crate::stats::normal;
+
+/// The probability density function of Student's t distribution.
+///
+/// pdf(x) = Γ((ν + 1) / 2) / (√(νπ) Γ(ν / 2)) · (1 + x² / ν)^(-(ν + 1) / 2)
+///
+/// * `x`: The points at which to evaluate the density.
+/// * `df`: The positive degrees of freedom ν.
+///
+/// * `pdf`: The density at each point.
+pub fn pdf<F: ag::Float>(x: &ag::NdArray<F>, df: F) -> ag::NdArray<F> {
+    let df = df.to_f64().unwrap();
+    x.mapv(|x| F::from(pdf_scalar(x.to_f64().unwrap(), df)).unwrap())
+}
+
+/// The cumulative distribution function of Student's t distribution, computed
+/// from the regularized incomplete beta function.
+///
+/// * `x`: The points at which to evaluate the cdf.
+/// * `df`: The positive degrees of freedom ν.
+///
+/// * `cdf`: P(X <= x) for each point.
+pub fn cdf<F: ag::Float>(x: &ag::NdArray<F>, df: F) -> ag::NdArray<F> {
+    let df = df.to_f64().unwrap();
+    x.mapv(|x| F::from(cdf_scalar(x.to_f64().unwrap(), df)).unwrap())
+}
+
+/// The quantile function of Student's t distribution.
+///
+/// Starts from the normal quantile and refines it with Newton steps, falling back
+/// to bisection whenever a step leaves the current bracket.
+///
+/// * `p`: The probabilities, each strictly between 0 and 1.
+/// * `df`: The positive degrees of freedom ν.
+///
+/// * `t`: The quantile of each probability, or `QuantError::InvalidInput` when a
+///   probability is outside of (0, 1) or `df` is not positive.
+pub fn quantile<F: ag::Float>(p: &ag::NdArray<F>, df: F) -> Result<ag::NdArray<F>, QuantError> {
+    if !(df > F::zero()) {
+        return Err(QuantError::InvalidInput(format!(
+            "degrees of freedom {} must be positive",
+            df
+        )));
+    }
+    if let Some(bad) = p.iter().find(|&&x| !(x > F::zero() && x < F::one())) {
+        return Err(QuantError::InvalidInput(format!(
+            "probability {} is not in (0, 1)",
+            bad
+        )));
+    }
+    let df = df.to_f64().unwrap();
+    Ok(p.mapv(|x| F::from(quantile_scalar(x.to_f64().unwrap(), df)).unwrap()))
+}
+
+pub(crate) fn pdf_scalar(x: f64, df: f64) -> f64 {
+    let log_norm = ln_gamma(0.5 * (df + 1.)) - ln_gamma(0.5 * df) - 0.5 * (df * std::f64::consts::PI).ln();
+    (log_norm - 0.5 * (df + 1.) * (x * x / df).ln_1p()).exp()
+}
+
+pub(crate) fn cdf_scalar(x: f64, df: f64) -> f64 {
+    let tail = 0.5 * beta_reg(0.5 * df, 0.5, df / (df + x * x));
+    if x > 0. {
+        1. - tail
+    } else {
+        tail
+    }
+}
+
+/// The quantile of a single probability in (0, 1) for positive degrees of freedom.
+pub(crate) fn quantile_scalar(p: f64, df: f64) -> f64 {
+    let (mut lo, mut hi) = (-1., 1.);
+    while cdf_scalar(lo, df) > p {
+        lo *= 2.;
+    }
+    while cdf_scalar(hi, df) < p {
+        hi *= 2.;
+    }
+
+    let mut x = normal::inverse_cdf_scalar(p).max(lo).min(hi);
+    for _ in 0..100 {
+        let error = cdf_scalar(x, df) - p;
+        if error.abs() < 1e-15 {
+            break;
+        }
+        if error > 0. {
+            hi = x;
+        } else {
+            lo = x;
+        }
+        let step = x - error / pdf_scalar(x, df);
+        x = if step > lo && step < hi { step } else { 0.5 * (lo + hi) };
+    }
+    x
+}
@@ -0,0 +1,92 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+use crate::numerics::linalg::{least_squares, solve};
+
+/// The asymptotic critical values of the Dickey-Fuller t statistic of a regression with
+/// a constant, at the 1%, 5% and 10% levels (MacKinnon 2010).
+const ADF_CRITICAL_VALUES: [f64; 3] = [-3.43, -2.86, -2.57];
+
+/// The result of an augmented Dickey-Fuller test.
+///
+/// * `statistic`: The t statistic of the lagged level's coefficient.
+/// * `lags`: The number of lagged differences in the regression.
+/// * `critical_values`: The critical values of the statistic at the 1%, 5% and 10%
+///   levels.
+/// * `reject`: Whether the statistic rejects the unit root at the 5% level, i.e. the
+///   series looks stationary.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdfResult<F> {
+    pub statistic: F,
+    pub lags: usize,
+    pub critical_values: [F; 3],
+    pub reject: bool,
+}
+
+/// Test a series for a unit root with the augmented Dickey-Fuller test.
+///
+/// Fits the regression
+///
+/// Δyₜ = α + γ yₜ₋₁ + δ₁ Δyₜ₋₁ + ... + δₚ Δyₜ₋ₚ + εₜ
+///
+/// by least squares and returns the t statistic of γ. The null hypothesis is a unit
+/// root, γ = 0, against a stationary series, γ < 0, so the null is rejected when the
+/// statistic is below the critical value. The number of lags is `⌊(n - 1)^{1/3}⌋`,
+/// growing with the sample as Said and Dickey (1984) require.
+///
+/// * `series`: The observed values in time order.
+///
+/// * `result`: The test result, or `QuantError::InvalidInput` when the series is too
+///   short for the regression or the regression is singular, e.g. for a constant
+///   series.
+pub fn adf_test<F: ag::Float>(series: &ag::NdArray<F>) -> Result<AdfResult<F>, QuantError> {
+    let values = series.iter().cloned().collect::<Vec<_>>();
+    if values.len() < 2 {
+        return Err(QuantError::InvalidInput("need at least 2 values to test".to_string()));
+    }
+    let lags = ((values.len() - 1) as f64).cbrt().floor() as usize;
+    let differences = values.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
+
+    // One row per difference with `lags` earlier differences available.
+    let n_rows = differences.len().saturating_sub(lags);
+    let n_columns = lags + 2;
+    if n_rows <= n_columns {
+        return Err(QuantError::InvalidInput(format!(
+            "{} values are too few for a regression with {} lags",
+            values.len(),
+            lags
+        )));
+    }
+    let x = nd::Array2::from_shape_fn((n_rows, n_columns), |(row, column)| {
+        let t = row + lags;
+        match column {
+            0 => values[t],
+            1 => F::one(),
+            lag => differences[t + 1 - lag],
+        }
+    });
+    let y = nd::Array1::from(differences[lags..].to_vec());
+
+    let singular = || QuantError::InvalidInput("the regression is singular".to_string());
+    let beta = least_squares(&x, &y).ok_or_else(singular)?;
+    let residuals = &x.dot(&beta) - &y;
+    let dof = F::from(n_rows - n_columns).unwrap();
+    let residual_variance = residuals.iter().fold(F::zero(), |acc, &e| acc + e * e) / dof;
+    let mut unit = nd::Array1::<F>::zeros(n_columns);
+    unit[0] = F::one();
+    let inverse = solve(&x.t().dot(&x), &unit).ok_or_else(singular)?;
+    let statistic = beta[0] / (residual_variance * inverse[0]).sqrt();
+
+    let mut critical_values = [F::zero(); 3];
+    for (value, &critical) in critical_values.iter_mut().zip(ADF_CRITICAL_VALUES.iter()) {
+        *value = F::from(critical).unwrap();
+    }
+    Ok(AdfResult {
+        statistic,
+        lags,
+        critical_values,
+        reject: statistic < critical_values[1],
+    })
+}
@@ -0,0 +1,82 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+
+/// Calculate the sample autocorrelation function of a series,
+///
+/// ρ̂ₖ = Σₜ (yₜ - ȳ)(yₜ₊ₖ - ȳ) / Σₜ (yₜ - ȳ)²,
+///
+/// for lags `0` to `max_lag`. Every lag divides by the same full sample variance, the
+/// biased estimator that keeps the autocorrelations a positive definite sequence. A
+/// moving average of order `q` has autocorrelations near zero beyond lag `q`, within
+/// about `±2/√n` for white noise.
+///
+/// * `series`: The observed values in time order.
+/// * `max_lag`: The largest lag, less than the length of the series.
+///
+/// * `acf`: The `[max_lag + 1]` autocorrelations, starting with `1` at lag 0, or
+///   `QuantError::InvalidInput` when `max_lag` is not less than the length or the
+///   series is constant, which leaves the correlations undefined.
+pub fn acf<F: ag::Float>(series: &ag::NdArray<F>, max_lag: usize) -> Result<ag::NdArray<F>, QuantError> {
+    let values = series.iter().cloned().collect::<Vec<_>>();
+    let n = values.len();
+    if max_lag >= n {
+        return Err(QuantError::InvalidInput(format!(
+            "max lag {} needs more than {} values",
+            max_lag, n
+        )));
+    }
+    let mean = values.iter().fold(F::zero(), |acc, &y| acc + y) / F::from(n).unwrap();
+    let deviations = values.iter().map(|&y| y - mean).collect::<Vec<_>>();
+    let autocovariance = |lag: usize| {
+        deviations
+            .iter()
+            .zip(deviations[lag..].iter())
+            .fold(F::zero(), |acc, (&a, &b)| acc + a * b)
+    };
+    let variance = autocovariance(0);
+    if !(variance > F::zero()) {
+        return Err(QuantError::InvalidInput("the series is constant".to_string()));
+    }
+    Ok(nd::Array1::from_shape_fn(max_lag + 1, |lag| autocovariance(lag) / variance).into_dyn())
+}
+
+/// Calculate the sample partial autocorrelation function of a series for lags `0` to
+/// `max_lag`.
+///
+/// The partial autocorrelation at lag `k` is the last coefficient `φₖₖ` of the best
+/// linear predictor of `yₜ` from `yₜ₋₁, ..., yₜ₋ₖ`, found from the sample
+/// autocorrelations with the Durbin-Levinson recursion
+///
+/// φₖₖ = (ρ̂ₖ - Σⱼ φₖ₋₁,ⱼ ρ̂ₖ₋ⱼ) / (1 - Σⱼ φₖ₋₁,ⱼ ρ̂ⱼ), φₖⱼ = φₖ₋₁,ⱼ - φₖₖ φₖ₋₁,ₖ₋ⱼ.
+///
+/// An autoregression of order `p` has partial autocorrelations near zero beyond lag
+/// `p`, which with `acf` identifies the orders of ARMA models.
+///
+/// * `series`: The observed values in time order.
+/// * `max_lag`: The largest lag, less than the length of the series.
+///
+/// * `pacf`: The `[max_lag + 1]` partial autocorrelations, starting with `1` at lag
+///   0, or `QuantError::InvalidInput` when `max_lag` is not less than the length or
+///   the series is constant.
+pub fn pacf<F: ag::Float>(series: &ag::NdArray<F>, max_lag: usize) -> Result<ag::NdArray<F>, QuantError> {
+    let rho = acf(series, max_lag)?;
+    let mut partial = nd::Array1::<F>::zeros(max_lag + 1);
+    partial[0] = F::one();
+
+    // The coefficients φₖ₋₁,₁..ₖ₋₁ of the previous order's predictor.
+    let mut phi: Vec<F> = Vec::with_capacity(max_lag);
+    for k in 1..max_lag + 1 {
+        let numerator = (1..k).fold(rho[k], |acc, j| acc - phi[j - 1] * rho[k - j]);
+        let denominator = (1..k).fold(F::one(), |acc, j| acc - phi[j - 1] * rho[j]);
+        let phi_kk = numerator / denominator;
+        let next = (1..k)
+            .map(|j| phi[j - 1] - phi_kk * phi[k - j - 1])
+            .chain(std::iter::once(phi_kk))
+            .collect();
+        phi = next;
+        partial[k] = phi_kk;
+    }
+    Ok(partial.into_dyn())
+}
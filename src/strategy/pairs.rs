@@ -0,0 +1,89 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+
+/// The hedge ratio of a pair of assets, the least squares slope β of the regression
+///
+/// a = α + β b + ε,
+///
+/// so that the spread `a - βb` holds no exposure to `b`.
+///
+/// * `asset_a`: The prices of the asset that is held, in time order.
+/// * `asset_b`: The prices of the asset that hedges it, at the same times.
+///
+/// * `beta`: The units of `b` to sell per unit of `a`, `QuantError::ShapeMismatch` when
+///   the inputs' shapes differ, or `QuantError::InvalidInput` with fewer than 2 prices
+///   or a constant `b`.
+pub fn hedge_ratio<F: ag::Float>(asset_a: &ag::NdArray<F>, asset_b: &ag::NdArray<F>) -> Result<F, QuantError> {
+    QuantError::check_shape(asset_a.shape(), asset_b.shape())?;
+    if asset_a.len() < 2 {
+        return Err(QuantError::InvalidInput("need at least 2 prices".to_string()));
+    }
+    let n = F::from(asset_a.len()).unwrap();
+    let mean_a = asset_a.iter().fold(F::zero(), |acc, &a| acc + a) / n;
+    let mean_b = asset_b.iter().fold(F::zero(), |acc, &b| acc + b) / n;
+    let (covariance, variance) = asset_a
+        .iter()
+        .zip(asset_b.iter())
+        .fold((F::zero(), F::zero()), |(cov, var), (&a, &b)| {
+            (cov + (a - mean_a) * (b - mean_b), var + (b - mean_b) * (b - mean_b))
+        });
+    if variance == F::zero() {
+        return Err(QuantError::InvalidInput("the hedging asset's prices are constant".to_string()));
+    }
+    Ok(covariance / variance)
+}
+
+/// The rolling z-score of the spread between a pair of assets, the signal of a pairs
+/// trade.
+///
+/// The spread is `a - βb` with the `hedge_ratio` β of the whole series. Each value is
+/// the last spread of a window of `window` spreads, less the window's mean and divided
+/// by its sample standard deviation, so a trader can e.g. sell the spread above 2 and
+/// buy it below -2. A window of constant spreads scores 0.
+///
+/// * `asset_a`: The prices of the asset that is held, in time order.
+/// * `asset_b`: The prices of the asset that hedges it, at the same times.
+/// * `window`: The number of spreads in each window, at least 2.
+///
+/// * `zscores`: The `n - window + 1` z-scores of the windows ending at each price,
+///   `QuantError::ShapeMismatch` when the inputs' shapes differ, or
+///   `QuantError::InvalidInput` when the window is shorter than 2 or longer than the
+///   series, or the hedge ratio is undefined.
+pub fn spread_zscore<F: ag::Float>(
+    asset_a: &ag::NdArray<F>,
+    asset_b: &ag::NdArray<F>,
+    window: usize,
+) -> Result<ag::NdArray<F>, QuantError> {
+    QuantError::check_shape(asset_a.shape(), asset_b.shape())?;
+    if window < 2 || window > asset_a.len() {
+        return Err(QuantError::InvalidInput(format!(
+            "window {} must be between 2 and the {} prices",
+            window,
+            asset_a.len()
+        )));
+    }
+    let beta = hedge_ratio(asset_a, asset_b)?;
+    let spread = asset_a
+        .iter()
+        .zip(asset_b.iter())
+        .map(|(&a, &b)| a - beta * b)
+        .collect::<Vec<_>>();
+
+    let n = F::from(window).unwrap();
+    let zscores = spread
+        .windows(window)
+        .map(|values| {
+            let mean = values.iter().fold(F::zero(), |acc, &s| acc + s) / n;
+            let sum_squares = values.iter().fold(F::zero(), |acc, &s| acc + (s - mean).powi(2));
+            let std = (sum_squares / (n - F::one())).sqrt();
+            if std > F::zero() {
+                (values[window - 1] - mean) / std
+            } else {
+                F::zero()
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok(nd::Array1::from(zscores).into_dyn())
+}
@@ -0,0 +1,2 @@
+pub mod returns;
+pub mod volatility;
@@ -0,0 +1,39 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+
+/// The log returns `ln(pₜ / pₜ₋₁)` of a price series.
+///
+/// * `prices`: The positive prices in time order.
+///
+/// * `returns`: The `n - 1` log returns between consecutive prices, or
+///   `QuantError::InvalidInput` when a price is not positive.
+pub fn log_returns<F: ag::Float>(prices: &ag::NdArray<F>) -> Result<ag::NdArray<F>, QuantError> {
+    if let Some(price) = prices.iter().find(|p| !(**p > F::zero())) {
+        return Err(QuantError::InvalidInput(format!(
+            "log returns need positive prices, got {}",
+            price
+        )));
+    }
+    Ok(consecutive(prices, |previous, current| (current / previous).ln()))
+}
+
+/// The simple returns `pₜ / pₜ₋₁ - 1` of a price series.
+///
+/// * `prices`: The prices in time order.
+///
+/// * `returns`: The `n - 1` simple returns between consecutive prices.
+pub fn simple_returns<F: ag::Float>(prices: &ag::NdArray<F>) -> ag::NdArray<F> {
+    consecutive(prices, |previous, current| current / previous - F::one())
+}
+
+/// Apply `f` to each pair of consecutive values of a series.
+fn consecutive<F: ag::Float>(series: &ag::NdArray<F>, f: impl Fn(F, F) -> F) -> ag::NdArray<F> {
+    let values = series
+        .iter()
+        .zip(series.iter().skip(1))
+        .map(|(&previous, &current)| f(previous, current))
+        .collect::<Vec<_>>();
+    nd::Array1::from(values).into_dyn()
+}
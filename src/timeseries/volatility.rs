@@ -0,0 +1,43 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use crate::error::QuantError;
+
+/// The annualized realized volatility over a sliding window of returns.
+///
+/// Each value is the sample standard deviation of `window` consecutive returns,
+/// scaled by `√periods_per_year`.
+///
+/// * `returns`: The returns per period as decimal, in time order.
+/// * `window`: The number of returns in each window, at least 2.
+/// * `periods_per_year`: The number of return periods in a year, e.g. `252` for daily
+///   returns.
+///
+/// * `volatility`: The `n - window + 1` volatilities of the windows ending at each
+///   return, or `QuantError::InvalidInput` when the window is shorter than 2 or longer
+///   than the series.
+pub fn rolling_volatility<F: ag::Float>(
+    returns: &ag::NdArray<F>,
+    window: usize,
+    periods_per_year: F,
+) -> Result<ag::NdArray<F>, QuantError> {
+    if window < 2 || window > returns.len() {
+        return Err(QuantError::InvalidInput(format!(
+            "window {} must be between 2 and the {} returns",
+            window,
+            returns.len()
+        )));
+    }
+    let returns = returns.iter().cloned().collect::<Vec<_>>();
+    let n = F::from(window).unwrap();
+    let annualize = periods_per_year.sqrt();
+    let volatility = returns
+        .windows(window)
+        .map(|values| {
+            let mean = values.iter().fold(F::zero(), |acc, &r| acc + r) / n;
+            let sum_squares = values.iter().fold(F::zero(), |acc, &r| acc + (r - mean).powi(2));
+            (sum_squares / (n - F::one())).sqrt() * annualize
+        })
+        .collect::<Vec<_>>();
+    Ok(nd::Array1::from(volatility).into_dyn())
+}
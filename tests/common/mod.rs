@@ -0,0 +1,15 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::options::black_scholes::BlackScholesPricingModel;
+use rquant::options::model::{OptionPricingModel, OptionType};
+
+/// The Black-Scholes price of a single european option.
+pub fn black_scholes(ty: OptionType, s: f64, k: f64, vol: f64, q: f64, r: f64, t: f64) -> f64 {
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (s, k, vol, q) = (tensor(s), tensor(k), tensor(vol), tensor(q));
+        BlackScholesPricingModel::price(ty, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()[0]
+    })
+}
@@ -1,3 +1,60 @@
+mod test_acf;
+mod test_adf;
+mod test_asian_options;
+mod test_bachelier;
+mod test_barrier_options;
+mod test_basket;
+mod test_binary_options;
 mod test_binomial_model;
+mod test_black76;
 mod test_black_scholes_model;
-mod test_normal_distribution;
\ No newline at end of file
+mod test_bollinger;
+mod test_bond;
+mod test_caplet;
+mod test_compound;
+mod test_csv;
+mod test_dupire;
+mod test_ewma;
+mod test_exchange;
+mod test_features;
+mod test_gap;
+mod test_garch;
+mod test_gbm;
+mod test_heston;
+mod test_integrate;
+mod test_interp;
+mod test_lognormal_distribution;
+mod test_lookback;
+mod test_lsm;
+mod test_macd;
+mod test_merton;
+mod test_metrics;
+mod test_monte_carlo;
+mod test_moving_average;
+mod test_nelson_siegel;
+mod test_normal_distribution;
+mod test_option_contract;
+mod test_ou;
+mod test_pairs;
+mod test_pde;
+mod test_portfolio;
+mod test_power;
+mod test_prelude;
+mod test_quanto;
+mod test_returns;
+mod test_rolling_volatility;
+mod test_root;
+mod test_rsi;
+mod test_sabr;
+mod test_serde;
+mod test_short_rate_models;
+mod test_simple_api;
+mod test_special_functions;
+mod test_strategy;
+mod test_student_t_distribution;
+mod test_svi;
+mod test_swap;
+mod test_trinomial;
+mod test_var;
+mod test_vol_surface;
+mod test_zero_curve;
@@ -0,0 +1,72 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+
+use rquant::error::QuantError;
+use rquant::stats::normal;
+use rquant::stats::timeseries::*;
+
+/// The series `yₜ = φ yₜ₋₁ + εₜ` from `y₀ = 0` with standard normal shocks.
+fn ar1(phi: f64, n: usize, seed: u64) -> ag::NdArray<f64> {
+    let shocks = normal::sample(&[n], 0., 1., &mut StdRng::seed_from_u64(seed));
+    let mut y = 0.;
+    let values = shocks
+        .iter()
+        .map(|e| {
+            y = phi * y + e;
+            y
+        })
+        .collect::<Vec<_>>();
+    nd::Array1::from(values).into_dyn()
+}
+
+#[test]
+fn test_small_series_by_hand() {
+    let series = nd::arr1(&[1., 2., 3., 4.]).into_dyn();
+    let rho = acf(&series, 2).unwrap();
+    let expected: [f64; 3] = [1., 0.25, -0.3];
+    assert!(rho.iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < 1e-12), "{:?}", rho);
+
+    let partial = pacf(&series, 2).unwrap();
+    let expected: [f64; 3] = [1., 0.25, -0.3625 / 0.9375];
+    assert!(partial.iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < 1e-12), "{:?}", partial);
+}
+
+#[test]
+fn test_white_noise_is_uncorrelated() {
+    let n = 2000;
+    let noise = ar1(0., n, 96);
+    let bound = 4. / (n as f64).sqrt();
+    let rho = acf(&noise, 10).unwrap();
+    let partial = pacf(&noise, 10).unwrap();
+    assert_eq!(rho.shape(), &[11]);
+    assert_eq!(rho[0], 1.);
+    assert!(rho.iter().skip(1).all(|c| c.abs() < bound), "{:?}", rho);
+    assert!(partial.iter().skip(1).all(|c| c.abs() < bound), "{:?}", partial);
+}
+
+#[test]
+fn test_ar1_decays_geometrically() {
+    let (phi, n) = (0.7f64, 20000);
+    let series = ar1(phi, n, 97);
+    let rho = acf(&series, 10).unwrap();
+    for lag in 1..6 {
+        assert!((rho[lag] - phi.powi(lag as i32)).abs() < 0.05, "lag {}: {:?}", lag, rho);
+    }
+
+    // Only the first lag predicts an AR(1) beyond the earlier ones.
+    let partial = pacf(&series, 10).unwrap();
+    let bound = 4. / (n as f64).sqrt();
+    assert!((partial[1] - phi).abs() < 0.05, "{:?}", partial);
+    assert!(partial.iter().skip(2).all(|c| c.abs() < bound), "{:?}", partial);
+}
+
+#[test]
+fn test_rejects_bad_input() {
+    let constant = nd::arr1(&[2.; 10]).into_dyn();
+    assert!(matches!(acf(&constant, 3), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(pacf(&constant, 3), Err(QuantError::InvalidInput(_))));
+    let short = nd::arr1(&[1., 2., 3.]).into_dyn();
+    assert!(matches!(acf(&short, 3), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(pacf(&short, 3), Err(QuantError::InvalidInput(_))));
+}
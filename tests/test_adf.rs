@@ -0,0 +1,43 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+
+use rquant::error::QuantError;
+use rquant::stats::normal;
+use rquant::stats::tests::*;
+
+/// The series `yₜ = c + φ yₜ₋₁ + εₜ` from `y₀ = 0` with standard normal shocks.
+fn ar1(c: f64, phi: f64, n: usize, seed: u64) -> ag::NdArray<f64> {
+    let shocks = normal::sample(&[n], 0., 1., &mut StdRng::seed_from_u64(seed));
+    let mut y = 0.;
+    let values = shocks
+        .iter()
+        .map(|e| {
+            y = c + phi * y + e;
+            y
+        })
+        .collect::<Vec<_>>();
+    nd::Array1::from(values).into_dyn()
+}
+
+#[test]
+fn test_random_walk_keeps_unit_root() {
+    let result = adf_test(&ar1(0.2, 1., 500, 82)).unwrap();
+    assert_eq!(result.lags, 7);
+    assert_eq!(result.critical_values, [-3.43, -2.86, -2.57]);
+    assert!(!result.reject, "statistic {}", result.statistic);
+}
+
+#[test]
+fn test_stationary_ar1_rejects_unit_root() {
+    let result = adf_test(&ar1(0.2, 0.5, 500, 83)).unwrap();
+    assert!(result.reject);
+    assert!(result.statistic < result.critical_values[0], "statistic {}", result.statistic);
+}
+
+#[test]
+fn test_adf_rejects_bad_input() {
+    assert!(matches!(adf_test(&nd::arr1(&[1., 2., 3.]).into_dyn()), Err(QuantError::InvalidInput(_))));
+    let constant = nd::Array1::from(vec![1.; 50]).into_dyn();
+    assert!(matches!(adf_test(&constant), Err(QuantError::InvalidInput(_))));
+}
@@ -0,0 +1,165 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+use autograd::tensor_ops as math;
+
+use common::black_scholes;
+use rquant::error::QuantError;
+use rquant::options::asian::*;
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[test]
+fn test_single_step_arithmetic_asian_is_european() {
+    for ty in [OptionType::Call, OptionType::Put] {
+        let estimate = price_arithmetic_asian(
+            ty, 100., 100., 0.2, 0.01, 0.05, 1., 1, 200_000, false, false, &mut StdRng::seed_from_u64(7),
+        )
+        .unwrap();
+        let european = black_scholes(ty, 100., 100., 0.2, 0.01, 0.05, 1.);
+        assert!(
+            (estimate.price - european).abs() < 4. * estimate.std_error,
+            "asian {} +/- {} vs european {}",
+            estimate.price,
+            estimate.std_error,
+            european
+        );
+    }
+}
+
+#[test]
+fn test_averaging_lowers_the_call_price() {
+    let asian = price_arithmetic_asian(
+        OptionType::Call, 100., 100., 0.3, 0., 0.05, 1., 52, 20_000, false, false, &mut StdRng::seed_from_u64(11),
+    )
+    .unwrap();
+    let european = black_scholes(OptionType::Call, 100., 100., 0.3, 0., 0.05, 1.);
+    assert!(asian.price + 4. * asian.std_error < european);
+    assert!(asian.std_error > 0.);
+}
+
+#[test]
+fn test_geometric_asian_below_european() {
+    let r = 0.05;
+    let t = 1.;
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |xs: &[f64]| math::convert_to_tensor(nd::arr1(xs).into_dyn(), ctx);
+        let s = tensor(&[100., 100., 100.]);
+        let k = tensor(&[90., 100., 110.]);
+        let vol = tensor(&[0.25, 0.25, 0.25]);
+        let q = tensor(&[0.02, 0.02, 0.02]);
+
+        let asian = price_geometric_asian(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap();
+        let european = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t)
+            .eval(ctx)
+            .unwrap();
+        for (a, e) in asian.iter().zip(european.iter()) {
+            assert!(*a > 0. && a < e, "geometric asian {} vs european {}", a, e);
+        }
+    });
+}
+
+#[test]
+fn test_geometric_asian_bounds_arithmetic_asian() {
+    let arithmetic = price_arithmetic_asian(
+        OptionType::Call, 100., 100., 0.3, 0., 0.05, 1., 250, 20_000, false, false, &mut StdRng::seed_from_u64(13),
+    )
+    .unwrap();
+    let geometric = ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        price_geometric_asian(OptionType::Call, &tensor(100.), &tensor(100.), &tensor(0.3), &tensor(0.), 0.05, 1.)
+            .eval(ctx)
+            .unwrap()[0]
+    });
+    assert!(geometric < arithmetic.price + 4. * arithmetic.std_error);
+}
+
+#[test]
+fn test_antithetic_paths_reduce_standard_error() {
+    let plain = price_arithmetic_asian(
+        OptionType::Call, 100., 100., 0.25, 0., 0.05, 1., 12, 20_000, false, false, &mut StdRng::seed_from_u64(5),
+    )
+    .unwrap();
+    let antithetic = price_arithmetic_asian(
+        OptionType::Call, 100., 100., 0.25, 0., 0.05, 1., 12, 20_000, true, false, &mut StdRng::seed_from_u64(5),
+    )
+    .unwrap();
+    assert!(antithetic.std_error < plain.std_error);
+    assert!((antithetic.price - plain.price).abs() < 4. * plain.std_error);
+}
+
+#[test]
+fn test_control_variate_reduces_standard_error() {
+    let plain = price_arithmetic_asian(
+        OptionType::Call, 100., 100., 0.25, 0., 0.05, 1., 12, 20_000, false, false, &mut StdRng::seed_from_u64(19),
+    )
+    .unwrap();
+    let controlled = price_arithmetic_asian(
+        OptionType::Call, 100., 100., 0.25, 0., 0.05, 1., 12, 20_000, false, true, &mut StdRng::seed_from_u64(19),
+    )
+    .unwrap();
+    assert!(controlled.std_error < plain.std_error / 10., "{:?} vs {:?}", controlled, plain);
+    assert!((controlled.price - plain.price).abs() < 4. * plain.std_error);
+}
+
+#[test]
+fn test_single_date_discrete_geometric_asian_is_european() {
+    for ty in [OptionType::Call, OptionType::Put] {
+        let asian = ag::run(|ctx: &mut ag::Context<f64>| {
+            let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+            price_discrete_geometric_asian(ty, &tensor(100.), &tensor(95.), &tensor(0.25), &tensor(0.01), 0.05, 1., 1)
+                .unwrap()
+                .eval(ctx)
+                .unwrap()[0]
+        });
+        let european = black_scholes(ty, 100., 95., 0.25, 0.01, 0.05, 1.);
+        assert!((asian - european).abs() < 1e-12, "{} vs {}", asian, european);
+    }
+}
+
+#[test]
+fn test_discrete_geometric_asian_approaches_continuous_monitoring() {
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (s, k, vol, q) = (tensor(100.), tensor(100.), tensor(0.25), tensor(0.));
+        let continuous = price_geometric_asian(OptionType::Call, &s, &k, &vol, &q, 0.05, 1.).eval(ctx).unwrap()[0];
+        let monthly = price_discrete_geometric_asian(OptionType::Call, &s, &k, &vol, &q, 0.05, 1., 12).unwrap();
+        let dense = price_discrete_geometric_asian(OptionType::Call, &s, &k, &vol, &q, 0.05, 1., 100_000).unwrap();
+        let (monthly, dense) = (monthly.eval(ctx).unwrap()[0], dense.eval(ctx).unwrap()[0]);
+        // Monitoring from t / 12 rather than 0 averages later, more dispersed prices.
+        assert!(monthly > continuous + 0.1, "{} vs {}", monthly, continuous);
+        assert!((dense - continuous).abs() < 1e-3, "{} vs {}", dense, continuous);
+    });
+}
+
+#[test]
+fn test_arithmetic_asian_without_paths_is_nan() {
+    for control_variate in [false, true] {
+        let mut rng = StdRng::seed_from_u64(1);
+        let (s, k, vol, q, r, t) = (100., 100., 0.25, 0., 0.05, 1.);
+        let estimate =
+            price_arithmetic_asian(OptionType::Call, s, k, vol, q, r, t, 12, 0, false, control_variate, &mut rng)
+                .unwrap();
+        assert!(estimate.price.is_nan() && estimate.std_error.is_nan(), "{:?}", estimate);
+    }
+}
+
+#[test]
+fn test_asian_options_without_averaging_dates_are_rejected() {
+    let (s, k, vol, q, r, t) = (100., 100., 0.25, 0., 0.05, 1.);
+    for control_variate in [false, true] {
+        let mut rng = StdRng::seed_from_u64(1);
+        let estimate =
+            price_arithmetic_asian(OptionType::Call, s, k, vol, q, r, t, 0, 1_000, false, control_variate, &mut rng);
+        assert!(matches!(estimate, Err(QuantError::InvalidInput(_))), "{:?}", estimate);
+    }
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (s, k, vol, q) = (tensor(s), tensor(k), tensor(vol), tensor(q));
+        let price = price_discrete_geometric_asian(OptionType::Call, &s, &k, &vol, &q, r, t, 0);
+        assert!(matches!(price, Err(QuantError::InvalidInput(_))));
+    });
+}
@@ -0,0 +1,54 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::options::bachelier::*;
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+#[test]
+fn test_bachelier_matches_black_scholes_at_the_money_for_small_variance() {
+    let t = 0.25;
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100., 2500.]);
+        let k = tensor(ctx, &[100., 2500.]);
+        let vol = tensor(ctx, &[0.1, 0.05]);
+        // The normal volatility of an at the money option is about the lognormal one times the price.
+        let normal_vol = tensor(ctx, &[10., 125.]);
+        let q = tensor(ctx, &[0., 0.]);
+
+        let lognormal = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, 0., t)
+            .eval(ctx)
+            .unwrap();
+        let normal = price_call_bachelier(&s, &k, &normal_vol, 0., t).eval(ctx).unwrap();
+        for (n, l) in normal.iter().zip(lognormal.iter()) {
+            assert!(((n - l) / l).abs() < 2e-4, "bachelier {} vs black-scholes {}", n, l);
+        }
+    });
+}
+
+#[test]
+fn test_bachelier_handles_negative_forwards() {
+    let r = 0.01;
+    let t = 2.;
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let f = tensor(ctx, &[-0.005, -0.005, 0.002]);
+        let k = tensor(ctx, &[-0.002, -0.01, -0.001]);
+        let vol = tensor(ctx, &[0.006, 0.006, 0.006]);
+
+        let call = price_call_bachelier(&f, &k, &vol, r, t).eval(ctx).unwrap();
+        let put = price_put_bachelier(&f, &k, &vol, r, t).eval(ctx).unwrap();
+        let forward = [-0.005, -0.005, 0.002];
+        let strike = [-0.002, -0.01, -0.001];
+        for i in 0..3 {
+            assert!(call[i].is_finite() && call[i] > 0. && put[i] > 0.);
+            // c - p = e^{-rt} (f - k)
+            let parity: f64 = (-r * t).exp() * (forward[i] - strike[i]);
+            assert!((call[i] - put[i] - parity).abs() < 1e-15);
+        }
+    });
+}
@@ -0,0 +1,81 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::options::barrier::*;
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+fn assert_close(actual: &ag::NdArray<f64>, expected: &ag::NdArray<f64>, tol: f64) {
+    assert_eq!(actual.shape(), expected.shape());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < tol, "expected {}, got {}", e, a);
+    }
+}
+
+#[test]
+fn test_down_and_out_call_reference_prices() {
+    // Haug's barrier table inputs, without the rebate.
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100., 100., 100.]);
+        let k = tensor(ctx, &[90., 100., 110.]);
+        let h = tensor(ctx, &[95., 95., 95.]);
+        let vol = tensor(ctx, &[0.25, 0.25, 0.25]);
+        let q = tensor(ctx, &[0.04, 0.04, 0.04]);
+
+        let price = price_barrier(OptionType::Call, BarrierType::DownAndOut, &s, &k, &h, &vol, &q, 0.08, 0.5);
+        let expected = nd::arr1(&[6.744729727765325, 4.51259860782368, 2.59601977294607]).into_dyn();
+        assert_close(&price.eval(ctx).unwrap(), &expected, 1e-8);
+    });
+}
+
+#[test]
+fn test_up_and_out_put_reference_prices() {
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100., 100., 100.]);
+        let k = tensor(ctx, &[90., 100., 110.]);
+        let h = tensor(ctx, &[105., 105., 105.]);
+        let vol = tensor(ctx, &[0.25, 0.25, 0.25]);
+        let q = tensor(ctx, &[0.04, 0.04, 0.04]);
+
+        let price = price_barrier(OptionType::Put, BarrierType::UpAndOut, &s, &k, &h, &vol, &q, 0.08, 0.5);
+        let expected = nd::arr1(&[1.4306061857827927, 3.147878725984981, 5.1733731357261235]).into_dyn();
+        assert_close(&price.eval(ctx).unwrap(), &expected, 1e-8);
+    });
+}
+
+#[test]
+fn test_in_out_parity() {
+    let r = 0.05;
+    let t = 0.75;
+    let cases = [
+        (BarrierType::DownAndIn, BarrierType::DownAndOut, 90.),
+        (BarrierType::UpAndIn, BarrierType::UpAndOut, 115.),
+    ];
+
+    for ty in [OptionType::Call, OptionType::Put] {
+        for (knock_in, knock_out, barrier) in cases.iter() {
+            ag::run(|ctx: &mut ag::Context<f64>| {
+                // Strikes on both sides of the barrier exercise every branch.
+                let s = tensor(ctx, &[100., 100., 100., 100.]);
+                let k = tensor(ctx, &[80., 95., 105., 120.]);
+                let h = tensor(ctx, &[*barrier; 4]);
+                let vol = tensor(ctx, &[0.3; 4]);
+                let q = tensor(ctx, &[0.01; 4]);
+
+                let knocked_in = price_barrier(ty, *knock_in, &s, &k, &h, &vol, &q, r, t);
+                let knocked_out = price_barrier(ty, *knock_out, &s, &k, &h, &vol, &q, r, t);
+                let vanilla = BlackScholesPricingModel::price(ty, &s, &k, &vol, &q, r, t);
+                assert_close(
+                    &(knocked_in + knocked_out).eval(ctx).unwrap(),
+                    &vanilla.eval(ctx).unwrap(),
+                    1e-9,
+                );
+            });
+        }
+    }
+}
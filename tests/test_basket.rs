@@ -0,0 +1,75 @@
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+
+use rquant::error::QuantError;
+use rquant::options::basket::*;
+use rquant::options::simple::bs_call_price;
+
+#[test]
+fn test_single_asset_basket_is_a_vanilla_call() {
+    let s = nd::arr1(&[100.]).into_dyn();
+    let weights = nd::arr1(&[1.]).into_dyn();
+    let vol = nd::arr1(&[0.25]).into_dyn();
+    let correlation = nd::arr2(&[[1.]]).into_dyn();
+
+    let estimate = price_basket_call(
+        s.view(), weights.view(), vol.view(), &correlation, 105., 0.03, 1., 200_000, &mut StdRng::seed_from_u64(3),
+    )
+    .unwrap();
+    let vanilla = bs_call_price(100., 105., 1., 0.25, 0.03);
+    assert!((estimate.price - vanilla).abs() < 4. * estimate.std_error, "{:?} vs {}", estimate, vanilla);
+}
+
+#[test]
+fn test_perfectly_correlated_basket_is_a_scaled_vanilla() {
+    // Two shares each of identical, perfectly correlated stocks move as one at 4x.
+    let s = nd::arr1(&[50., 50.]).into_dyn();
+    let weights = nd::arr1(&[2., 2.]).into_dyn();
+    let vol = nd::arr1(&[0.2, 0.2]).into_dyn();
+    let correlation = nd::arr2(&[[1., 0.999999], [0.999999, 1.]]).into_dyn();
+
+    let estimate = price_basket_call(
+        s.view(), weights.view(), vol.view(), &correlation, 200., 0.05, 0.5, 100_000, &mut StdRng::seed_from_u64(9),
+    )
+    .unwrap();
+    let vanilla = 4. * bs_call_price(50., 50., 0.5, 0.2, 0.05);
+    assert!((estimate.price - vanilla).abs() < 4. * estimate.std_error + 1e-3, "{:?} vs {}", estimate, vanilla);
+}
+
+#[test]
+fn test_diversification_lowers_the_basket_price() {
+    let s = nd::arr1(&[100., 100.]).into_dyn();
+    let weights = nd::arr1(&[0.5, 0.5]).into_dyn();
+    let vol = nd::arr1(&[0.3, 0.3]).into_dyn();
+    let price = |rho: f64| {
+        let correlation = nd::arr2(&[[1., rho], [rho, 1.]]).into_dyn();
+        price_basket_call(
+            s.view(), weights.view(), vol.view(), &correlation, 100., 0.02, 1., 50_000, &mut StdRng::seed_from_u64(1),
+        )
+        .unwrap()
+        .price
+    };
+    assert!(price(0.) < price(0.9));
+}
+
+#[test]
+fn test_basket_rejects_invalid_inputs() {
+    let s = nd::arr1(&[100., 100.]).into_dyn();
+    let vol = nd::arr1(&[0.3, 0.3]).into_dyn();
+    let short = nd::arr1(&[1.]).into_dyn();
+    let correlation = nd::arr2(&[[1., 0.5], [0.5, 1.]]).into_dyn();
+    let mut rng = StdRng::seed_from_u64(0);
+    assert!(matches!(
+        price_basket_call(s.view(), short.view(), vol.view(), &correlation, 100., 0.02, 1., 10, &mut rng),
+        Err(QuantError::ShapeMismatch { .. })
+    ));
+    let invalid = nd::arr2(&[[1., 2.], [2., 1.]]).into_dyn();
+    assert_eq!(
+        price_basket_call(s.view(), vol.view(), vol.view(), &invalid, 100., 0.02, 1., 10, &mut rng),
+        Err(QuantError::NotPositiveDefinite)
+    );
+    assert!(matches!(
+        price_basket_call(s.view(), vol.view(), vol.view(), &correlation, 100., 0.02, 1., 0, &mut rng),
+        Err(QuantError::InvalidInput(_))
+    ));
+}
@@ -0,0 +1,61 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::options::binary::*;
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+fn assert_close(actual: &ag::NdArray<f64>, expected: &ag::NdArray<f64>, tol: f64) {
+    assert_eq!(actual.shape(), expected.shape());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < tol, "expected {}, got {}", e, a);
+    }
+}
+
+#[test]
+fn test_binaries_reconstruct_vanilla_options() {
+    let r = 0.05;
+    let t = 0.5;
+    let strikes = [90., 100., 110.];
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100., 100., 100.]);
+        let k = tensor(ctx, &strikes);
+        let vol = tensor(ctx, &[0.25, 0.25, 0.25]);
+        let q = tensor(ctx, &[0.02, 0.02, 0.02]);
+
+        // A vanilla call is long an asset-or-nothing call and short `k` cash-or-nothing calls.
+        let call = price_asset_or_nothing(OptionType::Call, &s, &k, &vol, &q, r, t)
+            - price_cash_or_nothing(OptionType::Call, &s, &k, &vol, &q, r, t, 1.) * k;
+        let put = price_cash_or_nothing(OptionType::Put, &s, &k, &vol, &q, r, t, 1.) * k
+            - price_asset_or_nothing(OptionType::Put, &s, &k, &vol, &q, r, t);
+
+        let vanilla_call = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t);
+        let vanilla_put = BlackScholesPricingModel::price(OptionType::Put, &s, &k, &vol, &q, r, t);
+        assert_close(&call.eval(ctx).unwrap(), &vanilla_call.eval(ctx).unwrap(), 1e-10);
+        assert_close(&put.eval(ctx).unwrap(), &vanilla_put.eval(ctx).unwrap(), 1e-10);
+    });
+}
+
+#[test]
+fn test_cash_or_nothing_call_and_put_pay_discounted_cash() {
+    let r: f64 = 0.03;
+    let t: f64 = 2.;
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[80., 100., 120.]);
+        let k = tensor(ctx, &[100., 100., 100.]);
+        let vol = tensor(ctx, &[0.3, 0.3, 0.3]);
+        let q = tensor(ctx, &[0., 0., 0.]);
+
+        let total = price_cash_or_nothing(OptionType::Call, &s, &k, &vol, &q, r, t, 10.)
+            + price_cash_or_nothing(OptionType::Put, &s, &k, &vol, &q, r, t, 10.);
+        let expected = nd::Array1::from_elem(3, 10. * (-r * t).exp()).into_dyn();
+        assert_close(&total.eval(ctx).unwrap(), &expected, 1e-10);
+    });
+}
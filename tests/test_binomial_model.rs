@@ -0,0 +1,61 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use common::black_scholes;
+use rquant::options::binomial::*;
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[test]
+fn test_american_call_without_dividends_is_european() {
+    for &k in [90., 100., 110.].iter() {
+        let american = price_american_call(100., k, 0.25, 0., 0.05, 1., 1000);
+        let european = black_scholes(OptionType::Call, 100., k, 0.25, 0., 0.05, 1.);
+        assert!((american - european).abs() < 1e-2, "american {} vs european {}", american, european);
+    }
+}
+
+#[test]
+fn test_american_put_exceeds_european() {
+    for &k in [90., 100., 110.].iter() {
+        let american = price_american_put(100., k, 0.25, 0., 0.05, 1., 1000);
+        let european = black_scholes(OptionType::Put, 100., k, 0.25, 0., 0.05, 1.);
+        assert!(american > european, "american {} vs european {}", american, european);
+    }
+}
+
+#[test]
+fn test_american_put_reference_price() {
+    // S = 100, K = 100, t = 1, r = 0.05, vol = 0.2 converges to roughly 6.09.
+    let american = price_american_put(100., 100., 0.2, 0., 0.05, 1., 2000);
+    assert!((american - 6.09).abs() < 1e-2, "american put {}", american);
+}
+
+#[test]
+fn test_model_rho_matches_black_scholes() {
+    // Without dividends the american call is european.
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 100., 0.2, 0., 0.05, 1.);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (s, k, vol, q) = (tensor(s), tensor(k), tensor(vol), tensor(q));
+        let binomial = BinomialPricingModel::rho(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()[0];
+        let exact = BlackScholesPricingModel::rho(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()[0];
+        assert!((binomial - exact).abs() < 0.5, "{} vs {}", binomial, exact);
+    });
+}
+
+#[test]
+fn test_model_gamma_matches_black_scholes() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 100., 0.2, 0., 0.05, 1.);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (s, k, vol, q) = (tensor(s), tensor(k), tensor(vol), tensor(q));
+        let binomial = BinomialPricingModel::gamma(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()[0];
+        let exact = BlackScholesPricingModel::gamma(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()[0];
+        assert!((binomial - exact).abs() < 2e-3, "{} vs {}", binomial, exact);
+    });
+}
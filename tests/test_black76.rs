@@ -0,0 +1,59 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::options::black76::*;
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+fn assert_close(actual: &ag::NdArray<f64>, expected: &ag::NdArray<f64>, tol: f64) {
+    assert_eq!(actual.shape(), expected.shape());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < tol, "expected {}, got {}", e, a);
+    }
+}
+
+#[test]
+fn test_black76_on_the_forward_is_black_scholes() {
+    let r: f64 = 0.04;
+    let t: f64 = 1.5;
+    let spot = [90., 100., 110.];
+    let forward = spot.iter().map(|s| s * (r * t).exp()).collect::<Vec<_>>();
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &spot);
+        let f = tensor(ctx, &forward);
+        let k = tensor(ctx, &[100., 100., 100.]);
+        let vol = tensor(ctx, &[0.2, 0.25, 0.3]);
+        let q = tensor(ctx, &[0., 0., 0.]);
+
+        let call = price_call_black76(&f, &k, &vol, r, t).eval(ctx).unwrap();
+        let bs_call = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap();
+        assert_close(&call, &bs_call, 1e-10);
+
+        let put = price_put_black76(&f, &k, &vol, r, t).eval(ctx).unwrap();
+        let bs_put = BlackScholesPricingModel::price(OptionType::Put, &s, &k, &vol, &q, r, t).eval(ctx).unwrap();
+        assert_close(&put, &bs_put, 1e-10);
+    });
+}
+
+#[test]
+fn test_black76_put_call_parity() {
+    let r: f64 = 0.03;
+    let t: f64 = 0.5;
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let f = tensor(ctx, &[75., 80., 85.]);
+        let k = tensor(ctx, &[80., 80., 80.]);
+        let vol = tensor(ctx, &[0.35, 0.35, 0.35]);
+
+        let difference = (price_call_black76(&f, &k, &vol, r, t) - price_put_black76(&f, &k, &vol, r, t))
+            .eval(ctx)
+            .unwrap();
+        let expected = nd::arr1(&[-5., 0., 5.]).mapv(|x| x * (-r * t).exp()).into_dyn();
+        assert_close(&difference, &expected, 1e-10);
+    });
+}
@@ -0,0 +1,987 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::statrs::distribution::{ContinuousCDF, Normal};
+use autograd::prelude::*;
+use autograd::tensor_ops as math;
+use std::time::Instant;
+
+use rquant::error::QuantError;
+use rquant::fixed_income::curve::{FlatCurve, ZeroCurve};
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+use rquant::options::vol_surface::{FlatVolCurve, TermVolCurve, VolCurve};
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+fn assert_close(actual: &ag::NdArray<f64>, expected: &ag::NdArray<f64>, tol: f64) {
+    assert_eq!(actual.shape(), expected.shape());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < tol, "expected {}, got {}", e, a);
+    }
+}
+
+#[test]
+fn test_delta_matches_finite_difference() {
+    let h = 1e-4;
+    let r = 0.05;
+    let t = 0.75;
+    let spot = nd::arr1(&[90., 100., 110.]).into_dyn();
+
+    for ty in [OptionType::Call, OptionType::Put] {
+        ag::run(|ctx: &mut ag::Context<f64>| {
+            let s = ctx.placeholder("s", &[-1]);
+            let k = tensor(ctx, &[100., 100., 100.]);
+            let vol = tensor(ctx, &[0.2, 0.25, 0.3]);
+            let q = tensor(ctx, &[0., 0.01, 0.02]);
+
+            let delta = match ty {
+                OptionType::Call => call_delta(&s, &k, &vol, &q, r, t),
+                OptionType::Put => put_delta(&s, &k, &vol, &q, r, t),
+            };
+            let up = BlackScholesPricingModel::price(ty, &(s + h), &k, &vol, &q, r, t);
+            let down = BlackScholesPricingModel::price(ty, &(s - h), &k, &vol, &q, r, t);
+
+            let results = ctx
+                .evaluator()
+                .push(&delta)
+                .push(&up)
+                .push(&down)
+                .feed(s, spot.view())
+                .run();
+            let delta = results[0].as_ref().unwrap();
+            let bumped = (results[1].as_ref().unwrap() - results[2].as_ref().unwrap()) / (2. * h);
+            assert_close(delta, &bumped, 1e-5);
+        });
+    }
+}
+
+fn standard_normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2. * std::f64::consts::PI).sqrt()
+}
+
+#[test]
+fn test_gamma_matches_closed_form() {
+    let r: f64 = 0.05;
+    let t: f64 = 0.5;
+    let spot: [f64; 3] = [80., 100., 125.];
+    let strike: [f64; 3] = [100., 100., 100.];
+    let sigma: [f64; 3] = [0.2, 0.3, 0.4];
+    let dividend: [f64; 3] = [0., 0.02, 0.];
+
+    let expected = nd::Array1::from_shape_fn(3, |i| {
+        let d1 = ((spot[i] / strike[i]).ln() + (r - dividend[i] + 0.5 * sigma[i] * sigma[i]) * t)
+            / (sigma[i] * t.sqrt());
+        (-dividend[i] * t).exp() * standard_normal_pdf(d1) / (spot[i] * sigma[i] * t.sqrt())
+    })
+    .into_dyn();
+
+    for ty in [OptionType::Call, OptionType::Put] {
+        ag::run(|ctx: &mut ag::Context<f64>| {
+            let s = ctx.placeholder("s", &[-1]);
+            let k = tensor(ctx, &strike);
+            let vol = tensor(ctx, &sigma);
+            let q = tensor(ctx, &dividend);
+
+            let gamma = BlackScholesPricingModel::gamma(ty, &s, &k, &vol, &q, r, t);
+            let spot = nd::arr1(&spot).into_dyn();
+            let results = ctx.evaluator().push(&gamma).feed(s, spot.view()).run();
+            assert_close(results[0].as_ref().unwrap(), &expected, 1e-8);
+        });
+    }
+}
+
+fn standard_normal_cdf(x: f64) -> f64 {
+    Normal::new(0., 1.).unwrap().cdf(x)
+}
+
+#[test]
+fn test_vega_matches_finite_difference() {
+    let h = 1e-4;
+    let r = 0.03;
+    let t = 1.25;
+    let sigma = nd::arr1(&[0.15, 0.25, 0.35]).into_dyn();
+
+    for ty in [OptionType::Call, OptionType::Put] {
+        ag::run(|ctx: &mut ag::Context<f64>| {
+            let s = tensor(ctx, &[95., 100., 105.]);
+            let k = tensor(ctx, &[100., 100., 100.]);
+            let vol = ctx.placeholder("vol", &[-1]);
+            let q = tensor(ctx, &[0.01, 0.01, 0.01]);
+
+            let vega = match ty {
+                OptionType::Call => call_vega(&s, &k, &vol, &q, r, t),
+                OptionType::Put => put_vega(&s, &k, &vol, &q, r, t),
+            };
+            let up = BlackScholesPricingModel::price(ty, &s, &k, &(vol + h), &q, r, t);
+            let down = BlackScholesPricingModel::price(ty, &s, &k, &(vol - h), &q, r, t);
+
+            let results = ctx
+                .evaluator()
+                .push(&vega)
+                .push(&up)
+                .push(&down)
+                .feed(vol, sigma.view())
+                .run();
+            let bumped = (results[1].as_ref().unwrap() - results[2].as_ref().unwrap()) / (2. * h);
+            assert_close(results[0].as_ref().unwrap(), &bumped, 1e-5);
+        });
+    }
+}
+
+#[test]
+fn test_theta_and_rho_match_closed_form() {
+    let (s0, k0, sigma, q0, r, t): (f64, f64, f64, f64, f64, f64) = (105., 100., 0.25, 0.02, 0.04, 0.8);
+    let d1 = ((s0 / k0).ln() + (r - q0 + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+    let d2 = d1 - sigma * t.sqrt();
+    let decay = -s0 * (-q0 * t).exp() * standard_normal_pdf(d1) * sigma / (2. * t.sqrt());
+
+    let call_theta_expected = decay - r * k0 * (-r * t).exp() * standard_normal_cdf(d2)
+        + q0 * s0 * (-q0 * t).exp() * standard_normal_cdf(d1);
+    let put_theta_expected = decay + r * k0 * (-r * t).exp() * standard_normal_cdf(-d2)
+        - q0 * s0 * (-q0 * t).exp() * standard_normal_cdf(-d1);
+    let call_rho_expected = k0 * t * (-r * t).exp() * standard_normal_cdf(d2);
+    let put_rho_expected = -k0 * t * (-r * t).exp() * standard_normal_cdf(-d2);
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[s0]);
+        let k = tensor(ctx, &[k0]);
+        let vol = tensor(ctx, &[sigma]);
+        let q = tensor(ctx, &[q0]);
+
+        let cases = [
+            (call_theta(&s, &k, &vol, &q, r, t), call_theta_expected),
+            (put_theta(&s, &k, &vol, &q, r, t), put_theta_expected),
+            (call_rho(&s, &k, &vol, &q, r, t), call_rho_expected),
+            (put_rho(&s, &k, &vol, &q, r, t), put_rho_expected),
+        ];
+        for (greek, expected) in cases.iter() {
+            let value = greek.eval(ctx).unwrap();
+            assert_close(&value, &nd::arr1(&[*expected]).into_dyn(), 1e-10);
+        }
+    });
+}
+
+#[test]
+fn test_tensor_rate_and_time_price_differentiates_per_option() {
+    let (rates, times): ([f64; 2], [f64; 2]) = ([0.01, 0.06], [0.5, 2.]);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100., 100.]);
+        let k = tensor(ctx, &[95., 105.]);
+        let vol = tensor(ctx, &[0.2, 0.3]);
+        let q = tensor(ctx, &[0., 0.02]);
+        let r = tensor(ctx, &rates);
+        let t = tensor(ctx, &times);
+
+        for ty in [OptionType::Call, OptionType::Put] {
+            let price = price_with_tensor_rate_and_time(ty, &s, &k, &vol, &q, &r, &t);
+            let grads = math::grad(&[price], &[t, r]);
+            let (price, theta, rho) = (
+                price.eval(ctx).unwrap(),
+                math::neg(grads[0]).eval(ctx).unwrap(),
+                grads[1].eval(ctx).unwrap(),
+            );
+            for i in 0..2 {
+                let (si, ki) = (tensor(ctx, &[100.]), tensor(ctx, &[[95., 105.][i]]));
+                let (voli, qi) = (tensor(ctx, &[[0.2, 0.3][i]]), tensor(ctx, &[[0., 0.02][i]]));
+                let expected_price = BlackScholesPricingModel::price(ty, &si, &ki, &voli, &qi, rates[i], times[i]);
+                let expected_theta = BlackScholesPricingModel::theta(ty, &si, &ki, &voli, &qi, rates[i], times[i]);
+                let expected_rho = BlackScholesPricingModel::rho(ty, &si, &ki, &voli, &qi, rates[i], times[i]);
+                assert!((price[i] - expected_price.eval(ctx).unwrap()[0]).abs() < 1e-10);
+                assert!((theta[i] - expected_theta.eval(ctx).unwrap()[0]).abs() < 1e-10);
+                assert!((rho[i] - expected_rho.eval(ctx).unwrap()[0]).abs() < 1e-10);
+            }
+        }
+    });
+}
+
+#[test]
+fn test_greeks_match_individual_functions() {
+    let r = 0.02;
+    let t = 0.6;
+    let spot = nd::arr1(&[90., 100., 110.]).into_dyn();
+
+    for ty in [OptionType::Call, OptionType::Put] {
+        ag::run(|ctx: &mut ag::Context<f64>| {
+            let s = ctx.placeholder("s", &[-1]);
+            let k = tensor(ctx, &[100., 100., 100.]);
+            let vol = tensor(ctx, &[0.3, 0.3, 0.3]);
+            let q = tensor(ctx, &[0.01, 0.01, 0.01]);
+
+            let greeks = match ty {
+                OptionType::Call => call_greeks(&s, &k, &vol, &q, r, t),
+                OptionType::Put => put_greeks(&s, &k, &vol, &q, r, t),
+            };
+            let results = ctx
+                .evaluator()
+                .extend(&[greeks.delta, greeks.gamma, greeks.vega, greeks.theta, greeks.rho])
+                .push(&BlackScholesPricingModel::delta(ty, &s, &k, &vol, &q, r, t))
+                .push(&BlackScholesPricingModel::gamma(ty, &s, &k, &vol, &q, r, t))
+                .push(&BlackScholesPricingModel::vega(ty, &s, &k, &vol, &q, r, t))
+                .push(&BlackScholesPricingModel::theta(ty, &s, &k, &vol, &q, r, t))
+                .push(&BlackScholesPricingModel::rho(ty, &s, &k, &vol, &q, r, t))
+                .feed(s, spot.view())
+                .run();
+            for i in 0..5 {
+                assert_close(results[i].as_ref().unwrap(), results[i + 5].as_ref().unwrap(), 1e-10);
+            }
+        });
+    }
+}
+
+#[test]
+fn test_vanna_and_volga_match_cross_bumps() {
+    let h = 1e-3;
+    let r = 0.05;
+    let t = 0.9;
+    let spot = nd::arr1(&[85., 100., 115.]).into_dyn();
+    let sigma = nd::arr1(&[0.2, 0.25, 0.3]).into_dyn();
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = ctx.placeholder("s", &[-1]);
+        let vol = ctx.placeholder("vol", &[-1]);
+        let k = tensor(ctx, &[100., 100., 100.]);
+        let q = tensor(ctx, &[0., 0., 0.]);
+        let price = |ds: f64, dv: f64| {
+            BlackScholesPricingModel::price(OptionType::Call, &(s + ds), &k, &(vol + dv), &q, r, t)
+        };
+
+        let results = ctx
+            .evaluator()
+            .push(&call_vanna(&s, &k, &vol, &q, r, t))
+            .push(&call_volga(&s, &k, &vol, &q, r, t))
+            .push(&price(h, h))
+            .push(&price(h, -h))
+            .push(&price(-h, h))
+            .push(&price(-h, -h))
+            .push(&price(0., h))
+            .push(&price(0., 0.))
+            .push(&price(0., -h))
+            .feed(s, spot.view())
+            .feed(vol, sigma.view())
+            .run();
+        let p = |i: usize| results[i].as_ref().unwrap().clone();
+
+        let vanna = (p(2) - p(3) - p(4) + p(5)) / (4. * h * h);
+        let volga = (p(6) - p(7) * 2. + p(8)) / (h * h);
+        assert_close(&p(0), &vanna, 1e-4);
+        assert_close(&p(1), &volga, 1e-3);
+    });
+}
+
+#[test]
+fn test_at_the_money_reference_prices() {
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100.]);
+        let k = tensor(ctx, &[100.]);
+        let vol = tensor(ctx, &[0.2]);
+        let q = tensor(ctx, &[0.]);
+
+        let call = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, 0.05, 1.);
+        let put = BlackScholesPricingModel::price(OptionType::Put, &s, &k, &vol, &q, 0.05, 1.);
+        assert_close(&call.eval(ctx).unwrap(), &nd::arr1(&[10.450583572185565]).into_dyn(), 1e-6);
+        assert_close(&put.eval(ctx).unwrap(), &nd::arr1(&[5.573526022256971]).into_dyn(), 1e-6);
+    });
+}
+
+#[test]
+fn test_price_scales_with_volatility_and_time() {
+    // Reference values for S = K = 100, r = 0.05 with non unit vol * sqrt(t).
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100., 100.]);
+        let k = tensor(ctx, &[100., 100.]);
+        let vol = tensor(ctx, &[0.4, 0.1]);
+        let q = tensor(ctx, &[0., 0.]);
+
+        let call = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, 0.05, 0.25);
+        assert_close(
+            &call.eval(ctx).unwrap(),
+            &nd::arr1(&[8.552606881568877, 2.6648322216391875]).into_dyn(),
+            1e-6,
+        );
+    });
+}
+
+#[test]
+fn test_dividend_yield_reference_prices() {
+    // Haug's Black-Scholes-Merton example: S = 100, K = 95, t = 0.5, r = 0.1, q = 0.05, vol = 0.2.
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100.]);
+        let k = tensor(ctx, &[95.]);
+        let vol = tensor(ctx, &[0.2]);
+        let q = tensor(ctx, &[0.05]);
+
+        let call = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, 0.1, 0.5);
+        let put = BlackScholesPricingModel::price(OptionType::Put, &s, &k, &vol, &q, 0.1, 0.5);
+        assert_close(&call.eval(ctx).unwrap(), &nd::arr1(&[9.628983522021265]).into_dyn(), 1e-6);
+        assert_close(&put.eval(ctx).unwrap(), &nd::arr1(&[2.4647876467558234]).into_dyn(), 1e-6);
+    });
+}
+
+#[test]
+fn test_put_call_parity_with_dividends() {
+    let r: f64 = 0.04;
+    let t: f64 = 1.5;
+    let spot = [80., 100., 120.];
+    let strike = [100., 90., 110.];
+    let dividend = [0., 0.03, 0.06];
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &spot);
+        let k = tensor(ctx, &strike);
+        let vol = tensor(ctx, &[0.2, 0.3, 0.4]);
+        let q = tensor(ctx, &dividend);
+
+        let call = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t);
+        let put = BlackScholesPricingModel::price(OptionType::Put, &s, &k, &vol, &q, r, t);
+        let difference = (call - put).eval(ctx).unwrap();
+
+        let expected = nd::Array1::from_shape_fn(3, |i| {
+            spot[i] * (-dividend[i] * t).exp() - strike[i] * (-r * t).exp()
+        })
+        .into_dyn();
+        assert_close(&difference, &expected, 1e-9);
+    });
+}
+
+#[test]
+fn test_discrete_dividends_match_manual_escrow() {
+    let r: f64 = 0.06;
+    let t: f64 = 0.75;
+    // The last dividend is paid after maturity and must be ignored.
+    let dividends = [(0.25, 1.5), (0.5, 1.5), (1.0, 1.5)];
+    let pv = 1.5 * (-r * 0.25).exp() + 1.5 * (-r * 0.5).exp();
+    assert!((present_value_of_dividends(&dividends, r, t) - pv).abs() < 1e-12);
+
+    for ty in [OptionType::Call, OptionType::Put] {
+        ag::run(|ctx: &mut ag::Context<f64>| {
+            let s = tensor(ctx, &[100., 110.]);
+            let escrowed = tensor(ctx, &[100. - pv, 110. - pv]);
+            let k = tensor(ctx, &[100., 100.]);
+            let vol = tensor(ctx, &[0.3, 0.3]);
+            let q = tensor(ctx, &[0., 0.]);
+
+            let price = price_with_discrete_dividends(ty, &s, &k, &vol, &q, &dividends, r, t);
+            let manual = BlackScholesPricingModel::price(ty, &escrowed, &k, &vol, &q, r, t);
+            assert_close(&price.eval(ctx).unwrap(), &manual.eval(ctx).unwrap(), 1e-10);
+        });
+    }
+}
+
+#[test]
+fn test_newton_implied_volatility_round_trips() {
+    let r = 0.05;
+    let t = 0.5;
+    let spot = nd::arr1(&[100., 100., 100., 100.]).into_dyn();
+    let strike = nd::arr1(&[80., 95., 105., 130.]).into_dyn();
+    let dividend = nd::arr1(&[0.01, 0.01, 0.01, 0.01]).into_dyn();
+    let vol = nd::arr1(&[0.35, 0.3, 0.25, 0.2]).into_dyn();
+
+    for ty in [OptionType::Call, OptionType::Put] {
+        let price = ag::run(|ctx: &mut ag::Context<f64>| {
+            let s = math::convert_to_tensor(spot.clone(), ctx);
+            let k = math::convert_to_tensor(strike.clone(), ctx);
+            let sigma = math::convert_to_tensor(vol.clone(), ctx);
+            let q = math::convert_to_tensor(dividend.clone(), ctx);
+            BlackScholesPricingModel::price(ty, &s, &k, &sigma, &q, r, t).eval(ctx).unwrap()
+        });
+
+        let (implied, converged) = implied_volatility_newton(
+            ty,
+            price.view(),
+            spot.view(),
+            strike.view(),
+            dividend.view(),
+            r,
+            t,
+            1e-10,
+            50,
+        )
+        .unwrap();
+        assert!(converged);
+        assert_close(&implied, &vol, 1e-6);
+    }
+}
+
+#[test]
+fn test_newton_implied_volatility_reports_non_convergence() {
+    let price = nd::arr1(&[10.450583572185565]).into_dyn();
+    let spot = nd::arr1(&[100.]).into_dyn();
+    let strike = nd::arr1(&[100.]).into_dyn();
+    let dividend = nd::arr1(&[0.]).into_dyn();
+
+    let (_, converged) =
+        implied_volatility_newton(OptionType::Call, price.view(), spot.view(), strike.view(), dividend.view(), 0.05, 1., 1e-10, 0)
+            .unwrap();
+    assert!(!converged);
+}
+
+#[test]
+fn test_implied_volatility_report_flags_unreachable_prices() {
+    let r = 0.05;
+    let t = 1.;
+    // The first price is the call at 80% volatility, the second is far below intrinsic.
+    let spot = nd::arr1(&[100., 150.]).into_dyn();
+    let strike = nd::arr1(&[100., 100.]).into_dyn();
+    let dividend = nd::arr1(&[0., 0.]).into_dyn();
+    let target = ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100.]);
+        let k = tensor(ctx, &[100.]);
+        let vol = tensor(ctx, &[0.8]);
+        let q = tensor(ctx, &[0.]);
+        BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()[0]
+    });
+    let price = nd::arr1(&[target, 1.]).into_dyn();
+
+    let (vol, report) = BlackScholesPricingModel::implied_volatility_with_report(
+        OptionType::Call,
+        price.view(),
+        spot.view(),
+        strike.view(),
+        dividend.view(),
+        r,
+        t,
+        0.1,
+    )
+    .unwrap();
+    assert_eq!(vol.shape(), &[2]);
+    assert_eq!(report.loss.shape(), &[2]);
+    assert!(report.converged[0], "loss {}", report.loss[0]);
+    assert!(!report.converged[1]);
+    assert!(report.loss[1] > 40.);
+}
+
+#[test]
+fn test_implied_volatility_rejects_mismatched_shapes() {
+    let price = nd::arr1(&[10., 5.]).into_dyn();
+    let spot = nd::arr1(&[100., 100.]).into_dyn();
+    let strike = nd::arr1(&[100., 100., 100.]).into_dyn();
+    let dividend = nd::arr1(&[0., 0.]).into_dyn();
+    let expected = QuantError::ShapeMismatch { expected: vec![2], got: vec![3] };
+
+    for ty in [OptionType::Call, OptionType::Put] {
+        let adam = BlackScholesPricingModel::implied_volatility(ty, price.view(), spot.view(), strike.view(), dividend.view(), 0.05, 1.);
+        assert_eq!(adam.unwrap_err(), expected);
+
+        let newton = implied_volatility_newton(ty, price.view(), spot.view(), strike.view(), dividend.view(), 0.05, 1., 1e-8, 10);
+        assert_eq!(newton.unwrap_err(), expected);
+    }
+}
+
+#[test]
+fn test_bisection_implied_volatility_recovers_deep_out_of_the_money_vol() {
+    let r = 0.05;
+    let t = 0.25;
+    let spot = nd::arr1(&[100., 100.]).into_dyn();
+    let strike = nd::arr1(&[160., 100.]).into_dyn();
+    let dividend = nd::arr1(&[0., 0.]).into_dyn();
+    let vol = nd::arr1(&[0.25, 0.25]).into_dyn();
+    let price = ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = math::convert_to_tensor(spot.clone(), ctx);
+        let k = math::convert_to_tensor(strike.clone(), ctx);
+        let sigma = math::convert_to_tensor(vol.clone(), ctx);
+        let q = math::convert_to_tensor(dividend.clone(), ctx);
+        BlackScholesPricingModel::price(OptionType::Call, &s, &k, &sigma, &q, r, t).eval(ctx).unwrap()
+    });
+    // The deep out of the money call is worth about 5e-4 with a vega of about 0.03.
+    assert!(price[0] < 1e-3);
+
+    let implied = implied_volatility_bisection(
+        OptionType::Call,
+        price.view(),
+        spot.view(),
+        strike.view(),
+        dividend.view(),
+        r,
+        t,
+        1e-12,
+    )
+    .unwrap();
+    assert_close(&implied, &vol, 1e-6);
+}
+
+#[test]
+fn test_bisection_implied_volatility_rejects_prices_below_intrinsic() {
+    let price = nd::arr1(&[1.]).into_dyn();
+    let spot = nd::arr1(&[150.]).into_dyn();
+    let strike = nd::arr1(&[100.]).into_dyn();
+    let dividend = nd::arr1(&[0.]).into_dyn();
+
+    let implied =
+        implied_volatility_bisection(OptionType::Call, price.view(), spot.view(), strike.view(), dividend.view(), 0.05, 1., 1e-8);
+    assert_eq!(implied.unwrap_err(), QuantError::NoBracket);
+}
+
+#[test]
+fn test_chain_implied_volatility_matches_scalar_solver() {
+    let r = 0.03;
+    let spot = [100., 100., 100., 100., 100., 100.];
+    let strike = [80., 100., 120., 90., 110., 160.];
+    let maturity = [0.25, 0.25, 0.25, 1., 1., 1.];
+    let vol = [0.32, 0.25, 0.22, 0.28, 0.24, 0.3];
+
+    let mut prices = Vec::new();
+    for i in 0..spot.len() {
+        let price = ag::run(|ctx: &mut ag::Context<f64>| {
+            let s = tensor(ctx, &[spot[i]]);
+            let k = tensor(ctx, &[strike[i]]);
+            let sigma = tensor(ctx, &[vol[i]]);
+            let q = tensor(ctx, &[0.]);
+            BlackScholesPricingModel::price(OptionType::Put, &s, &k, &sigma, &q, r, maturity[i]).eval(ctx).unwrap()[0]
+        });
+        prices.push(price);
+    }
+
+    let p = nd::arr1(&prices).into_dyn();
+    let s = nd::arr1(&spot).into_dyn();
+    let k = nd::arr1(&strike).into_dyn();
+    let q = nd::Array1::<f64>::zeros(spot.len()).into_dyn();
+    let t = nd::arr1(&maturity).into_dyn();
+    let (implied, converged) =
+        implied_volatility_chain(OptionType::Put, p.view(), s.view(), k.view(), q.view(), t.view(), r, 1e-10, 50).unwrap();
+    assert!(converged.iter().all(|&c| c));
+    assert_close(&implied, &nd::arr1(&vol).into_dyn(), 1e-6);
+
+    for i in 0..spot.len() {
+        let (scalar, _) = implied_volatility_newton(
+            OptionType::Put,
+            p.slice(nd::s![i..i + 1]),
+            s.slice(nd::s![i..i + 1]),
+            k.slice(nd::s![i..i + 1]),
+            q.slice(nd::s![i..i + 1]),
+            r,
+            maturity[i],
+            1e-10,
+            50,
+        )
+        .unwrap();
+        assert!((scalar[0] - implied[i]).abs() < 1e-8);
+    }
+}
+
+#[test]
+fn test_chain_implied_volatility_masks_unconverged_entries() {
+    // The second put is priced below its intrinsic value and can never converge.
+    let p = nd::arr1(&[10.450583572185565 - 100. + 100. * (-0.05f64).exp(), 1.]).into_dyn();
+    let s = nd::arr1(&[100., 50.]).into_dyn();
+    let k = nd::arr1(&[100., 100.]).into_dyn();
+    let q = nd::arr1(&[0., 0.]).into_dyn();
+    let t = nd::arr1(&[1., 1.]).into_dyn();
+
+    let (implied, converged) =
+        implied_volatility_chain(OptionType::Put, p.view(), s.view(), k.view(), q.view(), t.view(), 0.05, 1e-8, 30).unwrap();
+    assert_eq!(converged.as_slice().unwrap(), &[true, false]);
+    assert!((implied[0] - 0.2).abs() < 1e-6);
+}
+
+#[test]
+fn test_call_and_parity_put_share_an_implied_volatility() {
+    let r: f64 = 0.05;
+    let t: f64 = 1.;
+    let spot = nd::arr1(&[100., 100.]).into_dyn();
+    let strike = nd::arr1(&[95., 105.]).into_dyn();
+    let dividend = nd::arr1(&[0.01, 0.01]).into_dyn();
+    let call = ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = math::convert_to_tensor(spot.clone(), ctx);
+        let k = math::convert_to_tensor(strike.clone(), ctx);
+        let vol = tensor(ctx, &[0.9, 0.9]);
+        let q = math::convert_to_tensor(dividend.clone(), ctx);
+        BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()
+    });
+    // c - p = s e^{-qt} - k e^{-rt}
+    let put = nd::Array1::from_shape_fn(2, |i| {
+        call[i] - spot[i] * (-dividend[i] * t).exp() + strike[i] * (-r * t).exp()
+    })
+    .into_dyn();
+
+    let call_vol =
+        BlackScholesPricingModel::implied_volatility(OptionType::Call, call.view(), spot.view(), strike.view(), dividend.view(), r, t)
+            .unwrap();
+    let put_vol =
+        BlackScholesPricingModel::implied_volatility(OptionType::Put, put.view(), spot.view(), strike.view(), dividend.view(), r, t)
+            .unwrap();
+    assert_close(&call_vol, &put_vol, 1e-6);
+    assert_close(&put_vol, &nd::arr1(&[0.9, 0.9]).into_dyn(), 1e-2);
+}
+
+#[test]
+fn test_payoff_is_the_zero_volatility_price() {
+    assert_eq!(OptionType::Call.payoff(110f64, 100.), 10.);
+    assert_eq!(OptionType::Call.payoff(90f64, 100.), 0.);
+    assert_eq!(OptionType::Put.payoff(90f64, 100.), 10.);
+    assert_eq!(OptionType::Put.payoff(110f64, 100.), 0.);
+
+    let spot = [80., 90., 110., 120.];
+    for ty in [OptionType::Call, OptionType::Put] {
+        let price = ag::run(|ctx: &mut ag::Context<f64>| {
+            let s = tensor(ctx, &spot);
+            let k = tensor(ctx, &[100.; 4]);
+            let vol = tensor(ctx, &[1e-6; 4]);
+            let q = tensor(ctx, &[0.; 4]);
+            BlackScholesPricingModel::price(ty, &s, &k, &vol, &q, 0., 1.).eval(ctx).unwrap()
+        });
+        for (&p, &st) in price.iter().zip(spot.iter()) {
+            assert!((p - ty.payoff(st, 100.)).abs() < 1e-8);
+        }
+    }
+}
+
+#[test]
+fn test_price_surface_matches_single_prices() {
+    let (s, vol, q, r) = (100., 0.2, 0.01, 0.04);
+    let strikes = [80., 95., 100., 105., 120.];
+    let maturities = [0.25, 1., 3.];
+
+    for ty in [OptionType::Call, OptionType::Put] {
+        let surface = price_surface(ty, s, &strikes, vol, q, r, &maturities);
+        assert_eq!(surface.shape(), &[5, 3]);
+        for (i, &k) in strikes.iter().enumerate() {
+            for (j, &t) in maturities.iter().enumerate() {
+                let single = ag::run(|ctx: &mut ag::Context<f64>| {
+                    let spot = tensor(ctx, &[s]);
+                    let strike = tensor(ctx, &[k]);
+                    let sigma = tensor(ctx, &[vol]);
+                    let dividends = tensor(ctx, &[q]);
+                    BlackScholesPricingModel::price(ty, &spot, &strike, &sigma, &dividends, r, t)
+                        .eval(ctx)
+                        .unwrap()[0]
+                });
+                assert!((surface[[i, j]] - single).abs() < 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_put_call_parity_check() {
+    let (s, k, q, r, t) = (100., 95., 0.03, 0.05, 1.25);
+    let price = |ty| {
+        ag::run(|ctx: &mut ag::Context<f64>| {
+            let spot = tensor(ctx, &[s]);
+            let strike = tensor(ctx, &[k]);
+            let sigma = tensor(ctx, &[0.3]);
+            let dividends = tensor(ctx, &[q]);
+            BlackScholesPricingModel::price(ty, &spot, &strike, &sigma, &dividends, r, t)
+                .eval(ctx)
+                .unwrap()[0]
+        })
+    };
+    let (call, put) = (price(OptionType::Call), price(OptionType::Put));
+
+    assert!(put_call_parity_residual(call, put, s, k, q, r, t).abs() < 1e-12);
+    assert!(check_put_call_parity(call, put, s, k, q, r, t, 1e-10));
+    // Ignoring the dividend yield breaks parity.
+    assert!(!check_put_call_parity(call, put, s, k, 0., r, t, 1e-2));
+    assert!(!check_put_call_parity(call + 0.01, put, s, k, q, r, t, 1e-3));
+}
+
+#[test]
+fn test_implied_volatility_stops_early_on_an_easy_point() {
+    let (r, t) = (0.05, 1.);
+    let spot = nd::arr1(&[100.]).into_dyn();
+    let strike = nd::arr1(&[100.]).into_dyn();
+    let dividend = nd::arr1(&[0.]).into_dyn();
+    let price = ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100.]);
+        let k = tensor(ctx, &[100.]);
+        let vol = tensor(ctx, &[0.2]);
+        let q = tensor(ctx, &[0.]);
+        BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()
+    });
+
+    let stopping = EarlyStopping {
+        max_epochs: 10_000,
+        tolerance: Some(1e-4),
+        patience: None,
+    };
+    let (vol, epochs) = implied_volatility_early_stopping(
+        OptionType::Call,
+        price.view(),
+        spot.view(),
+        strike.view(),
+        dividend.view(),
+        r,
+        t,
+        &stopping,
+    )
+    .unwrap();
+    assert!(epochs < 1500, "{} epochs", epochs);
+    assert_close(&vol, &nd::arr1(&[0.2]).into_dyn(), 1e-4);
+}
+
+#[test]
+fn test_implied_volatility_patience_stops_a_stalled_fit() {
+    let (r, t) = (0.05, 1.);
+    let spot = nd::arr1(&[100.]).into_dyn();
+    let strike = nd::arr1(&[100.]).into_dyn();
+    let dividend = nd::arr1(&[0.]).into_dyn();
+    let price = ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100.]);
+        let k = tensor(ctx, &[100.]);
+        let vol = tensor(ctx, &[0.2]);
+        let q = tensor(ctx, &[0.]);
+        BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()
+    });
+
+    // Without a tolerance the fit only ends when Adam's oscillation around the
+    // solution stops improving the loss.
+    let stopping = EarlyStopping {
+        max_epochs: 100_000,
+        tolerance: None,
+        patience: Some(50),
+    };
+    let (vol, epochs) = implied_volatility_early_stopping(
+        OptionType::Call,
+        price.view(),
+        spot.view(),
+        strike.view(),
+        dividend.view(),
+        r,
+        t,
+        &stopping,
+    )
+    .unwrap();
+    assert!(epochs < 2000, "{} epochs", epochs);
+    assert_close(&vol, &nd::arr1(&[0.2]).into_dyn(), 1e-2);
+}
+
+#[test]
+fn test_implied_dividend_yield_recovers_known_yield() {
+    let r: f64 = 0.03;
+    let t: f64 = 0.8;
+    let spot = [3900., 4000., 4100.];
+    let dividend = [0.015, 0.015, 0.015];
+
+    let (call, put) = ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &spot);
+        let k = tensor(ctx, &[4000., 4000., 4000.]);
+        let vol = tensor(ctx, &[0.18, 0.2, 0.22]);
+        let q = tensor(ctx, &dividend);
+        let call = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t);
+        let put = BlackScholesPricingModel::price(OptionType::Put, &s, &k, &vol, &q, r, t);
+        (call.eval(ctx).unwrap(), put.eval(ctx).unwrap())
+    });
+
+    for i in 0..3 {
+        let q = implied_dividend_yield(call[i], put[i], spot[i], 4000., r, t).unwrap();
+        assert!((q - dividend[i]).abs() < 1e-9, "expected {}, got {}", dividend[i], q);
+    }
+}
+
+#[test]
+fn test_implied_dividend_yield_rejects_negative_forward() {
+    // A put far above its parity value implies a negative forward.
+    match implied_dividend_yield(1f64, 150., 100., 100., 0.02, 1.) {
+        Err(QuantError::InvalidInput(_)) => {}
+        other => panic!("expected InvalidInput, got {:?}", other),
+    }
+    assert!(implied_dividend_yield(10f64, 5., 100., 100., 0.02, 0.).is_err());
+}
+
+#[test]
+fn test_flat_curve_reproduces_constant_rate_prices() {
+    let (r, t) = (0.04, 1.5);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[90., 100., 110.]);
+        let k = tensor(ctx, &[100.; 3]);
+        let vol = tensor(ctx, &[0.2, 0.25, 0.3]);
+        let q = tensor(ctx, &[0.01; 3]);
+        for &ty in [OptionType::Call, OptionType::Put].iter() {
+            let flat = BlackScholesPricingModel::price_with_curve(ty, &s, &k, &vol, &q, &FlatCurve::new(r), t);
+            let constant = BlackScholesPricingModel::price(ty, &s, &k, &vol, &q, r, t);
+            assert_eq!(flat.eval(ctx).unwrap(), constant.eval(ctx).unwrap());
+        }
+    });
+}
+
+#[test]
+fn test_zero_curve_discounts_at_the_zero_rate_to_maturity() {
+    let curve = ZeroCurve::new(vec![0.5, 1., 2.], vec![0.99, 0.975, 0.94]).unwrap();
+    let t = 1.5;
+    // Halfway between the 1 and 2 year pillars in log discount factor.
+    let zero_rate = -(0.975f64 * 0.94).sqrt().ln() / t;
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100.]);
+        let k = tensor(ctx, &[105.]);
+        let vol = tensor(ctx, &[0.2]);
+        let q = tensor(ctx, &[0.]);
+        let on_curve = BlackScholesPricingModel::price_with_curve(OptionType::Call, &s, &k, &vol, &q, &curve, t);
+        let expected = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, zero_rate, t);
+        assert_close(&on_curve.eval(ctx).unwrap(), &expected.eval(ctx).unwrap(), 1e-12);
+    });
+}
+
+#[test]
+fn test_flat_vol_curve_reproduces_constant_vol_prices() {
+    let (r, t) = (0.03, 0.75);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[90., 100., 110.]);
+        let k = tensor(ctx, &[100.; 3]);
+        let vol = tensor(ctx, &[0.25; 3]);
+        let q = tensor(ctx, &[0.01; 3]);
+        let term = price_call_term_vol(&s, &k, &FlatVolCurve::new(0.25), &q, r, t);
+        let constant = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t);
+        assert_eq!(term.eval(ctx).unwrap(), constant.eval(ctx).unwrap());
+    });
+}
+
+#[test]
+fn test_term_vol_strip_prices_at_root_mean_square_vol() {
+    let curve = TermVolCurve::new(&[0.25, 1., 2.], &[0.35, 0.28, 0.24]).unwrap();
+    let r = 0.02;
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100.]);
+        let k = tensor(ctx, &[100.]);
+        let q = tensor(ctx, &[0.]);
+        for &t in [0.25, 0.5, 1., 1.5, 2.].iter() {
+            let term = price_call_term_vol(&s, &k, &curve, &q, r, t);
+            let vol = tensor(ctx, &[curve.rms_vol(t)]);
+            let expected = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t);
+            assert_close(&term.eval(ctx).unwrap(), &expected.eval(ctx).unwrap(), 1e-12);
+        }
+    });
+}
+
+/// Times the Adam implied volatility fit of 10,000 options against the loop it replaced,
+/// which rebuilt the graph every epoch. Run it with
+/// `cargo test --release -- --ignored --nocapture bench_implied_volatility`.
+#[test]
+#[ignore]
+fn bench_implied_volatility_builds_its_graph_once() {
+    let (n, r, t) = (10_000, 0.05, 1.);
+    let stopping = EarlyStopping { max_epochs: 100, tolerance: None, patience: None };
+    let spot = nd::Array1::from_elem(n, 100.).into_dyn();
+    let strike = nd::Array1::linspace(80., 120., n).into_dyn();
+    let dividend = nd::Array1::zeros(n).into_dyn();
+    let price = ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = math::convert_to_tensor(spot.clone(), ctx);
+        let k = math::convert_to_tensor(strike.clone(), ctx);
+        let vol = math::convert_to_tensor(nd::ArrayD::from_elem(vec![n], 0.2), ctx);
+        let q = math::convert_to_tensor(dividend.clone(), ctx);
+        BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()
+    });
+
+    let start = Instant::now();
+    let mut env = ag::VariableEnvironment::new();
+    let vol_id = env.name("vol").set(nd::ArrayD::ones(vec![n]));
+    let adam = ag::optimizers::adam::Adam::default("AdamIV", env.default_namespace().current_var_ids(), &mut env);
+    for _ in 0..stopping.max_epochs {
+        env.run(|ctx| {
+            let vol = ctx.variable("vol");
+            let p = ctx.placeholder("p", &[-1]);
+            let s = ctx.placeholder("s", &[-1]);
+            let k = ctx.placeholder("k", &[-1]);
+            let q = ctx.placeholder("q", &[-1]);
+            let pred = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t);
+            let grads = math::grad(&[math::abs(p - pred)], &[vol]);
+
+            let mut feeder = ag::Feeder::new();
+            feeder.push(p, price.view()).push(s, spot.view()).push(k, strike.view()).push(q, dividend.view());
+            adam.update(&[vol], &grads, ctx, feeder);
+        });
+    }
+    let rebuilt_time = start.elapsed();
+    let rebuilt = env.get_array_by_id(vol_id).unwrap().clone().into_inner();
+
+    let start = Instant::now();
+    let (built_once, _) = implied_volatility_early_stopping(
+        OptionType::Call,
+        price.view(),
+        spot.view(),
+        strike.view(),
+        dividend.view(),
+        r,
+        t,
+        &stopping,
+    )
+    .unwrap();
+    let built_once_time = start.elapsed();
+
+    println!(
+        "{} options, {} epochs: rebuilt every epoch {:?}, built once {:?}, {:.1}x faster",
+        n,
+        stopping.max_epochs,
+        rebuilt_time,
+        built_once_time,
+        rebuilt_time.as_secs_f64() / built_once_time.as_secs_f64()
+    );
+    assert_close(&built_once, &rebuilt, 1e-10);
+    assert!(built_once_time < rebuilt_time);
+}
+
+/// Times the chain solver on 1,000 puts over ten maturities against looping the
+/// scalar Newton solver over them one at a time. Run it with
+/// `cargo test --release -- --ignored --nocapture bench_implied_volatility_chain`.
+#[test]
+#[ignore]
+fn bench_implied_volatility_chain_against_scalar_newton() {
+    let (r, per_maturity) = (0.03, 100);
+    let maturities = (1..=10).map(|i| 0.25 * i as f64).collect::<Vec<_>>();
+    let strikes = nd::Array1::linspace(70., 130., per_maturity).to_vec();
+
+    let (mut p, mut k, mut t) = (Vec::new(), Vec::new(), Vec::new());
+    for &maturity in maturities.iter() {
+        let prices = ag::run(|ctx: &mut ag::Context<f64>| {
+            let s = tensor(ctx, &vec![100.; per_maturity]);
+            let strike = tensor(ctx, &strikes);
+            let sigma = tensor(ctx, &vec![0.25; per_maturity]);
+            let q = tensor(ctx, &vec![0.; per_maturity]);
+            BlackScholesPricingModel::price(OptionType::Put, &s, &strike, &sigma, &q, r, maturity).eval(ctx).unwrap()
+        });
+        p.extend(prices.iter());
+        k.extend(strikes.iter());
+        t.extend(vec![maturity; per_maturity]);
+    }
+    let n = p.len();
+    let p = nd::arr1(&p).into_dyn();
+    let s = nd::Array1::from_elem(n, 100.).into_dyn();
+    let k = nd::arr1(&k).into_dyn();
+    let q = nd::Array1::<f64>::zeros(n).into_dyn();
+    let t = nd::arr1(&t).into_dyn();
+
+    let start = Instant::now();
+    let (chain, _) = implied_volatility_chain(
+        OptionType::Put,
+        p.view(),
+        s.view(),
+        k.view(),
+        q.view(),
+        t.view(),
+        r,
+        1e-10,
+        50,
+    )
+    .unwrap();
+    let chain_time = start.elapsed();
+
+    let start = Instant::now();
+    let scalar = (0..n)
+        .map(|i| {
+            let (vol, _) = implied_volatility_newton(
+                OptionType::Put,
+                p.slice(nd::s![i..i + 1]),
+                s.slice(nd::s![i..i + 1]),
+                k.slice(nd::s![i..i + 1]),
+                q.slice(nd::s![i..i + 1]),
+                r,
+                t[i],
+                1e-10,
+                50,
+            )
+            .unwrap();
+            vol[0]
+        })
+        .collect::<Vec<_>>();
+    let scalar_time = start.elapsed();
+
+    println!(
+        "{} options: scalar Newton loop {:?}, chain {:?}, {:.1}x faster",
+        n,
+        scalar_time,
+        chain_time,
+        scalar_time.as_secs_f64() / chain_time.as_secs_f64()
+    );
+    assert_close(&chain, &nd::arr1(&scalar).into_dyn(), 1e-8);
+    assert!(chain_time < scalar_time);
+}
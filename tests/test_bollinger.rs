@@ -0,0 +1,43 @@
+use autograd::ndarray as nd;
+
+use rquant::error::QuantError;
+use rquant::indicators::bollinger::*;
+use rquant::indicators::moving_average::sma;
+
+#[test]
+fn test_bollinger_small_example() {
+    // The window [1, 3] has mean 2 and population deviation 1.
+    let prices = nd::arr1(&[1., 3., 3., 7.]).into_dyn();
+    let (upper, middle, lower) = bollinger_bands(&prices, 2, 2.).unwrap();
+    assert_eq!(middle, nd::arr1(&[2., 3., 5.]).into_dyn());
+    assert_eq!(upper, nd::arr1(&[4., 3., 9.]).into_dyn());
+    assert_eq!(lower, nd::arr1(&[0., 3., 1.]).into_dyn());
+}
+
+#[test]
+fn test_bands_widen_with_volatility() {
+    // Oscillations of amplitude 1 for 40 prices, then of amplitude 5.
+    let prices = (0..80)
+        .map(|i| {
+            let amplitude = if i < 40 { 1. } else { 5. };
+            100. + amplitude * if i % 2 == 0 { 1. } else { -1. }
+        })
+        .collect::<Vec<f64>>();
+    let prices = nd::Array1::from(prices).into_dyn();
+    let window = 10;
+    let (upper, middle, lower) = bollinger_bands(&prices, window, 2.).unwrap();
+    assert_eq!(middle, sma(&prices, window).unwrap());
+    assert_eq!(upper.len(), 80 - window + 1);
+
+    let gap = &upper - &lower;
+    let (calm, volatile) = (gap[0], gap[gap.len() - 1]);
+    assert!((calm - 4.).abs() < 1e-12 && (volatile - 20.).abs() < 1e-12, "{} {}", calm, volatile);
+    assert!(upper.iter().zip(lower.iter()).all(|(u, l)| u >= l));
+}
+
+#[test]
+fn test_bollinger_rejects_bad_window() {
+    let prices = nd::arr1(&[1., 2., 3.]).into_dyn();
+    assert!(matches!(bollinger_bands(&prices, 0, 2.), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(bollinger_bands(&prices, 4, 2.), Err(QuantError::InvalidInput(_))));
+}
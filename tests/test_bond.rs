@@ -0,0 +1,77 @@
+use rquant::error::QuantError;
+use rquant::fixed_income::bond::*;
+
+#[test]
+fn test_bond_at_par_when_yield_equals_coupon() {
+    for &periods_per_year in [1, 2, 4].iter() {
+        let price: f64 = price_bond(100., 0.05, 5 * periods_per_year, periods_per_year, 0.05);
+        assert!((price - 100.).abs() < 1e-10, "price {}", price);
+    }
+}
+
+#[test]
+fn test_discount_bond_textbook_example() {
+    // A 10 year 6% semiannual bond yielding 8%.
+    let price: f64 = price_bond(1000., 0.06, 20, 2, 0.08);
+    assert!((price - 864.0967365503227).abs() < 1e-9, "price {}", price);
+}
+
+#[test]
+fn test_zero_coupon_bond() {
+    let price: f64 = price_bond(100., 0., 10, 1, 0.05);
+    assert!((price - 100. / 1.05f64.powi(10)).abs() < 1e-10);
+    assert!((price - 61.39132535407592).abs() < 1e-10);
+}
+
+#[test]
+fn test_yield_to_maturity_round_trips() {
+    for &(coupon, periods_per_year, y) in [(0.06, 2, 0.08), (0.05, 1, 0.03), (0., 4, 0.045), (0.1, 12, 0.25)].iter() {
+        let price: f64 = price_bond(100., coupon, 10 * periods_per_year, periods_per_year, y);
+        let ytm = yield_to_maturity(price, 100., coupon, 10 * periods_per_year, periods_per_year).unwrap();
+        assert!((ytm - y).abs() < 1e-10, "ytm {} vs {}", ytm, y);
+    }
+}
+
+#[test]
+fn test_yield_to_maturity_requires_a_positive_yield() {
+    // Above the undiscounted sum of the cash flows only a negative yield would fit.
+    assert_eq!(yield_to_maturity(130., 100., 0.05, 5, 1).unwrap_err(), QuantError::NoBracket);
+    assert!(matches!(
+        yield_to_maturity(0., 100., 0.05, 5, 1),
+        Err(QuantError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_yield_to_maturity_rejects_a_bond_without_periods() {
+    // Without periods the price is the face at every yield, so no bracket can grow.
+    assert!(matches!(yield_to_maturity(95., 100., 0.05, 0, 2), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(yield_to_maturity(95., 100., 0.05, 10, 0), Err(QuantError::InvalidInput(_))));
+}
+
+#[test]
+fn test_duration_and_convexity_match_the_cash_flow_sums() {
+    let (face, coupon, n, m, y): (f64, f64, usize, usize, f64) = (100., 0.07, 8, 2, 0.06);
+    let mf = m as f64;
+    let flows = (1..n + 1)
+        .map(|k| (k as f64, face * coupon / mf + if k == n { face } else { 0. }))
+        .collect::<Vec<_>>();
+    let growth = 1. + y / mf;
+    let price = flows.iter().fold(0., |pv, &(k, cf)| pv + cf / growth.powf(k));
+    let macaulay = flows.iter().fold(0., |acc, &(k, cf)| acc + k / mf * cf / growth.powf(k)) / price;
+    let convex = flows
+        .iter()
+        .fold(0., |acc, &(k, cf)| acc + cf * k * (k + 1.) / (mf * mf * growth.powf(k + 2.)))
+        / price;
+
+    let d = duration(face, coupon, n, m, y);
+    assert!((d.macaulay - macaulay).abs() < 1e-10, "macaulay {} vs {}", d.macaulay, macaulay);
+    assert!((d.modified - macaulay / growth).abs() < 1e-10);
+    assert!((convexity(face, coupon, n, m, y) - convex).abs() < 1e-8);
+}
+
+#[test]
+fn test_zero_coupon_macaulay_duration_is_its_maturity() {
+    let d: Duration<f64> = duration(100., 0., 20, 2, 0.05);
+    assert!((d.macaulay - 10.).abs() < 1e-10, "macaulay {}", d.macaulay);
+}
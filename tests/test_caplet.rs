@@ -0,0 +1,70 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::error::QuantError;
+use rquant::fixed_income::caplet::*;
+use rquant::fixed_income::curve::*;
+use rquant::options::black76::price_call_black76;
+
+fn curve() -> ZeroCurve<f64> {
+    let times = vec![0.5, 1., 2., 3.];
+    let discount_factors = times.iter().map(|&t: &f64| (-(0.03 + 0.004 * t) * t).exp()).collect();
+    ZeroCurve::new(times, discount_factors).unwrap()
+}
+
+#[test]
+fn test_caplet_is_scaled_black76_call() {
+    let (forward, strike, vol, time, accrual, discount_factor) = (0.045, 0.04, 0.25, 1.5, 0.25, 0.93);
+    let caplet = price_caplet(forward, strike, vol, time, accrual, discount_factor);
+    let black = ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (f, k, v) = (tensor(forward), tensor(strike), tensor(vol));
+        price_call_black76(&f, &k, &v, 0., time).eval(ctx).unwrap()[0]
+    });
+    assert!((caplet - accrual * discount_factor * black).abs() < 1e-12);
+}
+
+#[test]
+fn test_caplet_floorlet_parity() {
+    let (accrual, discount_factor) = (0.5, 0.96);
+    for &(forward, strike) in [(0.03, 0.04), (0.05, 0.05), (0.06, 0.035)].iter() {
+        let caplet = price_caplet(forward, strike, 0.3, 2., accrual, discount_factor);
+        let floorlet = price_floorlet(forward, strike, 0.3, 2., accrual, discount_factor);
+        let fra = accrual * discount_factor * (forward - strike);
+        assert!((caplet - floorlet - fra).abs() < 1e-15, "{} {}", forward, strike);
+    }
+    // An expired caplet is its intrinsic value.
+    assert!((price_caplet(0.05, 0.04, 0.3, 0., 0.5, 0.99) - 0.5 * 0.99 * 0.01).abs() < 1e-15);
+}
+
+#[test]
+fn test_cap_is_the_sum_of_its_caplets() {
+    let curve = curve();
+    let reset_times = [0.25, 0.5, 0.75, 1., 1.25, 1.5];
+    let vols = [0.2, 0.22, 0.23, 0.24, 0.24];
+    let strike = 0.035;
+
+    let caplets = reset_times.windows(2).zip(vols.iter()).fold(0., |acc, (period, &vol)| {
+        let forward = simple_forward_rate(&curve, period[0], period[1]);
+        acc + price_caplet(forward, strike, vol, period[0], period[1] - period[0], curve.discount_factor(period[1]))
+    });
+    let cap = price_cap(&curve, &reset_times, strike, &vols).unwrap();
+    assert!((cap - caplets).abs() < 1e-15);
+
+    // A cap less a floor is the swap paying the forward rates against the strike.
+    let floor = price_floor(&curve, &reset_times, strike, &vols).unwrap();
+    let swap = curve.discount_factor(0.25) - curve.discount_factor(1.5)
+        - reset_times
+            .windows(2)
+            .fold(0., |acc, period| acc + strike * (period[1] - period[0]) * curve.discount_factor(period[1]));
+    assert!((cap - floor - swap).abs() < 1e-14);
+}
+
+#[test]
+fn test_cap_rejects_bad_schedules() {
+    let curve = FlatCurve::new(0.03);
+    assert!(matches!(price_cap(&curve, &[0.5, 1., 1.5], 0.03, &[0.2]), Err(QuantError::ShapeMismatch { .. })));
+    assert!(matches!(price_cap(&curve, &[1., 0.5], 0.03, &[0.2]), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(price_floor(&curve, &[-0.5, 1.], 0.03, &[0.2]), Err(QuantError::InvalidInput(_))));
+}
@@ -0,0 +1,33 @@
+use rquant::error::QuantError;
+use rquant::options::compound::*;
+use rquant::options::simple::bs_call_price;
+
+#[test]
+fn test_compound_call_on_call_reference_price() {
+    // Haug, The Complete Guide to Option Pricing Formulas, prices the put on this
+    // call at 21.1965. By compound parity the call on the call is worth that plus
+    // the underlying call of 45.4081 less the discounted 50 paid for it.
+    let price = price_compound_call_on_call(500f64, 50., 0.25, 520., 0.5, 0.35, 0.03, 0.08).unwrap();
+    let expected = 21.1965 + 45.4081 - 50. * (-0.08f64 * 0.25).exp();
+    assert!((price - expected).abs() < 1e-3, "expected {}, got {}", expected, price);
+}
+
+#[test]
+fn test_free_compound_option_is_the_underlying_call() {
+    let (s, k2, t2, vol, r): (f64, f64, f64, f64, f64) = (100., 105., 1., 0.25, 0.04);
+    let price = price_compound_call_on_call(s, 1e-8, 0.5, k2, t2, vol, 0., r).unwrap();
+    let vanilla = bs_call_price(s, k2, t2, vol, r);
+    assert!((price - vanilla).abs() < 1e-6, "expected {}, got {}", vanilla, price);
+
+    // Paying more than the stock for the call is never worth it.
+    let worthless = price_compound_call_on_call(s, 1000., 0.5, k2, t2, vol, 0., r).unwrap();
+    assert!(worthless.abs() < 1e-8, "got {}", worthless);
+}
+
+#[test]
+fn test_compound_rejects_unordered_maturities() {
+    assert!(matches!(
+        price_compound_call_on_call(100f64, 5., 1., 100., 0.5, 0.2, 0., 0.03),
+        Err(QuantError::InvalidInput(_))
+    ));
+}
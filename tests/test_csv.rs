@@ -0,0 +1,38 @@
+#![cfg(feature = "csv")]
+
+use rquant::error::QuantError;
+use rquant::io::csv::*;
+use rquant::options::model::*;
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/data/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn test_load_option_chain() {
+    let chain = load_option_chain::<f64, _>(fixture("option_chain.csv")).unwrap();
+    // The rows with a missing or NaN bid are skipped.
+    assert_eq!(chain.len(), 4);
+    assert_eq!(
+        chain.types,
+        vec![OptionType::Call, OptionType::Call, OptionType::Put, OptionType::Put]
+    );
+    assert_eq!(chain.strikes.shape(), &[4]);
+    assert_eq!(chain.strikes.iter().cloned().collect::<Vec<_>>(), vec![95., 100., 95., 100.]);
+    assert!(chain.maturities.iter().all(|&t| t == 0.25));
+    assert_eq!(chain.bids[1], 3.9);
+    assert_eq!(chain.asks[3], 3.4);
+    assert!((chain.mids()[0] - 7.2).abs() < 1e-12);
+}
+
+#[test]
+fn test_malformed_rows_are_errors() {
+    assert!(matches!(
+        load_option_chain::<f64, _>(fixture("malformed_option_chain.csv")),
+        Err(QuantError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        load_option_chain::<f64, _>(fixture("missing.csv")),
+        Err(QuantError::Io(_))
+    ));
+}
@@ -0,0 +1,59 @@
+use rquant::error::QuantError;
+use rquant::models::dupire::*;
+use rquant::options::black_scholes::price_surface;
+use rquant::options::model::OptionType;
+
+fn grid() -> (Vec<f64>, Vec<f64>) {
+    let strikes = (0..33).map(|i| 60. + 2.5 * i as f64).collect();
+    let maturities = (0..16).map(|j| 0.5 + 0.1 * j as f64).collect();
+    (strikes, maturities)
+}
+
+#[test]
+fn test_flat_surface_has_constant_local_vol() {
+    let (strikes, maturities) = grid();
+    let (vol, r) = (0.2, 0.03);
+    let calls = price_surface(OptionType::Call, 100., &strikes, vol, 0., r, &maturities);
+    let surface = local_volatility(calls.view(), &strikes, &maturities, r).unwrap();
+    assert_eq!(surface.strikes(), &strikes[..]);
+    assert_eq!(surface.maturities(), &maturities[..]);
+
+    for &k in strikes.iter() {
+        for &t in maturities.iter() {
+            let local = surface.vol_at(k, t);
+            let tol = if (80. ..=120.).contains(&k) { 3e-3 } else { 3e-2 };
+            assert!((local - vol).abs() < tol, "local vol {} at ({}, {})", local, k, t);
+        }
+    }
+    // Between and beyond the grid points.
+    assert!((surface.vol_at(101.3, 1.23) - vol).abs() < 3e-3);
+    assert_eq!(surface.vol_at(200., 5.), surface.vol_at(140., 2.));
+}
+
+#[test]
+fn test_arbitrage_in_the_quotes_is_smoothed_over() {
+    let (strikes, maturities) = grid();
+    let mut calls = price_surface(OptionType::Call, 100., &strikes, 0.25, 0., 0.02, &maturities);
+    // Raise one call above the average of its neighbours, a negative butterfly.
+    calls[[16, 5]] += 1.;
+    let surface = local_volatility(calls.view(), &strikes, &maturities, 0.02).unwrap();
+    assert!(strikes.iter().all(|&k| {
+        let local = surface.vol_at(k, maturities[5]);
+        local.is_finite() && local > 0.
+    }));
+}
+
+#[test]
+fn test_local_volatility_rejects_bad_grids() {
+    let (strikes, maturities) = grid();
+    let calls = price_surface(OptionType::Call, 100., &strikes, 0.2, 0., 0.03, &maturities);
+    let short = &maturities[..3];
+    assert!(matches!(local_volatility(calls.view(), &strikes, short, 0.03), Err(QuantError::ShapeMismatch { .. })));
+
+    let few = price_surface(OptionType::Call, 100., &[90., 110.], 0.2, 0., 0.03, &[1., 2.]);
+    assert!(matches!(local_volatility(few.view(), &[90., 110.], &[1., 2.], 0.03), Err(QuantError::InvalidInput(_))));
+
+    let flat_prices = calls.mapv(|_| 1.);
+    let flat = local_volatility(flat_prices.view(), &strikes, &maturities, 0.03);
+    assert!(matches!(flat, Err(QuantError::InvalidInput(_))));
+}
@@ -0,0 +1,32 @@
+use autograd::ndarray as nd;
+
+use rquant::models::ewma::*;
+
+#[test]
+fn test_recursion() {
+    let returns = nd::arr1(&[0.01f64, -0.02, 0.03]).into_dyn();
+    let vol = ewma_volatility(&returns, 0.9);
+    let mut variance: f64 = (1e-4 + 4e-4 + 9e-4) / 3.;
+    for (i, &r) in returns.iter().enumerate() {
+        assert!((vol[i] - variance.sqrt()).abs() < 1e-15);
+        variance = 0.9 * variance + 0.1 * r * r;
+    }
+}
+
+#[test]
+fn test_reacts_faster_than_a_rolling_window() {
+    // A year of calm ±1% returns followed by a shock to ±3%.
+    let (calm, shock, window) = (250, 10, 60);
+    let returns = nd::Array1::from_shape_fn(calm + shock + 1, |i| {
+        let size = if i < calm { 0.01 } else { 0.03 };
+        if i % 2 == 0 { size } else { -size }
+    })
+    .into_dyn();
+
+    let ewma = ewma_volatility(&returns, RISKMETRICS_LAMBDA)[calm + shock];
+    let rolling = (returns.iter().skip(calm + shock - window).take(window).map(|r| r * r).sum::<f64>()
+        / window as f64)
+        .sqrt();
+    assert!(ewma > 0.02, "{}", ewma);
+    assert!(ewma > rolling + 0.005, "ewma {} rolling {}", ewma, rolling);
+}
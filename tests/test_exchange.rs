@@ -0,0 +1,57 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::options::black_scholes::*;
+use rquant::options::exchange::*;
+use rquant::options::model::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+#[test]
+fn test_riskless_second_asset_is_a_vanilla_call() {
+    let r: f64 = 0.05;
+    let t: f64 = 1.25;
+    let strike = [90., 100., 110.];
+    // A stock without volatility grows at the risk free rate, so today it is worth
+    // the discounted strike.
+    let discounted = strike.iter().map(|k| k * (-r * t).exp()).collect::<Vec<_>>();
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s1 = tensor(ctx, &[100.; 3]);
+        let s2 = tensor(ctx, &discounted);
+        let vol1 = tensor(ctx, &[0.3; 3]);
+        let zeros = tensor(ctx, &[0.; 3]);
+        let rho = tensor(ctx, &[0.4; 3]);
+        let k = tensor(ctx, &strike);
+
+        let exchange = price_exchange_option(&s1, &s2, &vol1, &zeros, &rho, t);
+        let call = BlackScholesPricingModel::price(OptionType::Call, &s1, &k, &vol1, &zeros, r, t);
+        let difference = (exchange - call).eval(ctx).unwrap();
+        assert!(difference.iter().all(|d| d.abs() < 1e-10), "{:?}", difference);
+    });
+}
+
+#[test]
+fn test_exchange_option_symmetry_and_correlation() {
+    let t: f64 = 1.;
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s1 = tensor(ctx, &[100., 120.]);
+        let s2 = tensor(ctx, &[110., 95.]);
+        let vol1 = tensor(ctx, &[0.2, 0.2]);
+        let vol2 = tensor(ctx, &[0.35, 0.35]);
+        let low = tensor(ctx, &[-0.5, -0.5]);
+        let high = tensor(ctx, &[0.9, 0.9]);
+
+        // V(S₁, S₂) - V(S₂, S₁) = S₁ - S₂, the parity of exchange options.
+        let parity = price_exchange_option(&s1, &s2, &vol1, &vol2, &low, t) - price_exchange_option(&s2, &s1, &vol2, &vol1, &low, t)
+            - (s1 - s2);
+        let spread = price_exchange_option(&s1, &s2, &vol1, &vol2, &low, t) - price_exchange_option(&s1, &s2, &vol1, &vol2, &high, t);
+        let mut results = ctx.evaluator().push(&parity).push(&spread).run().into_iter();
+        assert!(results.next().unwrap().unwrap().iter().all(|d| d.abs() < 1e-10));
+        // A higher correlation lowers the volatility of the spread and so the price.
+        assert!(results.next().unwrap().unwrap().iter().all(|&d| d > 0.));
+    });
+}
@@ -0,0 +1,50 @@
+//! Compile checks of each optional feature's API, so every feature combination can be
+//! verified with `cargo test --no-default-features --features ...` without CI. The
+//! `plot` feature has no API yet.
+
+mod without_features {
+    use rquant::options::model::OptionType;
+    use rquant::options::simple::bs_call_price;
+
+    #[test]
+    fn test_core_pricing_needs_no_features() {
+        assert!(bs_call_price(100., 100., 1., 0.2, 0.05) > 0.);
+        assert_eq!(OptionType::Put.payoff(90f64, 100.), 10.);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod with_serde {
+    use rquant::fixed_income::curve::FlatCurve;
+
+    #[test]
+    fn test_serde_derives_are_available() {
+        fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+        assert_serde::<FlatCurve<f64>>();
+        assert_serde::<rquant::options::model::OptionType>();
+    }
+}
+
+#[cfg(feature = "csv")]
+mod with_csv {
+    use rquant::error::QuantError;
+    use rquant::io::csv::load_option_chain;
+
+    #[test]
+    fn test_csv_loader_is_available() {
+        let missing = load_option_chain::<f64, _>("no/such/chain.csv");
+        assert!(matches!(missing, Err(QuantError::Io(_))));
+    }
+}
+
+#[cfg(feature = "rayon")]
+mod with_rayon {
+    use rquant::options::model::OptionType;
+    use rquant::options::monte_carlo::price_european_mc;
+
+    #[test]
+    fn test_parallel_monte_carlo_is_available() {
+        let estimate = price_european_mc(OptionType::Call, 100f64, 100., 0.2, 0., 0.05, 1., 10_000, 1);
+        assert!(estimate.std_error > 0.);
+    }
+}
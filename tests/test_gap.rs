@@ -0,0 +1,49 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::options::binary::*;
+use rquant::options::black_scholes::*;
+use rquant::options::gap::*;
+use rquant::options::model::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+#[test]
+fn test_gap_with_equal_strikes_is_vanilla() {
+    let (r, t): (f64, f64) = (0.05, 0.5);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[80., 100., 120.]);
+        let k = tensor(ctx, &[100.; 3]);
+        let vol = tensor(ctx, &[0.3; 3]);
+        let q = tensor(ctx, &[0.02; 3]);
+        for &ty in [OptionType::Call, OptionType::Put].iter() {
+            let gap = price_gap(ty, &s, &k, &k, &vol, &q, r, t);
+            let vanilla = BlackScholesPricingModel::price(ty, &s, &k, &vol, &q, r, t);
+            let difference = (gap - vanilla).eval(ctx).unwrap();
+            assert!(difference.iter().all(|d| d.abs() < 1e-10), "{:?}", difference);
+        }
+    });
+}
+
+#[test]
+fn test_gap_is_vanilla_plus_digital() {
+    let (r, t): (f64, f64) = (0.03, 1.);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[90., 100., 110.]);
+        let k = tensor(ctx, &[95.; 3]);
+        let trigger = tensor(ctx, &[105.; 3]);
+        let vol = tensor(ctx, &[0.25; 3]);
+        let q = tensor(ctx, &[0.; 3]);
+
+        // Struck 10 below its trigger, a gap call pays the vanilla at the trigger plus
+        // 10 whenever it is exercised.
+        let gap = price_gap(OptionType::Call, &s, &k, &trigger, &vol, &q, r, t);
+        let vanilla = BlackScholesPricingModel::price(OptionType::Call, &s, &trigger, &vol, &q, r, t);
+        let digital = price_cash_or_nothing(OptionType::Call, &s, &trigger, &vol, &q, r, t, 10.);
+        let difference = (gap - (vanilla + digital)).eval(ctx).unwrap();
+        assert!(difference.iter().all(|d| d.abs() < 1e-10), "{:?}", difference);
+    });
+}
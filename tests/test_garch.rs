@@ -0,0 +1,54 @@
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+
+use rquant::models::garch::*;
+use rquant::stats::normal;
+
+/// Simulate returns from a GARCH(1,1) process, discarding a burn in period.
+fn simulate(omega: f64, alpha: f64, beta: f64, n: usize, seed: u64) -> nd::ArrayD<f64> {
+    let burn_in = 500;
+    let z = normal::sample::<f64, _>(&[n + burn_in], 0., 1., &mut StdRng::seed_from_u64(seed));
+    let mut variance = omega / (1. - alpha - beta);
+    let returns = z
+        .iter()
+        .map(|&z| {
+            let r = variance.sqrt() * z;
+            variance = omega + alpha * r * r + beta * variance;
+            r
+        })
+        .skip(burn_in)
+        .collect::<Vec<_>>();
+    nd::Array1::from(returns).into_dyn()
+}
+
+#[test]
+fn test_fit_recovers_simulated_parameters() {
+    let returns = simulate(1e-5, 0.1, 0.85, 4000, 11);
+    let fit = fit_garch_11(&returns).unwrap();
+    assert!((fit.alpha - 0.1).abs() < 0.03, "{:?}", fit);
+    assert!((fit.beta - 0.85).abs() < 0.05, "{:?}", fit);
+    assert!((fit.alpha + fit.beta - 0.95).abs() < 0.03, "{:?}", fit);
+    assert!((fit.long_run_variance() - 2e-4).abs() < 5e-5, "{:?}", fit);
+}
+
+#[test]
+fn test_forecast_reverts_to_long_run_variance() {
+    let params: GarchParams<f64> = GarchParams {
+        omega: 1e-5,
+        alpha: 0.1,
+        beta: 0.85,
+        variance: 1e-4,
+    };
+    let forecast = forecast_variance(&params, 200);
+    assert_eq!(forecast.shape(), &[200]);
+    assert!((forecast[0] - 1e-4).abs() < 1e-18);
+    // Each step closes 5% of the gap to the long run variance of 2e-4.
+    assert!((forecast[1] - (2e-4 - 0.95 * 1e-4)).abs() < 1e-15);
+    assert!(forecast.iter().zip(forecast.iter().skip(1)).all(|(a, b)| b > a));
+    assert!((forecast[199] - 2e-4).abs() < 1e-8);
+}
+
+#[test]
+fn test_fit_rejects_short_series() {
+    assert!(fit_garch_11(&nd::arr1(&[0.01f64, -0.02]).into_dyn()).is_err());
+}
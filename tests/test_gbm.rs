@@ -0,0 +1,53 @@
+use autograd::rand::{rngs::StdRng, SeedableRng};
+
+use rquant::models::gbm::*;
+
+#[test]
+fn test_paths_layout() {
+    let paths = simulate_gbm_paths(100., 0.2, 0., 0.05, 1., 12, 5, false, &mut StdRng::seed_from_u64(1));
+    assert_eq!(paths.shape(), &[5, 13]);
+    assert!(paths.outer_iter().all(|path| path[0] == 100.));
+    assert!(paths.iter().all(|&st| st > 0.));
+}
+
+#[test]
+fn test_seeded_paths_are_reproducible() {
+    let a = simulate_gbm_paths(100., 0.2, 0.01, 0.05, 1., 10, 10, false, &mut StdRng::seed_from_u64(42));
+    let b = simulate_gbm_paths(100., 0.2, 0.01, 0.05, 1., 10, 10, false, &mut StdRng::seed_from_u64(42));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_terminal_distribution_is_lognormal() {
+    let (s, vol, q, r, t): (f64, f64, f64, f64, f64) = (100., 0.3, 0.02, 0.05, 2.);
+    let n_paths = 200_000;
+    let paths = simulate_gbm_paths(s, vol, q, r, t, 4, n_paths, false, &mut StdRng::seed_from_u64(3));
+    let n = n_paths as f64;
+
+    let terminal = paths.outer_iter().map(|path| path[4]).collect::<Vec<_>>();
+    let mean = terminal.iter().sum::<f64>() / n;
+    let expected_mean = s * ((r - q) * t).exp();
+    let std_error = expected_mean * ((vol * vol * t).exp() - 1.).sqrt() / n.sqrt();
+    assert!((mean - expected_mean).abs() < 4. * std_error, "mean {} vs {}", mean, expected_mean);
+
+    let logs = terminal.iter().map(|st| (st / s).ln()).collect::<Vec<_>>();
+    let log_mean = logs.iter().sum::<f64>() / n;
+    let log_var = logs.iter().map(|x| (x - log_mean).powi(2)).sum::<f64>() / (n - 1.);
+    assert!((log_mean - (r - q - 0.5 * vol * vol) * t).abs() < 5e-3);
+    assert!((log_var - vol * vol * t).abs() < 5e-3);
+}
+
+#[test]
+fn test_antithetic_paths_mirror_each_other() {
+    let (s, vol, r, t): (f64, f64, f64, f64) = (100., 0.2, 0.05, 1.);
+    let paths = simulate_gbm_paths(s, vol, 0., r, t, 6, 5, true, &mut StdRng::seed_from_u64(9));
+    // ln(up / s) + ln(down / s) cancels the shocks and leaves twice the drift.
+    let drift = (r - 0.5 * vol * vol) * t / 6.;
+    for pair in 0..2 {
+        for j in 1..7 {
+            let up = (paths[[2 * pair, j]] / s).ln();
+            let down = (paths[[2 * pair + 1, j]] / s).ln();
+            assert!((up + down - 2. * drift * j as f64).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use common::black_scholes;
+use rquant::models::heston::*;
+use rquant::options::model::*;
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[test]
+fn test_heston_benchmark_price() {
+    // Fang and Oosterlee (2008), table 1.
+    let price = price_call_heston(100., 100., 1., 0., 0.0175, 1.5768, 0.0398, 0.5751, -0.5711);
+    assert!((price - 5.785155450).abs() < 1e-6, "price {}", price);
+}
+
+#[test]
+fn test_heston_reduces_to_black_scholes_without_vol_of_vol() {
+    let (s, k, t, r, v0, kappa, theta): (f64, f64, f64, f64, f64, f64, f64) = (100., 110., 0.5, 0.05, 0.05, 2., 0.03);
+    // With a deterministic variance the call is priced at the mean variance over its life.
+    let mean_variance = theta + (v0 - theta) * (1. - (-kappa * t).exp()) / (kappa * t);
+    let european = black_scholes(OptionType::Call, s, k, mean_variance.sqrt(), 0., r, t);
+
+    let rough = price_call_heston(s, k, t, r, v0, kappa, theta, 1e-2, -0.5);
+    let fine = price_call_heston(s, k, t, r, v0, kappa, theta, 1e-4, -0.5);
+    assert!((fine - european).abs() < 5e-4, "heston {} vs black-scholes {}", fine, european);
+    assert!((fine - european).abs() < (rough - european).abs());
+}
+
+#[test]
+fn test_heston_respects_no_arbitrage_bounds() {
+    let (s, t, r): (f64, f64, f64) = (100., 2., 0.03);
+    let mut previous = s;
+    for &k in [60., 80., 100., 120., 140.].iter() {
+        let price = price_call_heston(s, k, t, r, 0.04, 1., 0.06, 0.6, -0.7);
+        assert!(price > (s - k * (-r * t).exp()).max(0.) && price < s, "price {} at strike {}", price, k);
+        assert!(price < previous);
+        previous = price;
+    }
+}
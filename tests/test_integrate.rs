@@ -0,0 +1,47 @@
+use rquant::numerics::integrate::*;
+
+#[test]
+fn test_gauss_legendre_integrates_sine() {
+    let integral = gauss_legendre(f64::sin, 0., std::f64::consts::PI, 16);
+    assert!((integral - 2.).abs() < 1e-13, "got {}", integral);
+}
+
+#[test]
+fn test_gauss_legendre_is_exact_for_polynomials() {
+    // 5 points integrate degree 9 exactly: ∫₋₁² x⁹ dx = (2¹⁰ - 1) / 10.
+    let integral = gauss_legendre(|x: f64| x.powi(9), -1., 2., 5);
+    assert!((integral - 102.3).abs() < 1e-10, "got {}", integral);
+}
+
+#[test]
+fn test_gauss_legendre_nodes_and_weights() {
+    let (nodes, weights) = gauss_legendre_nodes::<f64>(3);
+    let root = (0.6f64).sqrt();
+    let expected_nodes = [root, 0., -root];
+    let expected_weights = [5. / 9., 8. / 9., 5. / 9.];
+    for i in 0..3 {
+        assert!((nodes[i] - expected_nodes[i]).abs() < 1e-14);
+        assert!((weights[i] - expected_weights[i]).abs() < 1e-14);
+    }
+
+    // The weights sum to the length of [-1, 1] at high order too.
+    let (_, weights) = gauss_legendre_nodes::<f64>(128);
+    let total = weights.iter().sum::<f64>();
+    assert!((total - 2.).abs() < 1e-12, "got {}", total);
+}
+
+#[test]
+fn test_simpson_integrates_sine_and_exponential() {
+    let integral = simpson(f64::sin, 0., std::f64::consts::PI, 200);
+    assert!((integral - 2.).abs() < 1e-8, "got {}", integral);
+
+    // An odd number of intervals is rounded up to an even one.
+    let integral = simpson(f64::exp, 0., 1., 101);
+    assert!((integral - (std::f64::consts::E - 1.)).abs() < 1e-9, "got {}", integral);
+}
+
+#[test]
+fn test_simpson_is_exact_for_cubics() {
+    let integral = simpson(|x: f64| x * x * x - 2. * x + 1., 0., 3., 2);
+    assert!((integral - 14.25).abs() < 1e-12, "got {}", integral);
+}
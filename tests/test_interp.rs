@@ -0,0 +1,64 @@
+use rquant::error::QuantError;
+use rquant::numerics::interp::*;
+
+fn spline() -> CubicSpline<f64> {
+    CubicSpline::new(vec![0., 0.5, 1.5, 2., 4.], vec![1., 2., 0.5, 0.7, 3.]).unwrap()
+}
+
+#[test]
+fn test_spline_passes_through_knots() {
+    let spline = spline();
+    for (&x, &y) in spline.xs().iter().zip(spline.ys().iter()) {
+        assert!((spline.eval(x) - y).abs() < 1e-14, "expected {}, got {}", y, spline.eval(x));
+    }
+}
+
+#[test]
+fn test_spline_is_twice_continuously_differentiable() {
+    let spline = spline();
+    let h = 1e-9;
+    for &x in spline.xs()[1..4].iter() {
+        assert!((spline.eval(x - h) - spline.eval(x + h)).abs() < 1e-7);
+        assert!((spline.derivative(x - h) - spline.derivative(x + h)).abs() < 1e-6);
+        assert!((spline.second_derivative(x - h) - spline.second_derivative(x + h)).abs() < 1e-6);
+    }
+    // A natural spline has no curvature at its ends.
+    assert!(spline.second_derivative(0.).abs() < 1e-14);
+    assert!(spline.second_derivative(4.).abs() < 1e-14);
+
+    // The derivative agrees with a central difference of the values.
+    let x = 0.8;
+    let difference = (spline.eval(x + 1e-6) - spline.eval(x - 1e-6)) / 2e-6;
+    assert!((spline.derivative(x) - difference).abs() < 1e-6);
+}
+
+#[test]
+fn test_spline_reproduces_lines_and_extrapolates_flat() {
+    let spline = CubicSpline::new(vec![1., 2., 4., 7.], vec![3., 5., 9., 15.]).unwrap();
+    assert!((spline.eval(3.) - 7.).abs() < 1e-12);
+    assert!((spline.eval(5.5) - 12.).abs() < 1e-12);
+    assert_eq!(spline.eval(0.), 3.);
+    assert_eq!(spline.eval(10.), 15.);
+    assert_eq!(spline.derivative(10.), 0.);
+}
+
+#[test]
+fn test_spline_rejects_invalid_knots() {
+    assert!(matches!(CubicSpline::new(vec![0., 1.], vec![1.]), Err(QuantError::ShapeMismatch { .. })));
+    assert!(matches!(
+        CubicSpline::new(vec![0., 2., 1.], vec![1., 2., 3.]),
+        Err(QuantError::InvalidInput(_))
+    ));
+    assert!(CubicSpline::<f64>::new(vec![], vec![]).is_err());
+}
+
+#[test]
+fn test_linear_interpolation() {
+    let xs = [1., 2., 4.];
+    let ys = [10., 20., 0.];
+    assert_eq!(linear_interpolation(&xs, &ys, 1.5), 15.);
+    assert_eq!(linear_interpolation(&xs, &ys, 3.), 10.);
+    assert_eq!(linear_interpolation(&xs, &ys, 2.), 20.);
+    assert_eq!(linear_interpolation(&xs, &ys, 0.), 10.);
+    assert_eq!(linear_interpolation(&xs, &ys, 5.), 0.);
+}
@@ -0,0 +1,45 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+use autograd::tensor_ops as math;
+
+use rquant::stats::lognormal;
+
+#[test]
+fn test_sample_mean_matches_the_parameters() {
+    let (mu, sigma): (f64, f64) = (0.1, 0.3);
+    let samples = lognormal::sample(&[200_000], mu, sigma, &mut StdRng::seed_from_u64(23));
+    let n = samples.len() as f64;
+    let mean = samples.iter().fold(0., |acc, x| acc + x) / n;
+
+    let expected = (mu + 0.5 * sigma * sigma).exp();
+    let std = ((sigma * sigma).exp() - 1.).sqrt() * expected;
+    assert!((mean - expected).abs() < 4. * std / n.sqrt(), "mean {} vs {}", mean, expected);
+}
+
+#[test]
+fn test_pdf_first_moment_matches_the_mean() {
+    let (mu, sigma): (f64, f64) = (0.2, 0.25);
+    let n = 20001;
+    let (lo, hi): (f64, f64) = (1e-6, 20.);
+    let dx = (hi - lo) / (n - 1) as f64;
+    let grid = nd::Array1::linspace(lo, hi, n).into_dyn();
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let x = math::convert_to_tensor(grid.clone(), ctx);
+        let first_moment = (x * lognormal::pdf(&x, mu, sigma)).eval(ctx).unwrap();
+        let mean = first_moment.iter().fold(0., |acc, m| acc + m) * dx;
+        let expected = (mu + 0.5 * sigma * sigma).exp();
+        assert!((mean - expected).abs() < 1e-6, "mean {} vs {}", mean, expected);
+    });
+}
+
+#[test]
+fn test_cdf_is_one_half_at_the_median() {
+    let mu: f64 = 0.4;
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let x = math::convert_to_tensor(nd::arr1(&[mu.exp()]).into_dyn(), ctx);
+        let p = lognormal::cdf(&x, mu, 0.5).eval(ctx).unwrap();
+        assert!((p[0] - 0.5).abs() < 1e-12);
+    });
+}
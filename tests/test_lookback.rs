@@ -0,0 +1,67 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+use autograd::tensor_ops as math;
+
+use rquant::options::black_scholes::*;
+use rquant::options::lookback::*;
+use rquant::options::model::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+#[test]
+fn test_floating_lookback_reference_prices() {
+    // Hull, Options, Futures, and Other Derivatives: S = 50, σ = 40%, r = 10%, T = 3 months.
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[50.]);
+        let vol = tensor(ctx, &[0.4]);
+        let q = tensor(ctx, &[0.]);
+        let call = price_floating_lookback(OptionType::Call, &s, &s, &vol, &q, 0.1, 0.25).eval(ctx).unwrap();
+        let put = price_floating_lookback(OptionType::Put, &s, &s, &vol, &q, 0.1, 0.25).eval(ctx).unwrap();
+        assert!((call[0] - 8.04).abs() < 5e-3, "got {}", call[0]);
+        assert!((put[0] - 7.79).abs() < 5e-3, "got {}", put[0]);
+    });
+}
+
+#[test]
+fn test_lookback_call_exceeds_vanilla_call() {
+    let (r, t): (f64, f64) = (0.05, 1.);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100., 100.]);
+        // A new option, and one whose running minimum already sits below the spot.
+        let minimum = tensor(ctx, &[100., 90.]);
+        let vol = tensor(ctx, &[0.25, 0.25]);
+        let q = tensor(ctx, &[0.01, 0.01]);
+
+        let lookback = price_floating_lookback(OptionType::Call, &s, &minimum, &vol, &q, r, t);
+        let vanilla = BlackScholesPricingModel::price(OptionType::Call, &s, &minimum, &vol, &q, r, t);
+        let difference = (lookback - vanilla).eval(ctx).unwrap();
+        assert!(difference.iter().all(|&d| d > 0.), "{:?}", difference);
+    });
+}
+
+#[test]
+fn test_fixed_lookback_mc_exceeds_vanilla() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 100., 0.3, 0., 0.04, 0.5);
+    let vanilla = ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (spot, strike, sigma, dividends) = (tensor(s), tensor(k), tensor(vol), tensor(q));
+        let call = BlackScholesPricingModel::price(OptionType::Call, &spot, &strike, &sigma, &dividends, r, t);
+        let put = BlackScholesPricingModel::price(OptionType::Put, &spot, &strike, &sigma, &dividends, r, t);
+        (call.eval(ctx).unwrap()[0], put.eval(ctx).unwrap()[0])
+    });
+
+    let call = price_fixed_lookback_mc(OptionType::Call, s, k, vol, q, r, t, 100, 20_000, &mut StdRng::seed_from_u64(17));
+    let put = price_fixed_lookback_mc(OptionType::Put, s, k, vol, q, r, t, 100, 20_000, &mut StdRng::seed_from_u64(19));
+    assert!(call.price - 4. * call.std_error > vanilla.0, "{:?} vs {}", call, vanilla.0);
+    assert!(put.price - 4. * put.std_error > vanilla.1, "{:?} vs {}", put, vanilla.1);
+}
+
+#[test]
+fn test_fixed_lookback_mc_without_paths_is_nan() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let estimate = price_fixed_lookback_mc(OptionType::Call, 100., 100., 0.3, 0., 0.04, 0.5, 10, 0, &mut rng);
+    assert!(estimate.price.is_nan() && estimate.std_error.is_nan(), "{:?}", estimate);
+}
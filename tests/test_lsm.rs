@@ -0,0 +1,45 @@
+use autograd::rand::{rngs::StdRng, SeedableRng};
+
+use rquant::error::QuantError;
+use rquant::options::binomial::*;
+use rquant::options::lsm::*;
+use rquant::options::model::*;
+
+#[test]
+fn test_lsm_put_converges_to_binomial() {
+    // Longstaff and Schwartz (2001), table 1.
+    for &(s, vol) in [(36., 0.2), (40., 0.2), (44., 0.4)].iter() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let estimate = price_american_lsm(OptionType::Put, s, 40., vol, 0., 0.06, 1., 50, 50_000, 3, &mut rng).unwrap();
+        let binomial = price_american_put(s, 40., vol, 0., 0.06, 1., 2000);
+        assert!(
+            (estimate.price - binomial).abs() < 0.05 + 3. * estimate.std_error,
+            "lsm {} +/- {} vs binomial {}",
+            estimate.price,
+            estimate.std_error,
+            binomial
+        );
+    }
+}
+
+#[test]
+fn test_lsm_call_without_dividends_is_not_exercised_early() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let estimate =
+        price_american_lsm(OptionType::Call, 100., 100., 0.25, 0., 0.05, 1., 20, 50_000, 2, &mut rng).unwrap();
+    let binomial = price_american_call(100., 100., 0.25, 0., 0.05, 1., 2000);
+    assert!(
+        (estimate.price - binomial).abs() < 0.05 + 3. * estimate.std_error,
+        "lsm {} +/- {} vs binomial {}",
+        estimate.price,
+        estimate.std_error,
+        binomial
+    );
+}
+
+#[test]
+fn test_lsm_without_exercise_dates_is_rejected() {
+    let estimate =
+        price_american_lsm(OptionType::Put, 36., 40., 0.2, 0., 0.06, 1., 0, 1_000, 3, &mut StdRng::seed_from_u64(3));
+    assert!(matches!(estimate, Err(QuantError::InvalidInput(_))), "{:?}", estimate);
+}
@@ -0,0 +1,31 @@
+use autograd::ndarray as nd;
+
+use rquant::error::QuantError;
+use rquant::indicators::macd::*;
+use rquant::indicators::moving_average::ema;
+
+#[test]
+fn test_macd_histogram_crosses_at_the_turning_point() {
+    // A price rising one per period to a peak at 60, then falling one per period.
+    let prices = (0..121).map(|i| if i <= 60 { 100. + i as f64 } else { 220. - i as f64 }).collect::<Vec<f64>>();
+    let prices = nd::Array1::from(prices).into_dyn();
+    let (macd_line, signal_line, histogram) = macd(&prices, 12, 26, 9).unwrap();
+    assert_eq!(macd_line.len(), 121);
+    assert_eq!(signal_line.len(), 121);
+
+    let expected = ema(&prices, 12).unwrap() - ema(&prices, 26).unwrap();
+    assert_eq!(macd_line, expected);
+    assert_eq!(histogram, &macd_line - &signal_line);
+
+    assert_eq!(histogram[0], 0.);
+    assert!(histogram.iter().skip(1).take(60).all(|&h| h > 0.));
+    assert!(histogram.iter().skip(61).all(|&h| h < 0.));
+}
+
+#[test]
+fn test_macd_rejects_bad_spans() {
+    let prices = nd::arr1(&[1., 2., 3.]).into_dyn();
+    assert!(matches!(macd(&prices, 26, 12, 9), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(macd(&prices, 0, 12, 9), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(macd(&prices, 12, 26, 0), Err(QuantError::InvalidInput(_))));
+}
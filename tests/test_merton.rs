@@ -0,0 +1,49 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::models::merton::*;
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+
+fn assert_close(actual: &ag::NdArray<f64>, expected: &ag::NdArray<f64>, tol: f64) {
+    assert_eq!(actual.shape(), expected.shape());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < tol, "expected {}, got {}", e, a);
+    }
+}
+
+#[test]
+fn test_zero_jump_intensity_is_black_scholes() {
+    let r = 0.05;
+    let t = 0.75;
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |xs: &[f64]| math::convert_to_tensor(nd::arr1(xs).into_dyn(), ctx);
+        let s = tensor(&[100., 100., 100.]);
+        let k = tensor(&[90., 100., 110.]);
+        let vol = tensor(&[0.2, 0.25, 0.3]);
+        let q = tensor(&[0., 0., 0.]);
+
+        let merton = price_call_merton(&s, &k, &vol, r, t, 0., -0.1, 0.15).eval(ctx).unwrap();
+        let european = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r, t)
+            .eval(ctx)
+            .unwrap();
+        assert_close(&merton, &european, 1e-12);
+    });
+}
+
+#[test]
+fn test_merton_reference_prices() {
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |xs: &[f64]| math::convert_to_tensor(nd::arr1(xs).into_dyn(), ctx);
+        let s = tensor(&[100.]);
+        let k = tensor(&[100.]);
+        let vol = tensor(&[0.2]);
+        let price = price_call_merton(&s, &k, &vol, 0.05, 1., 1., -0.1, 0.15).eval(ctx).unwrap();
+        assert_close(&price, &nd::arr1(&[12.761288593628752]).into_dyn(), 1e-9);
+
+        let k = tensor(&[80.]);
+        let price = price_call_merton(&s, &k, &vol, 0.05, 0.5, 2., -0.05, 0.1).eval(ctx).unwrap();
+        assert_close(&price, &nd::arr1(&[22.713448354924058]).into_dyn(), 1e-9);
+    });
+}
@@ -0,0 +1,71 @@
+use autograd::ndarray as nd;
+
+use rquant::risk::metrics::*;
+
+fn returns() -> nd::ArrayD<f64> {
+    nd::arr1(&[0.02, -0.01, 0.03, -0.02, 0.01]).into_dyn()
+}
+
+#[test]
+fn test_sharpe_ratio() {
+    // A mean of 0.6% over a sample standard deviation of √0.00043.
+    let expected = 0.006 / 0.00043f64.sqrt();
+    assert!((sharpe_ratio(&returns(), 0.) - expected).abs() < 1e-12);
+    // Shifting the returns and the risk free rate together leaves the ratio unchanged.
+    let shifted = returns().mapv(|r| r + 0.001);
+    assert!((sharpe_ratio(&shifted, 0.001) - expected).abs() < 1e-12);
+}
+
+#[test]
+fn test_sortino_ratio_counts_only_the_downside() {
+    // The downside deviation is √((0.01² + 0.02²) / 5) = 1%.
+    assert!((sortino_ratio(&returns(), 0.) - 0.6).abs() < 1e-12);
+    let gains = nd::arr1(&[0.01f64, 0.02, 0.03]).into_dyn();
+    assert_eq!(sortino_ratio(&gains, 0.), f64::INFINITY);
+    assert_eq!(sortino_ratio(&gains, 0.02), 0.);
+}
+
+#[test]
+fn test_calmar_ratio() {
+    // The worst drawdown is the 2% loss from the peak after the third period.
+    assert!((calmar_ratio(&returns()) - 0.3).abs() < 1e-12);
+    let gains = nd::arr1(&[0.01f64, 0.02, 0.03]).into_dyn();
+    assert_eq!(calmar_ratio(&gains), f64::INFINITY);
+}
+
+#[test]
+fn test_max_drawdown() {
+    let curve = nd::arr1(&[100f64, 50., 120.]).into_dyn();
+    assert!((max_drawdown(&curve) - 0.5).abs() < 1e-15);
+    // A deeper fall from a later, higher peak is the maximum.
+    let curve = nd::arr1(&[100f64, 80., 120., 60., 90.]).into_dyn();
+    assert!((max_drawdown(&curve) - 0.5).abs() < 1e-15);
+    let curve = nd::arr1(&[100f64, 110., 110., 130.]).into_dyn();
+    assert_eq!(max_drawdown(&curve), 0.);
+}
+
+#[test]
+fn test_ulcer_index() {
+    // Drawdowns of 0, 10%, 20%, 0, 0 and 10% have a root mean square of 10%.
+    let curve = nd::arr1(&[100f64, 90., 80., 100., 110., 99.]).into_dyn();
+    assert!((ulcer_index(&curve) - 0.1).abs() < 1e-12);
+    let rising = nd::arr1(&[100f64, 110., 110., 130.]).into_dyn();
+    assert_eq!(ulcer_index(&rising), 0.);
+
+    // Two curves with the same maximum drawdown, one recovering at once.
+    let brief = nd::arr1(&[100f64, 90., 100., 100., 100., 100., 100.]).into_dyn();
+    let lasting = nd::arr1(&[100f64, 90., 90., 90., 90., 90., 100.]).into_dyn();
+    assert_eq!(max_drawdown(&brief), max_drawdown(&lasting));
+    assert!((ulcer_index(&brief) - (0.01f64 / 7.).sqrt()).abs() < 1e-12);
+    assert!((ulcer_index(&lasting) - (0.05f64 / 7.).sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn test_pain_ratio() {
+    // The compounded curve sits 1%, 2% and 1.02% below its peaks in three of six points.
+    let pain_index = (0.01 + 0.02 + 0.0102) / 6.;
+    assert!((pain_ratio(&returns(), 0.) - 0.006 / pain_index).abs() < 1e-12);
+    assert!((pain_ratio(&returns(), 0.001) - 0.005 / pain_index).abs() < 1e-12);
+    let gains = nd::arr1(&[0.01f64, 0.02, 0.03]).into_dyn();
+    assert_eq!(pain_ratio(&gains, 0.), f64::INFINITY);
+}
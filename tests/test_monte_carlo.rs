@@ -0,0 +1,159 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use common::black_scholes;
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+use rquant::options::monte_carlo::*;
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[test]
+fn test_european_mc_converges_to_black_scholes() {
+    let (s, k, vol, q, r, t) = (100., 105., 0.25, 0.02, 0.05, 1.5);
+    for ty in [OptionType::Call, OptionType::Put] {
+        let estimate = price_european_mc(ty, s, k, vol, q, r, t, 200_000, 17);
+        let exact = black_scholes(ty, s, k, vol, q, r, t);
+        assert!(
+            (estimate.price - exact).abs() < 4. * estimate.std_error,
+            "{:?} vs {}",
+            estimate,
+            exact
+        );
+    }
+}
+
+#[test]
+fn test_estimate_without_samples_is_nan() {
+    let estimate = MonteCarloEstimate::<f64>::from_samples(&[]);
+    assert!(estimate.price.is_nan() && estimate.std_error.is_nan(), "{:?}", estimate);
+}
+
+#[test]
+fn test_european_mc_is_reproducible() {
+    let price = |seed| price_european_mc(OptionType::Call, 100f64, 100., 0.2, 0., 0.03, 1., 10_000, seed);
+    assert_eq!(price(5), price(5));
+    assert_ne!(price(5), price(6));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_european_mc_does_not_depend_on_the_thread_count() {
+    use autograd::rayon::ThreadPoolBuilder;
+
+    let price = || price_european_mc(OptionType::Put, 100f64, 95., 0.3, 0.01, 0.04, 0.5, 50_000, 99);
+    let serial = ThreadPoolBuilder::new().num_threads(1).build().unwrap().install(price);
+    let parallel = ThreadPoolBuilder::new().num_threads(4).build().unwrap().install(price);
+    assert_eq!(serial, parallel);
+}
+
+/// Times two million paths on pools of 1, 2 and 4 threads. Run it with
+/// `cargo test --release --features rayon -- --ignored --nocapture bench_european_mc`.
+#[cfg(feature = "rayon")]
+#[test]
+#[ignore]
+fn bench_european_mc_scales_with_the_thread_count() {
+    use autograd::rayon::ThreadPoolBuilder;
+    use std::time::Instant;
+
+    let price = || price_european_mc(OptionType::Call, 100f64, 100., 0.2, 0., 0.03, 1., 2_000_000, 7);
+    let timed = |threads: usize| {
+        let pool = ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+        let start = Instant::now();
+        let estimate = pool.install(price);
+        (estimate, start.elapsed())
+    };
+
+    let (serial, serial_time) = timed(1);
+    println!("1 thread: {:?}", serial_time);
+    for threads in [2, 4] {
+        let (estimate, time) = timed(threads);
+        println!(
+            "{} threads: {:?}, {:.2}x the single thread speed",
+            threads,
+            time,
+            serial_time.as_secs_f64() / time.as_secs_f64()
+        );
+        assert_eq!(estimate, serial);
+    }
+}
+
+#[test]
+fn test_pathwise_greeks_match_black_scholes() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 95., 0.3, 0.01, 0.04, 0.75);
+    let (delta, vega) = ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (spot, strike, sigma, dividends) = (tensor(s), tensor(k), tensor(vol), tensor(q));
+        let delta = BlackScholesPricingModel::delta(OptionType::Call, &spot, &strike, &sigma, &dividends, r, t);
+        let vega = BlackScholesPricingModel::vega(OptionType::Call, &spot, &strike, &sigma, &dividends, r, t);
+        (delta.eval(ctx).unwrap()[0], vega.eval(ctx).unwrap()[0])
+    });
+
+    let mc_delta = mc_call_delta(s, k, vol, q, r, t, 200_000, 23);
+    let mc_vega = mc_call_vega(s, k, vol, q, r, t, 200_000, 23);
+    assert!((mc_delta.price - delta).abs() < 4. * mc_delta.std_error, "{:?} vs {}", mc_delta, delta);
+    assert!((mc_vega.price - vega).abs() < 4. * mc_vega.std_error, "{:?} vs {}", mc_vega, vega);
+    // The pathwise estimates are far less noisy than the price itself.
+    assert!(mc_delta.std_error < 2e-3);
+}
+
+#[test]
+fn test_likelihood_ratio_greeks_of_a_digital() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 105., 0.25, 0.02, 0.05, 0.5);
+    let std = vol * t.sqrt();
+    let d1 = ((s / k).ln() + (r - q + 0.5 * vol * vol) * t) / std;
+    let d2 = d1 - std;
+    let density = (-0.5 * d2 * d2).exp() / (2. * std::f64::consts::PI).sqrt();
+    let delta = (-r * t).exp() * density / (s * std);
+    let vega = -(-r * t).exp() * density * d1 / vol;
+
+    // Pays 1 above the strike; its delta is a point mass the pathwise method misses.
+    let digital = |st: f64| if st > k { 1. } else { 0. };
+    let mc_delta = mc_delta_lr(digital, s, vol, q, r, t, 400_000, 24);
+    let mc_vega = mc_vega_lr(digital, s, vol, q, r, t, 400_000, 24);
+    assert!((mc_delta.price - delta).abs() < 4. * mc_delta.std_error, "{:?} vs {}", mc_delta, delta);
+    assert!((mc_vega.price - vega).abs() < 4. * mc_vega.std_error, "{:?} vs {}", mc_vega, vega);
+}
+
+#[test]
+fn test_likelihood_ratio_delta_is_noisier_than_pathwise_for_a_call() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 100., 0.2, 0., 0.03, 1.);
+    let pathwise = mc_call_delta(s, k, vol, q, r, t, 100_000, 25);
+    let lr = mc_delta_lr(|st: f64| OptionType::Call.payoff(st, k), s, vol, q, r, t, 100_000, 25);
+    let combined = (pathwise.std_error.powi(2) + lr.std_error.powi(2)).sqrt();
+    assert!((pathwise.price - lr.price).abs() < 4. * combined);
+    assert!(lr.std_error > pathwise.std_error);
+}
+
+#[test]
+fn test_model_rho_uses_common_random_numbers() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 100., 0.2, 0., 0.05, 1.);
+    // K T e^{-rT} N(d2) of the Black-Scholes call.
+    let exact = ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (s, k, vol, q) = (tensor(s), tensor(k), tensor(vol), tensor(q));
+        BlackScholesPricingModel::rho(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()[0]
+    });
+    let rho = ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (s, k, vol, q) = (tensor(s), tensor(k), tensor(vol), tensor(q));
+        MonteCarloPricingModel::rho(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()[0]
+    });
+    // The pathwise rho of 500 paths has a standard error near 2.
+    assert!((rho - exact).abs() < 10., "{} vs {}", rho, exact);
+}
+
+#[test]
+fn test_model_gamma_uses_common_random_numbers() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 100., 0.2, 0., 0.05, 1.);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let tensor = |x: f64| math::convert_to_tensor(nd::arr1(&[x]).into_dyn(), ctx);
+        let (s, k, vol, q) = (tensor(s), tensor(k), tensor(vol), tensor(q));
+        let mc = MonteCarloPricingModel::gamma(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()[0];
+        let exact = BlackScholesPricingModel::gamma(OptionType::Call, &s, &k, &vol, &q, r, t).eval(ctx).unwrap()[0];
+        // The second difference of 500 paths has a standard error near 2e-3.
+        assert!((mc - exact).abs() < 1e-2, "{} vs {}", mc, exact);
+    });
+}
@@ -0,0 +1,34 @@
+use autograd::ndarray as nd;
+
+use rquant::error::QuantError;
+use rquant::indicators::moving_average::*;
+
+#[test]
+fn test_sma_small_example() {
+    let prices = nd::arr1(&[1., 2., 3., 4., 5., 9.]).into_dyn();
+    let averages = sma(&prices, 3).unwrap();
+    assert_eq!(averages, nd::arr1(&[2., 3., 4., 6.]).into_dyn());
+    assert_eq!(sma(&prices, 1).unwrap(), prices);
+}
+
+#[test]
+fn test_ema_small_example() {
+    // span 3 gives α = 1/2, seeded with the first price.
+    let prices = nd::arr1(&[10., 12., 11., 15.]).into_dyn();
+    let averages = ema(&prices, 3).unwrap();
+    let expected = [10., 11., 11., 13.];
+    assert_eq!(averages.len(), 4);
+    for (a, e) in averages.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < 1e-14, "{:?}", averages);
+    }
+    assert_eq!(ema(&prices, 1).unwrap(), prices);
+}
+
+#[test]
+fn test_moving_averages_reject_bad_input() {
+    let prices = nd::arr1(&[1., 2., 3.]).into_dyn();
+    assert!(matches!(sma(&prices, 0), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(sma(&prices, 4), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(ema(&prices, 0), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(ema(&nd::Array1::<f64>::zeros(0).into_dyn(), 3), Err(QuantError::InvalidInput(_))));
+}
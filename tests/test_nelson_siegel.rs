@@ -0,0 +1,37 @@
+use autograd::ndarray as nd;
+
+use rquant::fixed_income::nelson_siegel::*;
+
+#[test]
+fn test_fit_recovers_synthetic_parameters() {
+    let truth: NelsonSiegelParams<f64> = NelsonSiegelParams {
+        beta0: 0.05,
+        beta1: -0.02,
+        beta2: 0.015,
+        tau: 2.,
+    };
+    let maturities = nd::arr1(&[0.25, 0.5, 1., 2., 3., 5., 7., 10., 20., 30.]).into_dyn();
+    let yields = maturities.mapv(|t| truth.yield_at(t));
+
+    let fit = fit_nelson_siegel(maturities.view(), yields.view()).unwrap();
+    assert!((fit.beta0 - truth.beta0).abs() < 1e-4, "{:?}", fit);
+    assert!((fit.beta1 - truth.beta1).abs() < 1e-4, "{:?}", fit);
+    assert!((fit.beta2 - truth.beta2).abs() < 1e-3, "{:?}", fit);
+    assert!((fit.tau - truth.tau).abs() < 5e-2, "{:?}", fit);
+    for (&t, &y) in maturities.iter().zip(yields.iter()) {
+        assert!((fit.yield_at(t) - y).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_yield_at_limits() {
+    let params: NelsonSiegelParams<f64> = NelsonSiegelParams {
+        beta0: 0.04,
+        beta1: -0.01,
+        beta2: 0.02,
+        tau: 1.5,
+    };
+    assert!((params.yield_at(0.) - 0.03).abs() < 1e-15);
+    assert!((params.yield_at(1e-8) - 0.03).abs() < 1e-9);
+    assert!((params.yield_at(1e4) - 0.04).abs() < 1e-5);
+}
@@ -0,0 +1,169 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+use autograd::statrs::distribution::{ContinuousCDF, Normal};
+use autograd::tensor_ops as math;
+
+use rquant::error::QuantError;
+use rquant::stats::{normal, sample_correlated_normals};
+
+fn assert_close(actual: &ag::NdArray<f64>, expected: &ag::NdArray<f64>, tol: f64) {
+    assert_eq!(actual.shape(), expected.shape());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < tol, "expected {}, got {}", e, a);
+    }
+}
+
+#[test]
+fn test_pdf_integrates_to_one() {
+    let n = 4001;
+    let (lo, hi): (f64, f64) = (-12., 16.);
+    let dx = (hi - lo) / (n - 1) as f64;
+    let grid = nd::Array1::linspace(lo, hi, n).into_dyn();
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let x = math::convert_to_tensor(grid.clone(), ctx);
+        let density = normal::pdf(&x, 2., 1.5).eval(ctx).unwrap();
+        // Trapezoidal rule.
+        let total = density.iter().fold(0., |acc, d| acc + d) * dx - 0.5 * dx * (density[0] + density[n - 1]);
+        assert!((total - 1.).abs() < 1e-9, "integral {}", total);
+    });
+}
+
+#[test]
+fn test_pdf_is_the_slope_of_the_cdf() {
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let x = math::convert_to_tensor(nd::arr1(&[-2., -0.5, 0., 0.7, 3.]).into_dyn(), ctx);
+        let slope = math::grad(&[normal::cdf(&x, 0.5, 2.)], &[x])[0];
+        let density = normal::pdf(&x, 0.5, 2.);
+        assert_close(&slope.eval(ctx).unwrap(), &density.eval(ctx).unwrap(), 1e-10);
+    });
+}
+
+#[test]
+fn test_standard_pdf_at_zero() {
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let x = math::convert_to_tensor(nd::arr1(&[0., 1.]).into_dyn(), ctx);
+        let density = normal::pdf(&x, 0., 1.).eval(ctx).unwrap();
+        let expected = nd::arr1(&[0.3989422804014327, 0.24197072451914337]).into_dyn();
+        assert_close(&density, &expected, 1e-12);
+    });
+}
+
+#[test]
+fn test_inverse_cdf_at_known_quantiles() {
+    let p = nd::arr1(&[0.001, 0.5, 0.8, 0.95, 0.975, 1e-10]).into_dyn();
+    let expected = nd::arr1(&[
+        -3.090232306167813,
+        0.,
+        0.8416212335729144,
+        1.6448536269514715,
+        1.9599639845400536,
+        -6.361340902404056,
+    ])
+    .into_dyn();
+    assert_close(&normal::inverse_cdf(&p).unwrap(), &expected, 1e-9);
+}
+
+#[test]
+fn test_inverse_cdf_round_trips_through_the_cdf() {
+    let z = nd::arr1(&[-4., -1.3, 0.2, 2.5]).into_dyn();
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let x = math::convert_to_tensor(z.clone(), ctx);
+        let p = normal::cdf(&x, 0., 1.).eval(ctx).unwrap();
+        assert_close(&normal::inverse_cdf(&p).unwrap(), &z, 1e-9);
+    });
+}
+
+#[test]
+fn test_inverse_cdf_rejects_probabilities_outside_the_unit_interval() {
+    for &p in [0f64, 1., -0.5, 1.5].iter() {
+        assert!(matches!(
+            normal::inverse_cdf(&nd::arr1(&[0.5, p]).into_dyn()),
+            Err(QuantError::InvalidInput(_))
+        ));
+    }
+}
+
+#[test]
+fn test_sample_moments_converge_to_the_parameters() {
+    let samples = normal::sample::<f64, _>(&[400, 500], 1.5, 0.4, &mut StdRng::seed_from_u64(17));
+    assert_eq!(samples.shape(), &[400, 500]);
+
+    let n = samples.len() as f64;
+    let mean = samples.iter().fold(0., |acc, x| acc + x) / n;
+    let variance = samples.iter().fold(0., |acc, x| acc + (x - mean) * (x - mean)) / (n - 1.);
+    assert!((mean - 1.5).abs() < 4. * 0.4 / n.sqrt(), "mean {}", mean);
+    assert!((variance - 0.16).abs() < 2e-3, "variance {}", variance);
+}
+
+#[test]
+fn test_sample_is_reproducible_with_a_seed() {
+    let a = normal::sample::<f64, _>(&[3, 4], 0., 1., &mut StdRng::seed_from_u64(5));
+    let b = normal::sample::<f64, _>(&[3, 4], 0., 1., &mut StdRng::seed_from_u64(5));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_correlated_normals_match_the_covariance() {
+    let cov = nd::arr2(&[[0.04, 0.018, -0.01], [0.018, 0.09, 0.0], [-0.01, 0.0, 0.0625]]).into_dyn();
+    let n = 200_000;
+    let samples = sample_correlated_normals(&cov, n, &mut StdRng::seed_from_u64(29)).unwrap();
+    assert_eq!(samples.shape(), &[n, 3]);
+
+    for i in 0..3 {
+        for j in 0..3 {
+            let covariance = (0..n).fold(0., |acc, row| acc + samples[[row, i]] * samples[[row, j]]) / n as f64;
+            assert!((covariance - cov[[i, j]]).abs() < 2e-3, "cov[{}][{}] = {}", i, j, covariance);
+        }
+    }
+}
+
+#[test]
+fn test_correlated_normals_reject_indefinite_matrices() {
+    let cov = nd::arr2(&[[1., 2.], [2., 1.]]).into_dyn();
+    let samples = sample_correlated_normals(&cov, 10, &mut StdRng::seed_from_u64(1));
+    assert_eq!(samples.unwrap_err(), QuantError::NotPositiveDefinite);
+}
+
+fn standard_cdf(x: f64) -> f64 {
+    Normal::new(0., 1.).unwrap().cdf(x)
+}
+
+#[test]
+fn test_bivariate_cdf_at_the_origin() {
+    // M(0, 0; ρ) = 1/4 + asin(ρ) / 2π across all three of the method's regimes.
+    for &rho in [-0.99, -0.95, -0.6, -0.1, 0., 0.2, 0.5, 0.8, 0.93, 0.999].iter() {
+        let expected = 0.25 + f64::asin(rho) / (2. * std::f64::consts::PI);
+        let cdf = normal::bivariate_cdf(0., 0., rho);
+        assert!((cdf - expected).abs() < 1e-12, "rho {}: expected {}, got {}", rho, expected, cdf);
+    }
+}
+
+#[test]
+fn test_bivariate_cdf_marginal_consistency() {
+    let points = [(-1.5, 0.3), (0.4, 0.9), (2., -0.7), (-0.2, -2.5)];
+    for &(x, y) in points.iter() {
+        let independent = normal::bivariate_cdf(x, y, 0.);
+        assert!((independent - standard_cdf(x) * standard_cdf(y)).abs() < 1e-14);
+
+        // M(x, y; ρ) + M(x, -y; -ρ) = N(x), which checks the high correlation branch.
+        for &rho in [0.3, 0.7, 0.95, 0.99].iter() {
+            let total = normal::bivariate_cdf(x, y, rho) + normal::bivariate_cdf(x, -y, -rho);
+            assert!((total - standard_cdf(x)).abs() < 1e-10, "x {}, y {}, rho {}", x, y, rho);
+        }
+    }
+}
+
+#[test]
+fn test_bivariate_cdf_edge_cases() {
+    let (x, y) = (0.6, -0.4);
+    assert!((normal::bivariate_cdf(x, y, 1.) - standard_cdf(y)).abs() < 1e-14);
+    assert!((normal::bivariate_cdf(x, y, -1.) - (standard_cdf(x) - standard_cdf(-y))).abs() < 1e-14);
+    assert_eq!(normal::bivariate_cdf(-0.5, 0.2, -1.), 0.);
+
+    assert_eq!(normal::bivariate_cdf(f64::NEG_INFINITY, y, 0.5), 0.);
+    assert_eq!(normal::bivariate_cdf(x, f64::NEG_INFINITY, -0.5), 0.);
+    assert!((normal::bivariate_cdf(f64::INFINITY, y, 0.5) - standard_cdf(y)).abs() < 1e-15);
+    assert!((normal::bivariate_cdf(x, f64::INFINITY, 0.99) - standard_cdf(x)).abs() < 1e-15);
+}
@@ -0,0 +1,50 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::error::QuantError;
+use rquant::options::black_scholes::*;
+use rquant::options::contract::*;
+use rquant::options::model::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+#[test]
+fn test_contract_prices_like_the_model() {
+    let contract = OptionContract::builder(OptionType::Put)
+        .strike(100.)
+        .maturity(0.5)
+        .dividend_yield(0.02)
+        .build()
+        .unwrap();
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[90., 100., 110.]);
+        let vol = tensor(ctx, &[0.25, 0.2, 0.15]);
+        let k = tensor(ctx, &[100.; 3]);
+        let q = tensor(ctx, &[0.02; 3]);
+
+        let price = contract.price::<BlackScholesPricingModel, _>(&s, &vol, 0.05);
+        let direct = BlackScholesPricingModel::price(OptionType::Put, &s, &k, &vol, &q, 0.05, 0.5);
+        let mut results = ctx.evaluator().push(&price).push(&direct).run().into_iter();
+        assert_eq!(results.next().unwrap().unwrap(), results.next().unwrap().unwrap());
+    });
+}
+
+#[test]
+fn test_builder_defaults_and_validation() {
+    let contract = OptionContract::<f64>::builder(OptionType::Call).strike(50.).maturity(1.).build().unwrap();
+    assert_eq!(contract.dividend_yield, None);
+    assert_eq!(contract.ty, OptionType::Call);
+
+    assert!(matches!(
+        OptionContract::<f64>::builder(OptionType::Call).strike(50.).build(),
+        Err(QuantError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        OptionContract::<f64>::builder(OptionType::Call).strike(-1.).maturity(1.).build(),
+        Err(QuantError::InvalidInput(_))
+    ));
+}
@@ -0,0 +1,48 @@
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+
+use rquant::error::QuantError;
+use rquant::models::ou::*;
+
+#[test]
+fn test_ou_paths_revert_toward_theta() {
+    let (x0, kappa, theta, sigma, t): (f64, f64, f64, f64, f64) = (2., 2., 0.5, 0.3, 2.);
+    let n_paths = 20_000;
+    let paths = simulate_ou(x0, kappa, theta, sigma, t, 20, n_paths, &mut StdRng::seed_from_u64(79));
+    assert_eq!(paths.shape(), &[n_paths, 21]);
+
+    let n = n_paths as f64;
+    let mut previous_gap = (x0 - theta).abs();
+    for &j in [5, 10, 20].iter() {
+        let mean = paths.outer_iter().fold(0., |acc, path| acc + path[j]) / n;
+        let time = t * j as f64 / 20.;
+        let expected = theta + (x0 - theta) * (-kappa * time).exp();
+        let std = sigma * ((1. - (-2. * kappa * time).exp()) / (2. * kappa)).sqrt();
+        assert!((mean - expected).abs() < 4. * std / n.sqrt(), "mean {} vs {}", mean, expected);
+        assert!((mean - theta).abs() < previous_gap);
+        previous_gap = (mean - theta).abs();
+    }
+}
+
+#[test]
+fn test_fit_ou_recovers_parameters() {
+    let (kappa, theta, sigma, dt): (f64, f64, f64, f64) = (1.5, 1., 0.4, 0.1);
+    let n_steps = 10_000;
+    let t = dt * n_steps as f64;
+    let paths = simulate_ou(theta, kappa, theta, sigma, t, n_steps, 1, &mut StdRng::seed_from_u64(80));
+    let series = paths.index_axis(nd::Axis(0), 0).to_owned().into_dyn();
+
+    let params = fit_ou(&series, dt).unwrap();
+    assert!((params.kappa - kappa).abs() < 0.2, "{:?}", params);
+    assert!((params.theta - theta).abs() < 0.05, "{:?}", params);
+    assert!((params.sigma - sigma).abs() < 0.02, "{:?}", params);
+    assert!((params.half_life() - 2f64.ln() / params.kappa).abs() < 1e-15);
+}
+
+#[test]
+fn test_fit_ou_rejects_bad_input() {
+    let explosive = nd::Array1::from((0..50).map(|i| 1.1f64.powi(i)).collect::<Vec<_>>()).into_dyn();
+    assert!(matches!(fit_ou(&explosive, 0.1), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(fit_ou(&nd::arr1(&[1., 2.]).into_dyn(), 0.1), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(fit_ou(&nd::arr1(&[1., 2., 1.5]).into_dyn(), 0.), Err(QuantError::InvalidInput(_))));
+}
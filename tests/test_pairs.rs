@@ -0,0 +1,63 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+
+use rquant::error::QuantError;
+use rquant::models::ou::simulate_ou;
+use rquant::stats::normal;
+use rquant::strategy::pairs::*;
+
+/// A random walk `b` and `a = 10 + 1.5b + ε` with a mean reverting daily ε.
+fn cointegrated_pair(n: usize, seed: u64) -> (ag::NdArray<f64>, ag::NdArray<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let steps = normal::sample(&[n], 0., 1., &mut rng);
+    let noise = simulate_ou(0., 25., 0., 3., (n - 1) as f64 / 252., n - 1, 1, &mut rng);
+    let mut level = 50.;
+    let b = steps
+        .iter()
+        .map(|step| {
+            level += step;
+            level
+        })
+        .collect::<Vec<_>>();
+    let a = b.iter().zip(noise.iter()).map(|(b, e)| 10. + 1.5 * b + e).collect::<Vec<_>>();
+    (nd::Array1::from(a).into_dyn(), nd::Array1::from(b).into_dyn())
+}
+
+#[test]
+fn test_hedge_ratio_of_exact_pair() {
+    let b = nd::arr1(&[1., 2., 4., 3., 5.]).into_dyn();
+    let a = b.mapv(|b: f64| 3. - 0.5 * b);
+    assert!((hedge_ratio(&a, &b).unwrap() + 0.5).abs() < 1e-14);
+}
+
+#[test]
+fn test_spread_zscore_oscillates_around_zero() {
+    let (a, b) = cointegrated_pair(2000, 81);
+    assert!((hedge_ratio(&a, &b).unwrap() - 1.5).abs() < 0.02);
+
+    let window = 60;
+    let zscores = spread_zscore(&a, &b, window).unwrap();
+    assert_eq!(zscores.len(), 2000 - window + 1);
+    let mean = zscores.iter().fold(0., |acc, z| acc + z) / zscores.len() as f64;
+    assert!(mean.abs() < 0.3, "mean z-score {}", mean);
+
+    let crossings = zscores
+        .iter()
+        .zip(zscores.iter().skip(1))
+        .filter(|(z0, z1)| z0.signum() != z1.signum())
+        .count();
+    assert!(crossings > 50, "{} zero crossings", crossings);
+    assert!(zscores.iter().any(|&z| z > 1.5) && zscores.iter().any(|&z| z < -1.5));
+}
+
+#[test]
+fn test_spread_zscore_rejects_bad_input() {
+    let a = nd::arr1(&[1., 2., 3.]).into_dyn();
+    let b = nd::arr1(&[1., 2.]).into_dyn();
+    assert!(matches!(spread_zscore(&a, &b, 2), Err(QuantError::ShapeMismatch { .. })));
+    assert!(matches!(spread_zscore(&a, &a, 1), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(spread_zscore(&a, &a, 4), Err(QuantError::InvalidInput(_))));
+    let constant = nd::arr1(&[2., 2., 2.]).into_dyn();
+    assert!(matches!(hedge_ratio(&a, &constant), Err(QuantError::InvalidInput(_))));
+}
@@ -0,0 +1,82 @@
+use common::black_scholes;
+use rquant::error::QuantError;
+use rquant::numerics::linalg::solve_tridiagonal;
+use rquant::numerics::pde::*;
+use rquant::options::binomial::price_american_put;
+use rquant::options::model::*;
+
+#[path = "common/mod.rs"]
+mod common;
+
+const S_MAX: f64 = 400.;
+
+fn crank_nicolson_put(exercise: Exercise, s: f64, k: f64, vol: f64, q: f64, r: f64, t: f64) -> f64 {
+    let lower = |tau: f64| match exercise {
+        Exercise::European => k * (-r * tau).exp(),
+        Exercise::American => k,
+    };
+    let payoff = |spot: f64| (k - spot).max(0.);
+    price_crank_nicolson(payoff, lower, |_| 0., exercise, s, vol, q, r, t, S_MAX, 400, 200).unwrap()
+}
+
+#[test]
+fn test_tridiagonal_solve() {
+    // [2 1 0; 1 2 1; 0 1 2] x = [4 8 8] has the solution [1 2 3].
+    let x = solve_tridiagonal(&[0., 1., 1.], &[2., 2., 2.], &[1., 1., 0.], &[4., 8., 8.]).unwrap();
+    assert!(x.iter().zip([1., 2., 3.].iter()).all(|(a, b)| (a - b).abs() < 1e-12), "{:?}", x);
+    assert!(solve_tridiagonal(&[0., 1.], &[0., 1.], &[1., 0.], &[1., 1.]).is_none());
+}
+
+#[test]
+fn test_european_put_matches_black_scholes() {
+    for &(s, vol, q) in [(100., 0.2, 0.), (90., 0.3, 0.02), (120., 0.25, 0.01)].iter() {
+        let (k, r, t) = (100., 0.05, 1.);
+        let price = crank_nicolson_put(Exercise::European, s, k, vol, q, r, t);
+        let analytic = black_scholes(OptionType::Put, s, k, vol, q, r, t);
+        assert!((price - analytic).abs() < 5e-3, "{} vs {}", price, analytic);
+    }
+}
+
+#[test]
+fn test_european_call_matches_black_scholes_by_parity() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 100., 0.2, 0.01, 0.05, 1.);
+    let upper = |tau: f64| S_MAX * (-q * tau).exp() - k * (-r * tau).exp();
+    let payoff = |spot: f64| (spot - k).max(0.);
+    let call = price_crank_nicolson(payoff, |_| 0., upper, Exercise::European, s, vol, q, r, t, S_MAX, 400, 200);
+    let call = call.unwrap();
+    let put = black_scholes(OptionType::Put, s, k, vol, q, r, t);
+    let parity = put + s * (-q * t).exp() - k * (-r * t).exp();
+    assert!((call - parity).abs() < 5e-3, "{} vs {}", call, parity);
+}
+
+#[test]
+fn test_american_put_matches_binomial() {
+    for &(s, vol) in [(100., 0.2), (90., 0.3), (110., 0.25)].iter() {
+        let (k, q, r, t) = (100., 0., 0.05, 1.);
+        let price = crank_nicolson_put(Exercise::American, s, k, vol, q, r, t);
+        let binomial = price_american_put(s, k, vol, q, r, t, 2000);
+        assert!((price - binomial).abs() < 1e-2, "{} vs {}", price, binomial);
+        // Early exercise is worth something on a put with a positive rate.
+        let european = crank_nicolson_put(Exercise::European, s, k, vol, q, r, t);
+        assert!(price > european + 1e-2, "{} vs {}", price, european);
+    }
+}
+
+#[test]
+fn test_american_put_is_above_its_exercise_value() {
+    let price = crank_nicolson_put(Exercise::American, 70., 100., 0.2, 0., 0.05, 1.);
+    assert!(price >= 30. - 1e-9, "{}", price);
+}
+
+#[test]
+fn test_invalid_inputs() {
+    let payoff = |spot: f64| (100. - spot).max(0.);
+    let solve = |vol: f64, s: f64, t: f64, n_spot: usize, n_time: usize| {
+        price_crank_nicolson(payoff, |_| 100., |_| 0., Exercise::European, s, vol, 0., 0.05, t, S_MAX, n_spot, n_time)
+    };
+    assert!(matches!(solve(0., 100., 1., 100, 100), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(solve(0.2, 100., 0., 100, 100), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(solve(0.2, 500., 1., 100, 100), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(solve(0.2, 100., 1., 1, 100), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(solve(0.2, 100., 1., 100, 0), Err(QuantError::InvalidInput(_))));
+}
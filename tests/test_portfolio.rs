@@ -0,0 +1,174 @@
+use autograd::ndarray as nd;
+
+use rquant::error::QuantError;
+use rquant::risk::portfolio::*;
+
+#[test]
+fn test_two_asset_portfolio_variance() {
+    // 20% and 30% volatility with a correlation of 0.5, held 60/40.
+    let (s1, s2, rho): (f64, f64, f64) = (0.2, 0.3, 0.5);
+    let cov = nd::arr2(&[[s1 * s1, rho * s1 * s2], [rho * s1 * s2, s2 * s2]]).into_dyn();
+    let weights = nd::arr1(&[0.6, 0.4]).into_dyn();
+
+    let expected: f64 = 0.36 * 0.04 + 0.16 * 0.09 + 2. * 0.6 * 0.4 * 0.5 * 0.2 * 0.3;
+    assert!((portfolio_variance(&weights, &cov).unwrap() - expected).abs() < 1e-15);
+    assert!((portfolio_volatility(&weights, &cov).unwrap() - expected.sqrt()).abs() < 1e-15);
+}
+
+#[test]
+fn test_portfolio_variance_rejects_mismatched_shapes() {
+    let cov = nd::Array2::<f64>::eye(3).into_dyn();
+    let weights = nd::arr1(&[0.5, 0.5]).into_dyn();
+    assert_eq!(
+        portfolio_variance(&weights, &cov).unwrap_err(),
+        QuantError::ShapeMismatch { expected: vec![2, 2], got: vec![3, 3] }
+    );
+}
+
+#[test]
+fn test_risk_neutral_optimum_concentrates_in_best_asset() {
+    let vols: [f64; 3] = [0.1, 0.15, 0.25];
+    let corr = nd::arr2(&[[1., 0.3, 0.2], [0.3, 1., 0.4], [0.2, 0.4, 1.]]);
+    let cov = nd::Array2::from_shape_fn((3, 3), |(i, j)| vols[i] * vols[j] * corr[[i, j]]).into_dyn();
+    let expected_returns = nd::arr1(&[0.05, 0.08, 0.12]).into_dyn();
+
+    let weights = optimize_mean_variance(&expected_returns, &cov, 0.).unwrap();
+    assert!((weights.sum() - 1.).abs() < 1e-12);
+    assert!(weights[2] > 0.98, "{:?}", weights);
+}
+
+#[test]
+fn test_two_asset_mean_variance_optimum() {
+    let (s1, s2, rho): (f64, f64, f64) = (0.15, 0.25, 0.3);
+    let (c11, c12, c22) = (s1 * s1, rho * s1 * s2, s2 * s2);
+    let cov = nd::arr2(&[[c11, c12], [c12, c22]]).into_dyn();
+    let expected_returns = nd::arr1(&[0.06, 0.1]).into_dyn();
+    let lambda = 3.;
+
+    // The first order condition of the budget constrained utility.
+    let w1 = (0.06 - 0.1 + lambda * (c22 - c12)) / (lambda * (c11 + c22 - 2. * c12));
+    let weights = optimize_mean_variance(&expected_returns, &cov, lambda).unwrap();
+    assert!((weights[0] - w1).abs() < 1e-6, "{:?}", weights);
+    assert!((weights[1] - (1. - w1)).abs() < 1e-6, "{:?}", weights);
+}
+
+fn three_asset_covariance() -> nd::ArrayD<f64> {
+    let vols: [f64; 3] = [0.1, 0.15, 0.25];
+    let corr = nd::arr2(&[[1., 0.3, -0.2], [0.3, 1., 0.4], [-0.2, 0.4, 1.]]);
+    nd::Array2::from_shape_fn((3, 3), |(i, j)| vols[i] * vols[j] * corr[[i, j]]).into_dyn()
+}
+
+#[test]
+fn test_component_vars_sum_to_total_var() {
+    let cov = three_asset_covariance();
+    let weights = nd::arr1(&[0.5, 0.3, 0.2]).into_dyn();
+    let components = component_var(&weights, &cov, 0.99).unwrap();
+    let total = 2.3263478740408408 * portfolio_volatility(&weights, &cov).unwrap();
+    assert_eq!(components.shape(), &[3]);
+    assert!((components.sum() - total).abs() < 1e-12, "{:?} vs {}", components, total);
+
+    // A lone asset carries the whole VaR.
+    let single = component_var(&nd::arr1(&[0., 1., 0.]).into_dyn(), &cov, 0.99).unwrap();
+    assert!((single[1] - 2.3263478740408408 * 0.15).abs() < 1e-12, "{:?}", single);
+    assert!(single[0] == 0. && single[2] == 0., "{:?}", single);
+}
+
+#[test]
+fn test_short_position_hedges_correlated_risk() {
+    let cov = three_asset_covariance();
+    let weights = nd::arr1(&[0., 1., -0.3]).into_dyn();
+    let components = component_var(&weights, &cov, 0.95).unwrap();
+    assert!(components[2] < 0., "{:?}", components);
+}
+
+#[test]
+fn test_incremental_var() {
+    let cov = three_asset_covariance();
+    let weights = nd::arr1(&[0.5, 0.3, 0.2]).into_dyn();
+    assert_eq!(incremental_var(&weights, &cov, 0.99, 1, 0.).unwrap(), 0.);
+
+    // A small trade moves the VaR by its weight change times the marginal VaR.
+    let delta = 1e-6;
+    let components = component_var(&weights, &cov, 0.99).unwrap();
+    let marginal = components[2] / weights[2];
+    let incremental = incremental_var(&weights, &cov, 0.99, 2, delta).unwrap();
+    assert!((incremental - marginal * delta).abs() < 1e-10, "{} vs {}", incremental, marginal * delta);
+
+    // Selling the whole position leaves the VaR of the rest.
+    let rest = nd::arr1(&[0.5, 0.3, 0.]).into_dyn();
+    let expected = 2.3263478740408408
+        * (portfolio_volatility(&rest, &cov).unwrap() - portfolio_volatility(&weights, &cov).unwrap());
+    assert!((incremental_var(&weights, &cov, 0.99, 2, -0.2).unwrap() - expected).abs() < 1e-12);
+}
+
+#[test]
+fn test_var_decomposition_rejects_bad_input() {
+    let cov = three_asset_covariance();
+    let weights = nd::arr1(&[0.5, 0.3, 0.2]).into_dyn();
+    assert!(matches!(component_var(&weights, &cov, 1.), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(
+        component_var(&nd::arr1(&[0.; 3]).into_dyn(), &cov, 0.99),
+        Err(QuantError::InvalidInput(_))
+    ));
+    assert!(matches!(incremental_var(&weights, &cov, 0.99, 3, 0.1), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(
+        component_var(&nd::arr1(&[0.5, 0.5]).into_dyn(), &cov, 0.99),
+        Err(QuantError::ShapeMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_efficient_frontier_is_convex() {
+    let cov = three_asset_covariance();
+    let expected_returns = nd::arr1(&[0.05, 0.08, 0.12]).into_dyn();
+    let frontier = efficient_frontier(&expected_returns, &cov, 20).unwrap();
+    assert_eq!(frontier.len(), 20);
+
+    for (risk, ret, weights) in frontier.iter() {
+        assert!((weights.sum() - 1.).abs() < 1e-12, "{:?}", weights);
+        assert!(((weights * &expected_returns).sum() - ret).abs() < 1e-12);
+        assert!((portfolio_volatility(weights, &cov).unwrap() - risk).abs() < 1e-12);
+    }
+
+    // The frontier starts at the minimum variance portfolio and its risk rises ever
+    // faster with the return.
+    let min_variance = min_variance_portfolio(&cov).unwrap();
+    assert!((&frontier[0].2 - &min_variance).iter().all(|d| d.abs() < 1e-12));
+    let risks = frontier.iter().map(|point| point.0).collect::<Vec<_>>();
+    assert!(risks.windows(2).all(|w| w[1] > w[0]), "{:?}", risks);
+    assert!(risks.windows(3).all(|w| w[2] - 2. * w[1] + w[0] > 0.), "{:?}", risks);
+}
+
+#[test]
+fn test_tangency_portfolio_has_the_best_sharpe_ratio() {
+    let cov = three_asset_covariance();
+    let expected_returns = nd::arr1(&[0.05, 0.08, 0.12]).into_dyn();
+    let risk_free = 0.02;
+    let tangency = tangency_portfolio(&expected_returns, &cov, risk_free).unwrap();
+    assert!((tangency.sum() - 1.).abs() < 1e-12);
+
+    let sharpe = |weights: &nd::ArrayD<f64>| {
+        let ret = (weights * &expected_returns).sum();
+        (ret - risk_free) / portfolio_volatility(weights, &cov).unwrap()
+    };
+    let best = sharpe(&tangency);
+    let frontier = efficient_frontier(&expected_returns, &cov, 50).unwrap();
+    assert!(frontier.iter().all(|point| sharpe(&point.2) <= best + 1e-12));
+    assert!(matches!(tangency_portfolio(&expected_returns, &cov, 0.2), Err(QuantError::InvalidInput(_))));
+}
+
+#[test]
+fn test_efficient_frontier_rejects_bad_input() {
+    let cov = three_asset_covariance();
+    let expected_returns = nd::arr1(&[0.05, 0.08, 0.12]).into_dyn();
+    assert!(matches!(efficient_frontier(&expected_returns, &cov, 1), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(
+        efficient_frontier(&nd::arr1(&[0.1; 3]).into_dyn(), &cov, 10),
+        Err(QuantError::InvalidInput(_))
+    ));
+    let singular = nd::Array2::<f64>::ones((3, 3)).into_dyn();
+    assert!(matches!(
+        efficient_frontier(&expected_returns, &singular, 10),
+        Err(QuantError::NotPositiveDefinite)
+    ));
+}
@@ -0,0 +1,50 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+use rquant::options::power::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+#[test]
+fn test_power_one_is_vanilla() {
+    let (r, t): (f64, f64) = (0.04, 0.75);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[90., 100., 110.]);
+        let k = tensor(ctx, &[100.; 3]);
+        let vol = tensor(ctx, &[0.2, 0.3, 0.4]);
+        let q = tensor(ctx, &[0.01; 3]);
+        for &ty in [OptionType::Call, OptionType::Put].iter() {
+            let power = price_power(ty, &s, &k, &vol, &q, r, t, 1.);
+            let vanilla = BlackScholesPricingModel::price(ty, &s, &k, &vol, &q, r, t);
+            let difference = (power - vanilla).eval(ctx).unwrap();
+            assert!(difference.iter().all(|d| d.abs() < 1e-10), "{:?}", difference);
+        }
+    });
+}
+
+#[test]
+fn test_squared_power_parity() {
+    let (r, t, sigma, dividend): (f64, f64, f64, f64) = (0.03, 1., 0.25, 0.02);
+    let spot = [95., 105.];
+    let strike = [9000., 11000.];
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &spot);
+        let k = tensor(ctx, &strike);
+        let vol = tensor(ctx, &[sigma; 2]);
+        let q = tensor(ctx, &[dividend; 2]);
+        let call = price_power(OptionType::Call, &s, &k, &vol, &q, r, t, 2.);
+        let put = price_power(OptionType::Put, &s, &k, &vol, &q, r, t, 2.);
+        let difference = (call - put).eval(ctx).unwrap();
+        for i in 0..2 {
+            // C - P = e^{-rT} (E[S_T²] - K), E[S_T²] = S² e^{(2(r - q) + σ²)T}.
+            let second_moment = spot[i] * spot[i] * ((2. * (r - dividend) + sigma * sigma) * t).exp();
+            let expected = (-r * t).exp() * (second_moment - strike[i]);
+            assert!((difference[i] - expected).abs() < 1e-8, "expected {}, got {}", expected, difference[i]);
+        }
+    });
+}
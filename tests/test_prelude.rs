@@ -0,0 +1,29 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::prelude::*;
+
+#[test]
+fn test_prelude_prices_a_contract() {
+    let contract = OptionContract::builder(OptionType::Call)
+        .strike(100.)
+        .maturity(1.)
+        .build()
+        .unwrap();
+    let expected = bs_call_price(100., 100., 1., 0.2, 0.05);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = math::convert_to_tensor(nd::arr1(&[100.]).into_dyn(), ctx);
+        let vol = math::convert_to_tensor(nd::arr1(&[0.2]).into_dyn(), ctx);
+        let price = contract.price::<BlackScholesPricingModel, _>(&s, &vol, 0.05).eval(ctx).unwrap();
+        assert!((price[0] - expected).abs() < 1e-10);
+
+        let zero = math::convert_to_tensor(nd::arr1(&[0.]).into_dyn(), ctx);
+        let half = normal::cdf(&zero, 0., 1.).eval(ctx).unwrap();
+        assert!((half[0] - 0.5).abs() < 1e-12);
+    });
+
+    let missing: Result<OptionContract<f64>, QuantError> = OptionContract::builder(OptionType::Put).build();
+    assert!(matches!(missing, Err(QuantError::InvalidInput(_))));
+    assert_eq!(FlatCurve::new(0.05).zero_rate(2.), 0.05);
+}
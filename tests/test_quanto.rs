@@ -0,0 +1,60 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+use rquant::options::quanto::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+#[test]
+fn test_zero_correlation_is_foreign_asset_call() {
+    let (r_domestic, r_foreign, t): (f64, f64, f64) = (0.05, 0.02, 0.5);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[90., 100., 110.]);
+        let k = tensor(ctx, &[100.; 3]);
+        let vol = tensor(ctx, &[0.2, 0.3, 0.4]);
+        let fx_vol = tensor(ctx, &[0.1; 3]);
+        let rho = tensor(ctx, &[0.; 3]);
+        let q = tensor(ctx, &[0.01; 3]);
+
+        // Without correlation the stock drifts at r_f - q and pays off in domestic units.
+        let quanto = price_quanto_call(&s, &k, &vol, &fx_vol, &rho, &q, r_domestic, r_foreign, t);
+        let foreign_q = tensor(ctx, &[0.01 + r_domestic - r_foreign; 3]);
+        let foreign = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &foreign_q, r_domestic, t);
+        let difference = (quanto - foreign).eval(ctx).unwrap();
+        assert!(difference.iter().all(|d| d.abs() < 1e-12), "{:?}", difference);
+
+        // With equal rates it is the vanilla call.
+        let quanto = price_quanto_call(&s, &k, &vol, &fx_vol, &rho, &q, r_domestic, r_domestic, t);
+        let vanilla = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &q, r_domestic, t);
+        let difference = (quanto - vanilla).eval(ctx).unwrap();
+        assert!(difference.iter().all(|d| d.abs() < 1e-12), "{:?}", difference);
+    });
+}
+
+#[test]
+fn test_positive_correlation_lowers_the_drift() {
+    let (r_domestic, r_foreign, t): (f64, f64, f64) = (0.03, 0.01, 1.);
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100.]);
+        let k = tensor(ctx, &[100.]);
+        let vol = tensor(ctx, &[0.25]);
+        let fx_vol = tensor(ctx, &[0.12]);
+        let q = tensor(ctx, &[0.]);
+        let price = |correlation: f64| {
+            let rho = tensor(ctx, &[correlation]);
+            price_quanto_call(&s, &k, &vol, &fx_vol, &rho, &q, r_domestic, r_foreign, t).eval(ctx).unwrap()[0]
+        };
+        let (negative, zero, positive) = (price(-0.5), price(0.), price(0.5));
+        assert!(negative > zero && zero > positive);
+
+        // The adjusted dividend yield q + r_d - r_f + ρσ_Sσ_X = 0.035.
+        let adjusted_q = tensor(ctx, &[0.035]);
+        let expected = BlackScholesPricingModel::price(OptionType::Call, &s, &k, &vol, &adjusted_q, r_domestic, t);
+        assert!((positive - expected.eval(ctx).unwrap()[0]).abs() < 1e-12);
+    });
+}
@@ -0,0 +1,45 @@
+use autograd::ndarray as nd;
+
+use rquant::error::QuantError;
+use rquant::timeseries::returns::*;
+
+fn prices() -> nd::ArrayD<f64> {
+    nd::arr1(&[100., 110., 99., 99., 123.75]).into_dyn()
+}
+
+#[test]
+fn test_simple_returns() {
+    let returns = simple_returns(&prices());
+    let expected = [0.1, -0.1, 0., 0.25];
+    assert_eq!(returns.shape(), &[4]);
+    for (r, e) in returns.iter().zip(expected.iter()) {
+        assert!((r - e).abs() < 1e-14);
+    }
+}
+
+#[test]
+fn test_log_returns_agree_with_simple_returns() {
+    let log = log_returns(&prices()).unwrap();
+    let simple = simple_returns(&prices());
+    assert_eq!(log.shape(), simple.shape());
+    for (l, s) in log.iter().zip(simple.iter()) {
+        assert!((l - s.ln_1p()).abs() < 1e-14);
+        // A log return never exceeds the simple return.
+        assert!(*l <= *s);
+    }
+    // Log returns sum to the log return over the whole series.
+    assert!((log.sum() - (123.75f64 / 100.).ln()).abs() < 1e-14);
+}
+
+#[test]
+fn test_log_returns_reject_non_positive_prices() {
+    let prices = nd::arr1(&[100., 0., 101.]).into_dyn();
+    assert!(matches!(log_returns::<f64>(&prices), Err(QuantError::InvalidInput(_))));
+}
+
+#[test]
+fn test_short_series_have_no_returns() {
+    let prices = nd::arr1(&[100f64]).into_dyn();
+    assert_eq!(simple_returns(&prices).len(), 0);
+    assert_eq!(log_returns(&prices).unwrap().len(), 0);
+}
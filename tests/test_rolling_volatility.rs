@@ -0,0 +1,32 @@
+use autograd::ndarray as nd;
+
+use rquant::error::QuantError;
+use rquant::timeseries::volatility::*;
+
+#[test]
+fn test_constant_returns_have_zero_volatility() {
+    // Alternating ±1% returns followed by a constant 0.5% return.
+    let returns = nd::Array1::from_shape_fn(20, |i| match i {
+        0..=9 if i % 2 == 0 => 0.01f64,
+        0..=9 => -0.01,
+        _ => 0.005,
+    })
+    .into_dyn();
+    let vol = rolling_volatility(&returns, 4, 252.).unwrap();
+    assert_eq!(vol.shape(), &[17]);
+
+    // Four alternating returns have a sample variance of 4e-4 / 3.
+    let expected = (4e-4f64 / 3.).sqrt() * 252f64.sqrt();
+    assert!((vol[0] - expected).abs() < 1e-12);
+    for &sigma in vol.iter().skip(10) {
+        assert!(sigma < 1e-12);
+    }
+}
+
+#[test]
+fn test_window_must_fit_the_series() {
+    let returns = nd::arr1(&[0.01f64, 0.02, -0.01]).into_dyn();
+    assert_eq!(rolling_volatility(&returns, 3, 1.).unwrap().len(), 1);
+    assert!(matches!(rolling_volatility(&returns, 4, 1.), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(rolling_volatility(&returns, 1, 1.), Err(QuantError::InvalidInput(_))));
+}
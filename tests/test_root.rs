@@ -0,0 +1,46 @@
+use rquant::error::QuantError;
+use rquant::numerics::root::*;
+
+fn cubic(x: f64) -> f64 {
+    x * x * x - 2. * x - 5.
+}
+
+// The real root of x³ - 2x - 5.
+const CUBIC_ROOT: f64 = 2.0945514815423265;
+
+#[test]
+fn test_newton_finds_polynomial_roots() {
+    let root = newton(cubic, |x| 3. * x * x - 2., 2., 1e-14, 50).unwrap();
+    assert!((root - CUBIC_ROOT).abs() < 1e-12, "got {}", root);
+
+    let root = newton(|x: f64| x * x - 2., |x| 2. * x, 1., 1e-14, 50).unwrap();
+    assert!((root - 2f64.sqrt()).abs() < 1e-14, "got {}", root);
+}
+
+#[test]
+fn test_bisect_and_brent_find_polynomial_roots() {
+    let root = bisect(cubic, 2., 3., 1e-12).unwrap();
+    assert!((root - CUBIC_ROOT).abs() < 1e-12, "got {}", root);
+
+    let root = brent(cubic, 2., 3., 1e-12).unwrap();
+    assert!((root - CUBIC_ROOT).abs() < 1e-12, "got {}", root);
+
+    // The bracket may be given in either order.
+    let root = brent(|x: f64| x * x - 2., 2., 0., 1e-14).unwrap();
+    assert!((root - 2f64.sqrt()).abs() < 1e-14, "got {}", root);
+}
+
+#[test]
+fn test_brent_converges_where_newton_diverges() {
+    // Newton's steps on atan overshoot and grow without bound from |x0| > 1.39.
+    assert_eq!(newton(f64::atan, |x| 1. / (1. + x * x), 2., 1e-12, 100), Err(QuantError::NoConvergence));
+
+    let root = brent(f64::atan, -2., 3., 1e-12).unwrap();
+    assert!(root.abs() < 1e-12, "got {}", root);
+}
+
+#[test]
+fn test_bracketing_solvers_reject_same_signs() {
+    assert_eq!(bisect(cubic, 3., 4., 1e-12), Err(QuantError::NoBracket));
+    assert_eq!(brent(cubic, 3., 4., 1e-12), Err(QuantError::NoBracket));
+}
@@ -0,0 +1,37 @@
+use autograd::ndarray as nd;
+
+use rquant::error::QuantError;
+use rquant::indicators::rsi::*;
+
+#[test]
+fn test_rsi_of_monotone_series() {
+    let rising = nd::Array1::from((0..30).map(|i| 100. + i as f64).collect::<Vec<_>>()).into_dyn();
+    let index = rsi(&rising, 14).unwrap();
+    assert_eq!(index.len(), 30 - 14);
+    assert!(index.iter().all(|&x| x == 100.));
+
+    let falling = rising.mapv(|p: f64| 250. - p);
+    assert!(rsi(&falling, 14).unwrap().iter().all(|&x| x == 0.));
+}
+
+#[test]
+fn test_rsi_small_example() {
+    // Changes +2, -1, +1, -2 with period 2: averages (1, 0.5), (1, 0.25) and then (0.5, 1.125).
+    let prices = nd::arr1(&[10., 12., 11., 12., 10.]).into_dyn();
+    let index = rsi(&prices, 2).unwrap();
+    let expected = [100. - 100. / 3., 80., 100. - 100. / (1. + 0.5 / 1.125)];
+    assert_eq!(index.len(), 3);
+    for (x, e) in index.iter().zip(expected.iter()) {
+        assert!((x - e).abs() < 1e-12, "{:?}", index);
+    }
+}
+
+#[test]
+fn test_rsi_is_bounded_and_rejects_bad_input() {
+    let prices = nd::Array1::from((0..100).map(|i| 50. + 10. * (i as f64 * 0.3).sin()).collect::<Vec<_>>()).into_dyn();
+    assert!(rsi(&prices, 14).unwrap().iter().all(|&x| (0. ..=100.).contains(&x)));
+
+    let short = nd::arr1(&[1., 2., 3.]).into_dyn();
+    assert!(matches!(rsi(&short, 0), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(rsi(&short, 3), Err(QuantError::InvalidInput(_))));
+}
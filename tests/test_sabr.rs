@@ -0,0 +1,34 @@
+use rquant::models::sabr::*;
+
+#[test]
+fn test_sabr_smile_reference_values() {
+    let (f, t, alpha, beta, rho, nu): (f64, f64, f64, f64, f64, f64) = (0.05, 2., 0.04, 0.5, -0.3, 0.4);
+    let expected = [
+        (0.03, 0.2560881474622336),
+        (0.04, 0.21108992271337448),
+        (0.05, 0.18217098593326278),
+        (0.06, 0.16801976639636584),
+        (0.08, 0.1681407042739516),
+    ];
+    for &(k, vol) in expected.iter() {
+        let implied = sabr_implied_vol(f, k, t, alpha, beta, rho, nu);
+        assert!((implied - vol).abs() < 1e-12, "vol {} vs {} at strike {}", implied, vol, k);
+    }
+}
+
+#[test]
+fn test_sabr_is_continuous_at_the_money() {
+    let (f, t, alpha, beta, rho, nu): (f64, f64, f64, f64, f64, f64) = (0.05, 2., 0.04, 0.5, -0.3, 0.4);
+    let atm = sabr_implied_vol(f, f, t, alpha, beta, rho, nu);
+    assert!(atm.is_finite());
+    for &bump in [1e-9, -1e-9, 1e-6, -1e-6].iter() {
+        let near = sabr_implied_vol(f, f * (1. + bump), t, alpha, beta, rho, nu);
+        assert!((near - atm).abs() < 1e-6, "vol {} vs atm {}", near, atm);
+    }
+}
+
+#[test]
+fn test_sabr_without_vol_of_vol_is_lognormal() {
+    let vol: f64 = sabr_implied_vol(100., 120., 1., 0.2, 1., 0., 1e-9);
+    assert!((vol - 0.2).abs() < 1e-8);
+}
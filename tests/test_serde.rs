@@ -0,0 +1,31 @@
+#![cfg(feature = "serde")]
+
+use autograd::ndarray as nd;
+
+use rquant::options::contract::*;
+use rquant::options::model::*;
+
+#[test]
+fn test_contract_round_trips_through_json() {
+    let contract = OptionContract::builder(OptionType::Put)
+        .strike(95.5f64)
+        .maturity(0.25)
+        .dividend_yield(0.015)
+        .build()
+        .unwrap();
+    let json = serde_json::to_string(&contract).unwrap();
+    let parsed: OptionContract<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, contract);
+}
+
+#[test]
+fn test_implied_vol_report_round_trips_through_json() {
+    let report = ImpliedVolReport {
+        loss: nd::arr2(&[[1e-9f64, 2e-3], [0., 5e-7]]).into_dyn(),
+        converged: nd::arr2(&[[true, false], [true, true]]).into_dyn(),
+    };
+    let json = serde_json::to_string(&report).unwrap();
+    let parsed: ImpliedVolReport<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.loss, report.loss);
+    assert_eq!(parsed.converged, report.converged);
+}
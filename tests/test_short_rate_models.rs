@@ -0,0 +1,72 @@
+use autograd as ag;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+
+use rquant::models::cir::*;
+use rquant::models::vasicek::*;
+
+/// The mean and standard error of the pathwise discount factors exp(-∫r dt), with
+/// the integral taken by the trapezoidal rule.
+fn discount_factors(paths: &ag::NdArray<f64>, t: f64) -> (f64, f64) {
+    let n_steps = paths.shape()[1] - 1;
+    let dt = t / n_steps as f64;
+    let samples = paths
+        .outer_iter()
+        .map(|path| {
+            let integral = (0..n_steps).fold(0., |acc, j| acc + 0.5 * (path[j] + path[j + 1]) * dt);
+            (-integral).exp()
+        })
+        .collect::<Vec<_>>();
+    let n = samples.len() as f64;
+    let mean = samples.iter().fold(0., |acc, x| acc + x) / n;
+    let variance = samples.iter().fold(0., |acc, x| acc + (x - mean) * (x - mean)) / (n - 1.);
+    (mean, (variance / n).sqrt())
+}
+
+#[test]
+fn test_vasicek_mean_reverts_toward_theta() {
+    let (r0, kappa, theta, sigma, t): (f64, f64, f64, f64, f64) = (0.01, 1.5, 0.05, 0.02, 3.);
+    let n_paths = 50_000;
+    let paths = simulate_vasicek(r0, kappa, theta, sigma, t, 30, n_paths, &mut StdRng::seed_from_u64(35));
+    assert_eq!(paths.shape(), &[n_paths, 31]);
+
+    let n = n_paths as f64;
+    for &j in [10, 20, 30].iter() {
+        let mean = paths.outer_iter().fold(0., |acc, path| acc + path[j]) / n;
+        let time = t * j as f64 / 30.;
+        let expected = theta + (r0 - theta) * (-kappa * time).exp();
+        let std = sigma * ((1. - (-2. * kappa * time).exp()) / (2. * kappa)).sqrt();
+        assert!((mean - expected).abs() < 4. * std / n.sqrt(), "mean {} vs {}", mean, expected);
+    }
+}
+
+#[test]
+fn test_vasicek_bond_price_matches_simulated_discounting() {
+    let (r0, kappa, theta, sigma, t): (f64, f64, f64, f64, f64) = (0.03, 0.5, 0.05, 0.01, 2.);
+    let paths = simulate_vasicek(r0, kappa, theta, sigma, t, 200, 20_000, &mut StdRng::seed_from_u64(36));
+    let (mean, std_error) = discount_factors(&paths, t);
+    let price = vasicek_bond_price(r0, kappa, theta, sigma, t);
+    assert!((mean - price).abs() < 4. * std_error + 1e-5, "simulated {} vs {}", mean, price);
+}
+
+#[test]
+fn test_cir_rates_stay_non_negative() {
+    // 2κθ < σ², so the continuous process reaches zero and the Euler steps overshoot.
+    let paths = simulate_cir(0.01, 0.3, 0.02, 0.3, 5., 250, 2_000, &mut StdRng::seed_from_u64(37));
+    assert!(paths.iter().all(|&r| r >= 0.));
+    assert!(paths.iter().any(|&r| r == 0.));
+}
+
+#[test]
+fn test_cir_bond_price_matches_simulated_discounting() {
+    let (r0, kappa, theta, sigma, t): (f64, f64, f64, f64, f64) = (0.04, 0.8, 0.05, 0.1, 2.);
+    let paths = simulate_cir(r0, kappa, theta, sigma, t, 400, 20_000, &mut StdRng::seed_from_u64(38));
+    let (mean, std_error) = discount_factors(&paths, t);
+    let price = cir_bond_price(r0, kappa, theta, sigma, t);
+    assert!((mean - price).abs() < 4. * std_error + 2e-4, "simulated {} vs {}", mean, price);
+}
+
+#[test]
+fn test_cir_bond_price_short_maturity_limit() {
+    let price: f64 = cir_bond_price(0.04, 0.8, 0.05, 0.1, 1e-6);
+    assert!((price - (-0.04f64 * 1e-6).exp()).abs() < 1e-12);
+}
@@ -0,0 +1,50 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+use rquant::options::simple::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+#[test]
+fn test_scalar_api_matches_tensor_api() {
+    let (spot, strike, time, vol, rate) = (105., 100., 0.75, 0.25, 0.03);
+    for ty in [OptionType::Call, OptionType::Put] {
+        let (price, greeks) = match ty {
+            OptionType::Call => (
+                bs_call_price(spot, strike, time, vol, rate),
+                bs_call_greeks(spot, strike, time, vol, rate),
+            ),
+            OptionType::Put => (
+                bs_put_price(spot, strike, time, vol, rate),
+                bs_put_greeks(spot, strike, time, vol, rate),
+            ),
+        };
+        ag::run(|ctx: &mut ag::Context<f64>| {
+            let s = tensor(ctx, &[spot]);
+            let k = tensor(ctx, &[strike]);
+            let sigma = tensor(ctx, &[vol]);
+            let q = tensor(ctx, &[0.]);
+            let value = |x: ag::Tensor<f64>| x.eval(ctx).unwrap()[0];
+            type M = BlackScholesPricingModel;
+            assert_eq!(price, value(M::price(ty, &s, &k, &sigma, &q, rate, time)));
+            assert!((greeks.delta - value(M::delta(ty, &s, &k, &sigma, &q, rate, time))).abs() < 1e-12);
+            assert!((greeks.gamma - value(M::gamma(ty, &s, &k, &sigma, &q, rate, time))).abs() < 1e-9);
+            assert!((greeks.vega - value(M::vega(ty, &s, &k, &sigma, &q, rate, time))).abs() < 1e-9);
+            assert!((greeks.theta - value(M::theta(ty, &s, &k, &sigma, &q, rate, time))).abs() < 1e-6);
+            assert!((greeks.rho - value(M::rho(ty, &s, &k, &sigma, &q, rate, time))).abs() < 1e-9);
+        });
+    }
+}
+
+#[test]
+fn test_scalar_put_call_parity() {
+    let (spot, strike, time, vol, rate): (f64, f64, f64, f64, f64) = (95., 100., 2., 0.3, 0.05);
+    let parity = spot - strike * (-rate * time).exp();
+    let difference = bs_call_price(spot, strike, time, vol, rate) - bs_put_price(spot, strike, time, vol, rate);
+    assert!((difference - parity).abs() < 1e-10);
+}
@@ -0,0 +1,41 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::stats::special;
+
+fn assert_close(actual: &ag::NdArray<f64>, expected: &ag::NdArray<f64>, tol: f64) {
+    assert_eq!(actual.shape(), expected.shape());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < tol, "expected {}, got {}", e, a);
+    }
+}
+
+#[test]
+fn test_erf_and_erfc_known_values() {
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let x = math::convert_to_tensor(nd::arr1(&[0.5, 1., -2., 0., 3.]).into_dyn(), ctx);
+        let erf = nd::arr1(&[0.5204998778130465, 0.8427007929497149, -0.9953222650189527, 0., 0.9999779095030014]).into_dyn();
+        let erfc = nd::arr1(&[0.4795001221869535, 0.15729920705028513, 1.9953222650189528, 1., 2.2090496998585438e-05]).into_dyn();
+        assert_close(&special::erf(&x).eval(ctx).unwrap(), &erf, 1e-14);
+        assert_close(&special::erfc(&x).eval(ctx).unwrap(), &erfc, 1e-14);
+    });
+}
+
+#[test]
+fn test_erf_gradient_matches_finite_difference() {
+    let h = 1e-5;
+    let points = nd::arr1(&[-1.5, -0.2, 0., 0.8, 2.]).into_dyn();
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let x = math::convert_to_tensor(points.clone(), ctx);
+        let analytic = math::grad(&[special::erf(&x)], &[x])[0].eval(ctx).unwrap();
+
+        let up = special::erf(&(x + h)).eval(ctx).unwrap();
+        let down = special::erf(&(x - h)).eval(ctx).unwrap();
+        let numeric = (up - down) / (2. * h);
+        assert_close(&analytic, &numeric, 1e-8);
+
+        let slope = math::grad(&[special::erfc(&x)], &[x])[0].eval(ctx).unwrap();
+        assert_close(&slope, &analytic.mapv(|g| -g), 1e-12);
+    });
+}
@@ -0,0 +1,130 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+use autograd::tensor_ops as math;
+
+use rquant::error::QuantError;
+use rquant::options::black_scholes::*;
+use rquant::options::model::*;
+use rquant::options::strategy::*;
+
+fn tensor<'g>(g: &'g ag::Graph<f64>, xs: &[f64]) -> ag::Tensor<'g, f64> {
+    math::convert_to_tensor(nd::arr1(xs).into_dyn(), g)
+}
+
+#[test]
+fn test_long_straddle_payoff_is_v_shaped() {
+    let straddle = Strategy::straddle(100f64, 0.5).unwrap();
+    assert_eq!(straddle.payoff_at(100.), 0.);
+    assert_eq!(straddle.payoff_at(85.), 15.);
+    assert_eq!(straddle.payoff_at(115.), 15.);
+    assert!(straddle.payoff_at(80.) > straddle.payoff_at(90.));
+    assert!(straddle.payoff_at(120.) > straddle.payoff_at(110.));
+}
+
+#[test]
+fn test_strangle_is_flat_between_its_strikes() {
+    let strangle = Strategy::strangle(90f64, 110., 0.5).unwrap();
+    assert_eq!(strangle.payoff_at(95.), 0.);
+    assert_eq!(strangle.payoff_at(105.), 0.);
+    assert_eq!(strangle.payoff_at(80.), 10.);
+    assert_eq!(strangle.payoff_at(125.), 15.);
+}
+
+#[test]
+fn test_spread_and_condor_payoffs_are_capped() {
+    let spread = Strategy::bull_call_spread(95f64, 105., 1.).unwrap();
+    assert_eq!(spread.payoff_at(90.), 0.);
+    assert_eq!(spread.payoff_at(100.), 5.);
+    assert_eq!(spread.payoff_at(105.), 10.);
+    assert_eq!(spread.payoff_at(1000.), 10.);
+
+    let condor = Strategy::iron_condor([80f64, 90., 110., 120.], 1.).unwrap();
+    assert_eq!(condor.payoff_at(100.), 0.);
+    assert_eq!(condor.payoff_at(85.), -5.);
+    assert_eq!(condor.payoff_at(50.), -10.);
+    assert_eq!(condor.payoff_at(200.), -10.);
+}
+
+#[test]
+fn test_strategy_price_sums_its_legs() {
+    let (r, t): (f64, f64) = (0.03, 0.75);
+    let straddle = Strategy::straddle(100., t).unwrap();
+    let spread = Strategy::bull_call_spread(95., 105., t).unwrap();
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[90., 100., 110.]);
+        let vol = tensor(ctx, &[0.2; 3]);
+        let q = tensor(ctx, &[0.; 3]);
+        let strike = |k: f64| tensor(ctx, &[k; 3]);
+        let (k95, k100, k105) = (strike(95.), strike(100.), strike(105.));
+
+        let call = |k| BlackScholesPricingModel::price(OptionType::Call, &s, k, &vol, &q, r, t);
+        let put = BlackScholesPricingModel::price(OptionType::Put, &s, &k100, &vol, &q, r, t);
+        let straddle_difference = straddle.price::<BlackScholesPricingModel, _>(&s, &vol, r) - (call(&k100) + put);
+        let spread_difference = spread.price::<BlackScholesPricingModel, _>(&s, &vol, r) - (call(&k95) - call(&k105));
+
+        let results = ctx.evaluator().push(&straddle_difference).push(&spread_difference).run();
+        for difference in results.into_iter() {
+            assert!(difference.unwrap().iter().all(|d| d.abs() < 1e-10));
+        }
+    });
+}
+
+#[test]
+fn test_constructors_reject_unordered_strikes() {
+    assert!(matches!(Strategy::bull_call_spread(105f64, 95., 1.), Err(QuantError::InvalidInput(_))));
+    assert!(Strategy::iron_condor([80f64, 110., 90., 120.], 1.).is_err());
+    assert!(Strategy::straddle(100f64, 0.).is_err());
+}
+
+#[test]
+fn test_straddle_struck_at_zero_d1_is_delta_neutral() {
+    let (r, t, sigma): (f64, f64, f64) = (0.04, 0.5, 0.25);
+    // A call's delta is N(d1) and a put's N(d1) - 1, which cancel where d1 = 0.
+    let strike = 100. * ((r + sigma * sigma / 2.) * t).exp();
+    let straddle = Strategy::straddle(strike, t).unwrap();
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[100.]);
+        let vol = tensor(ctx, &[sigma]);
+        let greeks = straddle.greeks::<BlackScholesPricingModel, _>(&s, &vol, r);
+        let results = ctx.evaluator().push(&greeks.delta).push(&greeks.gamma).push(&greeks.vega).run();
+        let values = results.into_iter().map(|x| x.unwrap()[0]).collect::<Vec<_>>();
+        assert!(values[0].abs() < 1e-10, "net delta {}", values[0]);
+        assert!(values[1] > 0. && values[2] > 0.);
+    });
+}
+
+#[test]
+fn test_short_legs_reverse_the_greeks() {
+    let (r, t): (f64, f64) = (0.02, 1.);
+    let spread = Strategy::bull_call_spread(95., 105., t).unwrap();
+    let mut short = spread.clone();
+    for leg in short.legs.iter_mut() {
+        leg.quantity = -leg.quantity;
+    }
+
+    ag::run(|ctx: &mut ag::Context<f64>| {
+        let s = tensor(ctx, &[90., 100., 110.]);
+        let vol = tensor(ctx, &[0.3; 3]);
+        let q = tensor(ctx, &[0.; 3]);
+        let (low, high) = (tensor(ctx, &[95.; 3]), tensor(ctx, &[105.; 3]));
+
+        let long = spread.greeks::<BlackScholesPricingModel, _>(&s, &vol, r);
+        let short = short.greeks::<BlackScholesPricingModel, _>(&s, &vol, r);
+        let expected_gamma = BlackScholesPricingModel::gamma(OptionType::Call, &s, &low, &vol, &q, r, t)
+            - BlackScholesPricingModel::gamma(OptionType::Call, &s, &high, &vol, &q, r, t);
+
+        let mut evaluator = ctx.evaluator();
+        evaluator.push(&long.delta).push(&short.delta).push(&long.gamma).push(&expected_gamma);
+        evaluator.push(&long.vega).push(&short.vega).push(&long.theta).push(&short.theta);
+        let values = evaluator.run().into_iter().map(|x| x.unwrap()).collect::<Vec<_>>();
+        // Short deltas, vegas and thetas are the negated long ones; the long gamma is
+        // the gamma of the long call less that of the short call.
+        for (pair, sign) in values.chunks(2).zip([-1f64, 1., -1., -1.].iter()) {
+            assert!(pair[0].iter().zip(pair[1].iter()).all(|(a, b)| (a - sign * b).abs() < 1e-10));
+        }
+        // The long call dominates the delta of a bull spread.
+        assert!(values[0].iter().all(|&delta| delta > 0.));
+    });
+}
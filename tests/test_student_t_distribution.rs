@@ -0,0 +1,57 @@
+use autograd as ag;
+use autograd::ndarray as nd;
+
+use rquant::error::QuantError;
+use rquant::stats::{normal, student_t};
+
+fn assert_close(actual: &ag::NdArray<f64>, expected: &ag::NdArray<f64>, tol: f64) {
+    assert_eq!(actual.shape(), expected.shape());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < tol, "expected {}, got {}", e, a);
+    }
+}
+
+#[test]
+fn test_quantile_matches_t_tables() {
+    let p = nd::arr1(&[0.95, 0.975, 0.99]).into_dyn();
+    let five = nd::arr1(&[2.0150483733330235, 2.5705818356363148, 3.3649299989072178]).into_dyn();
+    assert_close(&student_t::quantile(&p, 5.).unwrap(), &five, 1e-9);
+
+    let p = nd::arr1(&[0.975, 0.99]).into_dyn();
+    let thirty = nd::arr1(&[2.0422724563012379, 2.457261542400591]).into_dyn();
+    assert_close(&student_t::quantile(&p, 30.).unwrap(), &thirty, 1e-9);
+}
+
+#[test]
+fn test_quantile_approaches_the_normal_with_many_degrees_of_freedom() {
+    let p = nd::arr1(&[0.01, 0.3, 0.975]).into_dyn();
+    let z = normal::inverse_cdf(&p).unwrap();
+    let t5 = student_t::quantile(&p, 5.).unwrap();
+    let t30 = student_t::quantile(&p, 30.).unwrap();
+    let t10000 = student_t::quantile(&p, 10_000.).unwrap();
+    for i in 0..3 {
+        assert!((t30[i] - z[i]).abs() < (t5[i] - z[i]).abs());
+    }
+    assert_close(&t10000, &z, 1e-3);
+}
+
+#[test]
+fn test_pdf_and_cdf_reference_values() {
+    let density = student_t::pdf(&nd::arr1(&[0.]).into_dyn(), 5.);
+    assert_close(&density, &nd::arr1(&[0.37960668982249443]).into_dyn(), 1e-12);
+    let density = student_t::pdf(&nd::arr1(&[1.2]).into_dyn(), 4.);
+    assert_close(&density, &nd::arr1(&[0.17385372358466919]).into_dyn(), 1e-12);
+
+    let p = student_t::cdf(&nd::arr1(&[1.5]).into_dyn(), 5.);
+    assert_close(&p, &nd::arr1(&[0.90304815987876326]).into_dyn(), 1e-12);
+    let p = student_t::cdf(&nd::arr1(&[-0.7]).into_dyn(), 3.);
+    assert_close(&p, &nd::arr1(&[0.26716349915238186]).into_dyn(), 1e-12);
+}
+
+#[test]
+fn test_quantile_rejects_invalid_inputs() {
+    let p = nd::arr1(&[0.5]).into_dyn();
+    assert!(matches!(student_t::quantile(&p, 0.), Err(QuantError::InvalidInput(_))));
+    let p = nd::arr1(&[1.]).into_dyn();
+    assert!(matches!(student_t::quantile(&p, 5.), Err(QuantError::InvalidInput(_))));
+}
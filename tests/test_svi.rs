@@ -0,0 +1,53 @@
+use autograd::ndarray as nd;
+
+use rquant::error::QuantError;
+use rquant::models::svi::*;
+
+#[test]
+fn test_fit_recovers_synthetic_parameters() {
+    let truth: SviParams<f64> = SviParams {
+        a: 0.03,
+        b: 0.2,
+        rho: 0.3,
+        m: -0.13,
+        sigma: 0.27,
+    };
+    let k = nd::Array1::from_shape_fn(21, |i| -0.5 + 0.05 * i as f64).into_dyn();
+    let w = k.mapv(|k| truth.total_variance(k));
+
+    let fit = fit_svi(k.view(), w.view()).unwrap();
+    assert!((fit.a - truth.a).abs() < 2e-3, "{:?}", fit);
+    assert!((fit.b - truth.b).abs() < 2e-3, "{:?}", fit);
+    assert!((fit.rho - truth.rho).abs() < 2e-3, "{:?}", fit);
+    assert!((fit.m - truth.m).abs() < 2e-3, "{:?}", fit);
+    assert!((fit.sigma - truth.sigma).abs() < 2e-3, "{:?}", fit);
+    for (&k, &w) in k.iter().zip(w.iter()) {
+        assert!((fit.total_variance(k) - w).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_implied_vol_at_strike() {
+    let params: SviParams<f64> = SviParams {
+        a: 0.02,
+        b: 0.1,
+        rho: -0.4,
+        m: 0.,
+        sigma: 0.2,
+    };
+    let t = 0.5;
+    // At the money the total variance is a + bσ.
+    let atm = params.implied_vol(100., 100., t);
+    assert!((atm - (0.04f64 / t).sqrt()).abs() < 1e-14);
+    // A negative skew makes low strikes more volatile than high ones.
+    assert!(params.implied_vol(80., 100., t) > params.implied_vol(120., 100., t));
+}
+
+#[test]
+fn test_fit_rejects_invalid_quotes() {
+    let k = nd::arr1(&[-0.2, -0.1, 0., 0.1, 0.2]).into_dyn();
+    let w = nd::arr1(&[0.05, 0.04, 0.03, 0.035]).into_dyn();
+    assert!(matches!(fit_svi(k.view(), w.view()), Err(QuantError::ShapeMismatch { .. })));
+    let w = nd::arr1(&[0.05, 0.04, 0., 0.035, 0.04]).into_dyn();
+    assert!(matches!(fit_svi(k.view(), w.view()), Err(QuantError::InvalidInput(_))));
+}
@@ -0,0 +1,41 @@
+use rquant::error::QuantError;
+use rquant::fixed_income::curve::*;
+use rquant::fixed_income::swap::*;
+
+fn schedule() -> Vec<f64> {
+    (0..11).map(|i| 0.5 * i as f64).collect()
+}
+
+#[test]
+fn test_swap_at_par_rate_has_zero_pv() {
+    let times = vec![1., 2., 3., 5.];
+    let discount = ZeroCurve::new(times.clone(), times.iter().map(|&t| (-0.03 * t).exp()).collect()).unwrap();
+    let forward = ZeroCurve::new(times.clone(), times.iter().map(|&t| (-(0.035 + 0.002 * t) * t).exp()).collect())
+        .unwrap();
+
+    let rate = par_swap_rate(&schedule(), &discount, &forward).unwrap();
+    let pv = price_vanilla_swap(1e6, rate, &schedule(), &discount, &forward).unwrap();
+    assert!(pv.abs() < 1e-8, "pv {}", pv);
+
+    // Paying more than par costs the fixed payer the annuity on the difference.
+    let above = price_vanilla_swap(1e6, rate + 0.001, &schedule(), &discount, &forward).unwrap();
+    assert!(above < 0.);
+}
+
+#[test]
+fn test_single_curve_par_rate() {
+    // Off one curve the floating leg telescopes to 1 - P(T).
+    let curve = FlatCurve::new(0.04);
+    let annuity = schedule().windows(2).fold(0., |acc, p| acc + (p[1] - p[0]) * curve.discount_factor(p[1]));
+    let expected = (1. - curve.discount_factor(5.)) / annuity;
+    assert!((par_swap_rate(&schedule(), &curve, &curve).unwrap() - expected).abs() < 1e-15);
+    // A continuously compounded 4% is a semiannually compounded 2(e^0.02 - 1).
+    assert!((expected - 2. * (0.02f64.exp() - 1.)).abs() < 1e-14);
+}
+
+#[test]
+fn test_swap_rejects_bad_schedules() {
+    let curve = FlatCurve::new(0.04);
+    assert!(matches!(price_vanilla_swap(1., 0.04, &[1.], &curve, &curve), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(par_swap_rate(&[0., 1., 1.], &curve, &curve), Err(QuantError::InvalidInput(_))));
+}
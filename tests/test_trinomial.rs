@@ -0,0 +1,63 @@
+use common::black_scholes;
+use rquant::options::binomial::*;
+use rquant::options::model::*;
+use rquant::options::trinomial::*;
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[test]
+fn test_european_converges_to_black_scholes() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 105., 0.2, 0., 0.05, 1.);
+    for &ty in [OptionType::Call, OptionType::Put].iter() {
+        let analytic = black_scholes(ty, s, k, vol, q, r, t);
+        let errors = [50, 200, 800]
+            .iter()
+            .map(|&steps| (price_trinomial(ty, Exercise::European, s, k, vol, q, r, t, steps) - analytic).abs())
+            .collect::<Vec<_>>();
+        assert!(errors[0] > errors[1] && errors[1] > errors[2], "{:?}", errors);
+        assert!(errors[2] < 5e-3, "{:?}", errors);
+    }
+}
+
+#[test]
+fn test_oscillates_less_than_binomial() {
+    // Without dividends the american call is european, so the binomial lattice prices it too.
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 105., 0.2, 0., 0.05, 1.);
+    let max_change = |prices: Vec<f64>| {
+        prices
+            .iter()
+            .zip(prices.iter().skip(1))
+            .fold(0f64, |change, (a, b)| change.max((a - b).abs()))
+    };
+    let trinomial = max_change(
+        (200..211)
+            .map(|steps| price_trinomial(OptionType::Call, Exercise::European, s, k, vol, q, r, t, steps))
+            .collect(),
+    );
+    let binomial = max_change((200..211).map(|steps| price_american_call(s, k, vol, q, r, t, steps)).collect());
+    assert!(trinomial < binomial / 2., "{} vs {}", trinomial, binomial);
+}
+
+#[test]
+fn test_american_put_matches_binomial() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 100., 0.2, 0., 0.05, 1.);
+    let trinomial = price_trinomial(OptionType::Put, Exercise::American, s, k, vol, q, r, t, 1000);
+    let binomial = price_american_put(s, k, vol, q, r, t, 2000);
+    assert!((trinomial - binomial).abs() < 5e-3, "{} vs {}", trinomial, binomial);
+    let european = price_trinomial(OptionType::Put, Exercise::European, s, k, vol, q, r, t, 1000);
+    assert!(trinomial > european + 0.1, "{} vs {}", trinomial, european);
+}
+
+#[test]
+fn test_american_call_early_exercise_with_dividends() {
+    let (s, k, vol, q, r, t): (f64, f64, f64, f64, f64, f64) = (100., 100., 0.3, 0.08, 0.03, 1.);
+    let american = price_trinomial(OptionType::Call, Exercise::American, s, k, vol, q, r, t, 500);
+    let european = price_trinomial(OptionType::Call, Exercise::European, s, k, vol, q, r, t, 500);
+    assert!(american > european + 0.1, "{} vs {}", american, european);
+
+    // Without dividends early exercise of a call is never optimal.
+    let american = price_trinomial(OptionType::Call, Exercise::American, s, k, vol, 0., r, t, 500);
+    let european = price_trinomial(OptionType::Call, Exercise::European, s, k, vol, 0., r, t, 500);
+    assert!((american - european).abs() < 1e-12, "{} vs {}", american, european);
+}
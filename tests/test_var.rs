@@ -0,0 +1,109 @@
+use autograd::ndarray as nd;
+use autograd::rand::{rngs::StdRng, SeedableRng};
+
+use rquant::error::QuantError;
+use rquant::risk::var::*;
+use rquant::stats::normal;
+
+/// The returns -10%, -9.9%, ..., 10% in a scrambled order.
+fn uniform_returns() -> nd::ArrayD<f64> {
+    let n = 201;
+    nd::Array1::from_shape_fn(n, |i| -0.1 + 0.001 * ((i * 37) % n) as f64).into_dyn()
+}
+
+#[test]
+fn test_historical_var_of_a_known_distribution() {
+    let returns = uniform_returns();
+    assert!((historical_var(&returns, 0.95).unwrap() - 0.09).abs() < 1e-12);
+    assert!((historical_var(&returns, 0.99).unwrap() - 0.098).abs() < 1e-12);
+}
+
+#[test]
+fn test_historical_var_interpolates_between_order_statistics() {
+    let returns = nd::arr1(&[0., -3., 2., -1.]).into_dyn();
+    // The 10% quantile sits 30% of the way from -3 to -1.
+    assert!((historical_var(&returns, 0.9).unwrap() - 2.4).abs() < 1e-12);
+}
+
+#[test]
+fn test_historical_var_rejects_invalid_inputs() {
+    let returns = uniform_returns();
+    assert!(matches!(historical_var(&returns, 1.), Err(QuantError::InvalidInput(_))));
+    let empty = nd::ArrayD::<f64>::zeros(vec![0]);
+    assert!(matches!(historical_var(&empty, 0.95), Err(QuantError::InvalidInput(_))));
+    // A missing observation shows up as NaN.
+    let gappy = nd::arr1(&[0.01, f64::NAN, -0.02]).into_dyn();
+    assert!(matches!(historical_var(&gappy, 0.95), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(expected_shortfall(&gappy, 0.95), Err(QuantError::InvalidInput(_))));
+}
+
+#[test]
+fn test_parametric_var_multiplier() {
+    let var: f64 = parametric_var(0., 0.02, 0.975, 1.).unwrap();
+    assert!((var - 1.959963984540054 * 0.02).abs() < 1e-12);
+
+    // Ten periods scale the mean by 10 and the volatility by √10.
+    let var: f64 = parametric_var(0.001, 0.02, 0.99, 10.).unwrap();
+    let expected = 2.3263478740408408 * 0.02 * 10f64.sqrt() - 0.01;
+    assert!((var - expected).abs() < 1e-12);
+}
+
+#[test]
+fn test_student_t_var_has_fatter_tails() {
+    let normal: f64 = parametric_var(0., 0.02, 0.99, 1.).unwrap();
+    let fat = parametric_var_student_t(0., 0.02, 0.99, 1., 4.).unwrap();
+    let thin = parametric_var_student_t(0., 0.02, 0.99, 1., 1000.).unwrap();
+    assert!(fat > normal);
+    assert!((thin - normal).abs() < 1e-4);
+    assert!(matches!(
+        parametric_var_student_t(0., 0.02, 0.99, 1., 2.),
+        Err(QuantError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_expected_shortfall_is_at_least_var() {
+    let samples = normal::sample::<f64, _>(&[5_000], 0.0005, 0.015, &mut StdRng::seed_from_u64(44));
+    for &confidence in [0.9, 0.95, 0.975, 0.99, 0.999].iter() {
+        let var = historical_var(&samples, confidence).unwrap();
+        let es = expected_shortfall(&samples, confidence).unwrap();
+        assert!(es >= var, "es {} < var {} at {}", es, var, confidence);
+    }
+
+    let returns = uniform_returns();
+    // The returns at or below -9% average to -9.5%.
+    assert!((expected_shortfall(&returns, 0.95).unwrap() - 0.095).abs() < 1e-12);
+}
+
+#[test]
+fn test_parametric_expected_shortfall_matches_the_normal_formula() {
+    let es: f64 = parametric_expected_shortfall(0., 0.02, 0.975, 1.).unwrap();
+    assert!((es - 2.3378027922014173 * 0.02).abs() < 1e-10);
+
+    let es: f64 = parametric_expected_shortfall(0.001, 0.02, 0.99, 4.).unwrap();
+    assert!((es - (2.665214220345808 * 0.02 * 2. - 0.004)).abs() < 1e-10);
+    assert!(es > parametric_var(0.001, 0.02, 0.99, 4.).unwrap());
+}
+
+#[test]
+fn test_cornish_fisher_without_higher_moments_is_normal_var() {
+    for &(mean, std, confidence) in [(0., 0.02, 0.975), (0.001, 0.015, 0.99), (-0.002, 0.03, 0.9)].iter() {
+        let var: f64 = cornish_fisher_var(mean, std, 0., 0., confidence).unwrap();
+        assert_eq!(var, parametric_var(mean, std, confidence, 1.).unwrap());
+    }
+    assert_eq!(cornish_fisher_quantile(-1.5f64, 0., 0.), -1.5);
+    assert!(matches!(cornish_fisher_var(0., 0.02, 0., 0., 1.), Err(QuantError::InvalidInput(_))));
+}
+
+#[test]
+fn test_cornish_fisher_fattens_the_left_tail() {
+    let z = -2.3263478740408408;
+    let adjusted: f64 = cornish_fisher_quantile(z, -0.5, 3.);
+    assert!((adjusted - -3.301284492180553).abs() < 1e-12, "{}", adjusted);
+
+    let normal: f64 = parametric_var(0., 0.02, 0.99, 1.).unwrap();
+    let skewed = cornish_fisher_var(0., 0.02, -0.5, 0., 0.99).unwrap();
+    let fat = cornish_fisher_var(0., 0.02, 0., 3., 0.99).unwrap();
+    assert!(skewed > normal && fat > normal, "{} {} {}", skewed, fat, normal);
+    assert!((cornish_fisher_var(0., 0.02, -0.5, 3., 0.99).unwrap() - 0.02 * -adjusted).abs() < 1e-12);
+}
@@ -0,0 +1,105 @@
+use autograd::ndarray as nd;
+
+use rquant::error::QuantError;
+use rquant::options::vol_surface::*;
+
+fn surface() -> VolSurface<f64> {
+    let strikes = [80., 90., 100., 110., 120.];
+    let maturities = [0.25, 0.5, 1., 2.];
+    let vols = nd::Array2::from_shape_fn((5, 4), |(i, j)| {
+        let moneyness: f64 = (strikes[i] / 100f64).ln();
+        0.2 + 0.3 * moneyness * moneyness - 0.05 * moneyness + 0.01 * j as f64
+    })
+    .into_dyn();
+    VolSurface::new(&strikes, &maturities, vols.view()).unwrap()
+}
+
+#[test]
+fn test_grid_points_return_stored_vols() {
+    let surface = surface();
+    let strikes = surface.strikes().to_vec();
+    let maturities = surface.maturities().to_vec();
+    for &k in strikes.iter() {
+        for (j, &t) in maturities.iter().enumerate() {
+            let moneyness: f64 = (k / 100f64).ln();
+            let expected = 0.2 + 0.3 * moneyness * moneyness - 0.05 * moneyness + 0.01 * j as f64;
+            assert_eq!(surface.vol_at(k, t), expected);
+        }
+    }
+}
+
+#[test]
+fn test_total_variance_is_linear_between_maturities() {
+    let surface = surface();
+    let (v0, v1) = (surface.vol_at(100., 0.5), surface.vol_at(100., 1.));
+    let vol = surface.vol_at(100., 0.75);
+    let expected = (v0 * v0 * 0.5 + v1 * v1 * 1.) / 2.;
+    assert!((vol * vol * 0.75 - expected).abs() < 1e-14);
+    assert!(v0 < vol && vol < v1);
+}
+
+#[test]
+fn test_queries_outside_the_grid_are_clamped() {
+    let surface = surface();
+    assert_eq!(surface.vol_at(50., 1.), surface.vol_at(80., 1.));
+    assert_eq!(surface.vol_at(200., 1.), surface.vol_at(120., 1.));
+    assert_eq!(surface.vol_at(95., 0.01), surface.vol_at(95., 0.25));
+    assert_eq!(surface.vol_at(95., 10.), surface.vol_at(95., 2.));
+    assert!(surface.vol_at(0., 0.).is_finite());
+}
+
+#[test]
+fn test_forward_vol() {
+    let surface = surface();
+    let (v1, v2) = (surface.vol_at(100., 0.5), surface.vol_at(100., 2.));
+    let forward = surface.forward_vol(100., 0.5, 2.).unwrap();
+    assert!((forward * forward * 1.5 - (v2 * v2 * 2. - v1 * v1 * 0.5)).abs() < 1e-14);
+    assert!(forward > v2);
+    assert!(surface.forward_vol(100., 2., 0.5).is_err());
+
+    // Vols that fall fast enough with maturity imply a negative forward variance.
+    let vols = nd::arr2(&[[0.4, 0.2], [0.4, 0.2]]).into_dyn();
+    let inverted = VolSurface::new(&[90., 110.], &[0.5, 1.], vols.view()).unwrap();
+    assert!(matches!(inverted.forward_vol(100., 0.5, 1.), Err(QuantError::InvalidInput(_))));
+}
+
+#[test]
+fn test_new_rejects_mismatched_grid() {
+    let vols = nd::Array2::from_elem((2, 3), 0.2).into_dyn();
+    assert!(matches!(
+        VolSurface::new(&[90., 110.], &[0.5, 1.], vols.view()),
+        Err(QuantError::ShapeMismatch { .. })
+    ));
+    let vols = nd::Array2::from_elem((2, 2), 0.2).into_dyn();
+    assert!(matches!(
+        VolSurface::new(&[110., 90.], &[0.5, 1.], vols.view()),
+        Err(QuantError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_term_vol_curve_interpolates_total_variance() {
+    let curve = TermVolCurve::new(&[0.5, 1., 2.], &[0.3, 0.25, 0.22]).unwrap();
+    assert_eq!(curve.maturities(), &[0.5, 1., 2.]);
+    for (&t, &vol) in [0.5, 1., 2.].iter().zip([0.3, 0.25, 0.22].iter()) {
+        assert!((curve.rms_vol(t) - vol).abs() < 1e-15);
+    }
+    let (v1, v2) = (0.25f64.powi(2), 0.22f64.powi(2) * 2.);
+    assert!((curve.variance_to(1.5) - 0.5 * (v1 + v2)).abs() < 1e-15);
+    assert!((curve.rms_vol(0.25) - 0.3).abs() < 1e-15);
+    // The last interval's forward variance carries on beyond the last pillar.
+    assert!((curve.variance_to(3.) - (v2 + (v2 - v1))).abs() < 1e-15);
+
+    let flat = FlatVolCurve::new(0.2);
+    assert!((flat.variance_to(2.) - 0.08).abs() < 1e-15);
+    assert_eq!(flat.rms_vol(5.), 0.2);
+}
+
+#[test]
+fn test_term_vol_curve_rejects_bad_pillars() {
+    assert!(matches!(TermVolCurve::new(&[1., 2.], &[0.2]), Err(QuantError::ShapeMismatch { .. })));
+    assert!(matches!(TermVolCurve::new(&[2., 1.], &[0.2, 0.2]), Err(QuantError::InvalidInput(_))));
+    assert!(matches!(TermVolCurve::new(&[1., 2.], &[0.2, 0.]), Err(QuantError::InvalidInput(_))));
+    // σ²T falls from 0.09 to 0.08.
+    assert!(matches!(TermVolCurve::new(&[1., 2.], &[0.3, 0.2]), Err(QuantError::InvalidInput(_))));
+}
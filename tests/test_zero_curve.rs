@@ -0,0 +1,108 @@
+use rquant::error::QuantError;
+use rquant::fixed_income::curve::*;
+
+fn true_discount_factor(t: f64) -> f64 {
+    (-(0.02 + 0.005 * t) * t).exp()
+}
+
+#[test]
+fn test_bootstrap_recovers_annual_pillars() {
+    let instruments = (1..6)
+        .map(|year| {
+            let bond = Instrument::Bond {
+                maturity: year as f64,
+                coupon_rate: 0.04,
+                periods_per_year: 1,
+                price: 0.,
+            };
+            let price = bond
+                .cash_flows()
+                .iter()
+                .fold(0., |pv, &(t, cf)| pv + cf * true_discount_factor(t));
+            Instrument::Bond {
+                maturity: year as f64,
+                coupon_rate: 0.04,
+                periods_per_year: 1,
+                price,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let curve = bootstrap_zero_curve(&instruments).unwrap();
+    for (&t, &df) in curve.times().iter().zip(curve.discount_factors().iter()) {
+        assert!((df - true_discount_factor(t)).abs() < 1e-12, "df({}) = {}", t, df);
+    }
+    for instrument in instruments.iter() {
+        assert!((curve.present_value(instrument) - instrument.price()).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_bootstrap_reprices_semiannual_par_yields() {
+    let instruments = [(0.5, 0.030), (1., 0.032), (2., 0.035), (3., 0.037), (5., 0.04), (10., 0.043)]
+        .iter()
+        .map(|&(maturity, rate)| Instrument::ParYield {
+            maturity,
+            rate,
+            periods_per_year: 2,
+        })
+        .collect::<Vec<_>>();
+
+    let curve = bootstrap_zero_curve(&instruments).unwrap();
+    for instrument in instruments.iter() {
+        let pv: f64 = curve.present_value(instrument);
+        assert!((pv - 1.).abs() < 1e-12, "pv {}", pv);
+    }
+}
+
+#[test]
+fn test_discount_factor_is_log_linear_between_pillars() {
+    let curve = ZeroCurve::new(vec![1., 3.], vec![0.97, 0.88]).unwrap();
+    let mid: f64 = curve.discount_factor(2.);
+    assert!((mid - (0.97f64 * 0.88).sqrt()).abs() < 1e-14);
+    assert!((curve.discount_factor(0.5) - 0.97f64.sqrt()).abs() < 1e-14);
+    assert!((curve.zero_rate(6.) - curve.zero_rate(3.)).abs() < 1e-14);
+    assert_eq!(curve.discount_factor(0.), 1.);
+}
+
+#[test]
+fn test_bootstrap_rejects_unordered_maturities() {
+    let instruments = [
+        Instrument::ParYield { maturity: 2., rate: 0.03, periods_per_year: 1 },
+        Instrument::ParYield { maturity: 1., rate: 0.03, periods_per_year: 1 },
+    ];
+    assert!(matches!(bootstrap_zero_curve(&instruments), Err(QuantError::InvalidInput(_))));
+}
+
+#[test]
+fn test_zero_rate_spline_passes_through_pillar_rates() {
+    let times = vec![0.5, 1., 2., 5., 10.];
+    let curve = ZeroCurve::new(times.clone(), times.iter().map(|&t| true_discount_factor(t)).collect()).unwrap();
+    let spline = curve.zero_rate_spline();
+    for &t in times.iter() {
+        assert!((spline.eval(t) - curve.zero_rate(t)).abs() < 1e-12);
+    }
+    // The zero rates are linear in time, which the spline follows closely inside.
+    assert!((spline.eval(3.) - (0.02 + 0.005 * 3.)).abs() < 1e-3);
+    assert_eq!(spline.eval(20.), spline.eval(10.));
+}
+
+#[test]
+fn test_discount_curves_share_an_interface() {
+    fn forward_discount<C: DiscountCurve<f64>>(curve: &C, t0: f64, t1: f64) -> f64 {
+        curve.discount_factor(t1) / curve.discount_factor(t0)
+    }
+
+    let flat = FlatCurve::new(0.03);
+    assert!((forward_discount(&flat, 1., 3.) - (-0.06f64).exp()).abs() < 1e-15);
+    assert_eq!(DiscountCurve::zero_rate(&flat, 2.), 0.03);
+    assert_eq!(flat.instantaneous_rate(7.), 0.03);
+
+    let curve = ZeroCurve::new(vec![1., 3.], vec![0.97, 0.88]).unwrap();
+    let first = -0.97f64.ln();
+    let second = (0.97f64.ln() - 0.88f64.ln()) / 2.;
+    assert!((curve.instantaneous_rate(0.5) - first).abs() < 1e-15);
+    assert!((curve.instantaneous_rate(1.) - second).abs() < 1e-15);
+    assert!((curve.instantaneous_rate(5.) - curve.zero_rate(3.)).abs() < 1e-15);
+    assert!((forward_discount(&curve, 1., 3.) - (-2. * second).exp()).abs() < 1e-15);
+}